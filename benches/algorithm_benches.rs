@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, Criterion, SamplingMode};
 use flyconomy::{
-    algorithms::calculate_interest_score,
+    algorithms::{calculate_interest_score, calculate_interest_score_with_index, SpatialIndex},
     model::{StringBasedWorldData, WorldDataGateway},
 };
 
@@ -20,6 +20,59 @@ fn bench_calculate_interest_score(c: &mut Criterion) {
     c.bench_function("calculate_interest_score", |b| {
         b.iter(|| calculate_interest_score(lat, lon, &heritage_sites, max_distance))
     });
+
+    let index = SpatialIndex::new(heritage_sites);
+    c.bench_function("calculate_interest_score_with_index", |b| {
+        b.iter(|| calculate_interest_score_with_index(lat, lon, &index, max_distance))
+    });
+}
+
+fn bench_calculate_aerodromes_interest_scores_brute_force(c: &mut Criterion) {
+    let data = StringBasedWorldData::default();
+    let mut points_of_interest: Vec<(f64, f64, f64)> = data
+        .world_heritage_sites()
+        .iter()
+        .map(|site| (site.lat, site.lon, 1.0f64))
+        .collect();
+    points_of_interest.extend(
+        data.attractions()
+            .iter()
+            .map(|attraction| (attraction.lat, attraction.lon, 1.0f64)),
+    );
+
+    let mut group = c.benchmark_group("calculate_aerodromes_interest_scores");
+    group.sampling_mode(SamplingMode::Flat);
+    group.sample_size(10);
+
+    group.bench_function("brute_force", |b| {
+        b.iter(|| {
+            data.aerodromes()
+                .iter()
+                .map(|aerodrome| {
+                    calculate_interest_score(
+                        aerodrome.lat,
+                        aerodrome.lon,
+                        &points_of_interest,
+                        250_000.0,
+                    )
+                })
+                .sum::<f32>()
+        })
+    });
+
+    group.finish();
+}
+
+fn bench_nearest_aerodromes(c: &mut Criterion) {
+    let data = StringBasedWorldData::default();
+
+    c.bench_function("nearest_aerodromes", |b| {
+        b.iter(|| data.nearest_aerodromes(50.033333, 8.570556, 10))
+    });
+
+    c.bench_function("aerodromes_within", |b| {
+        b.iter(|| data.aerodromes_within(50.033333, 8.570556, 1_200.0))
+    });
 }
 
 fn bench_calculate_aerodromes_interest_scores(c: &mut Criterion) {
@@ -29,7 +82,7 @@ fn bench_calculate_aerodromes_interest_scores(c: &mut Criterion) {
     group.sampling_mode(SamplingMode::Flat);
     group.sample_size(10);
 
-    group.bench_function("calculate_aerodromes_interest_scores", |b| {
+    group.bench_function("spatial_index", |b| {
         b.iter(|| data.calculate_aerodromes_interest_scores())
     });
 
@@ -39,6 +92,8 @@ fn bench_calculate_aerodromes_interest_scores(c: &mut Criterion) {
 criterion_group!(
     benches,
     bench_calculate_interest_score,
-    bench_calculate_aerodromes_interest_scores
+    bench_calculate_aerodromes_interest_scores,
+    bench_calculate_aerodromes_interest_scores_brute_force,
+    bench_nearest_aerodromes
 );
 criterion_main!(benches);
@@ -1,25 +1,28 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use flyconomy::model::analytics::{calculate_cash_history, calculate_total_flight_distance};
-use flyconomy::model::{CompanyFinances, Environment, Flight, FlightState};
+use flyconomy::model::analytics::{
+    calculate_average_profit_per_flight, calculate_cash_history, calculate_total_flight_distance,
+    calculate_transported_passengers,
+};
+use flyconomy::model::{Environment, EnvironmentConfig, Flight, FlightState, Timestamp};
 
 const TIMESTAMP_COLLECTION: [u128; 3] = [1000, 10_000, 100_000];
 
+const HOUR: Timestamp = 60 * 60 * 1000;
+const DAY: Timestamp = 24 * HOUR;
+const GAME_DAYS: [u128; 3] = [30, 365, 3 * 365];
+
 fn bench_calculate_cash_history(c: &mut Criterion) {
     let mut group = c.benchmark_group("calculate_cash_history");
 
     for timestamp in TIMESTAMP_COLLECTION.iter() {
-        let company_finances = CompanyFinances::new(0.0);
-        let mut environment = Environment {
-            timestamp: 0,
-            company_finances,
+        let mut environment = Environment::new(EnvironmentConfig {
+            start_capital: 0.0,
             ..Default::default()
-        };
+        });
 
         for _ in 0..*timestamp {
             environment.timestamp += 1;
-            environment
-                .company_finances
-                .add_income(environment.timestamp, 1.0);
+            environment.add_income(environment.timestamp, 1.0);
         }
 
         group.bench_with_input(
@@ -47,6 +50,11 @@ fn bench_calculate_total_flight_distance(c: &mut Criterion) {
                 ..Default::default()
             })
             .collect();
+        for flight in &environment.flights {
+            environment
+                .metrics
+                .record_landing(flight.arrival_time.unwrap(), flight, 0.0);
+        }
 
         group.bench_with_input(
             BenchmarkId::from_parameter(timestamp),
@@ -58,10 +66,94 @@ fn bench_calculate_total_flight_distance(c: &mut Criterion) {
     group.finish();
 }
 
+/// A game of `days` with hourly income, expenses and 20 landings a day.
+fn long_game(days: u128) -> Environment {
+    let mut environment = Environment::default();
+    let flight = Flight {
+        state: FlightState::Finished,
+        ..Default::default()
+    };
+
+    for hour in 1..=days * 24 {
+        environment.timestamp = hour * HOUR;
+        environment.add_income(environment.timestamp, 1_000.0);
+        environment.add_expense(environment.timestamp, 400.0);
+        if hour % 24 < 20 {
+            let flight = Flight {
+                arrival_time: Some(environment.timestamp),
+                ..flight.clone()
+            };
+            environment
+                .metrics
+                .record_landing(environment.timestamp, &flight, 0.0);
+            environment.flights.push(flight);
+        }
+        if environment.timestamp % DAY == 0 {
+            environment
+                .metrics
+                .compact(environment.timestamp - DAY, HOUR);
+            environment
+                .company_finances
+                .compact(environment.timestamp - DAY, HOUR);
+        }
+    }
+
+    environment
+}
+
+/// The analytics as computed before the metrics store, scanning all flights
+/// and finance entries for every sample.
+fn rescan_analytics(environment: &Environment) -> (f64, u32, f64) {
+    let samples = 100;
+    let interval = environment.timestamp / samples;
+    let mut result = (0.0, 0, 0.0);
+    for timestamp in (interval..=environment.timestamp).step_by(interval as usize) {
+        let finished = || {
+            environment.flights.iter().filter(move |flight| {
+                flight.state == FlightState::Finished && flight.arrival_time.unwrap() <= timestamp
+            })
+        };
+        result.0 += environment.company_finances.cash(timestamp);
+        result.1 += finished()
+            .map(|flight| flight.calculate_booked_seats())
+            .sum::<u32>();
+        result.2 += finished()
+            .map(|flight| flight.calculate_total_distance())
+            .sum::<f64>();
+    }
+    result
+}
+
+fn bench_long_game(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long_game_analytics");
+    group.sample_size(10);
+
+    for days in GAME_DAYS.iter() {
+        let environment = long_game(*days);
+
+        group.bench_with_input(BenchmarkId::new("metrics_store", days), days, |b, _| {
+            b.iter(|| {
+                (
+                    calculate_cash_history(&environment),
+                    calculate_transported_passengers(&environment),
+                    calculate_total_flight_distance(&environment),
+                    calculate_average_profit_per_flight(&environment),
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("rescan", days), days, |b, _| {
+            b.iter(|| rescan_analytics(&environment))
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_calculate_cash_history,
-    bench_calculate_total_flight_distance
+    bench_calculate_total_flight_distance,
+    bench_long_game
 );
 
 criterion_main!(benches);
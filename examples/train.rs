@@ -158,7 +158,7 @@ pub fn update_ai_manager_system(
         simulation.environment = ai_manager_resource.default_environment.clone();
         simulation.elapsed_time = Duration::from_secs(0);
         simulation.command_history.clear();
-        simulation.error_messages.clear();
+        simulation.journal.clear();
         for (entity, _, _) in query.iter() {
             commands.entity(entity).remove::<FlightVisual>();
             commands.entity(entity).despawn_recursive();
@@ -106,7 +106,7 @@ impl AiManager {
         while termination_strategy.i < iterations {
             simulation.elapsed_time = Duration::from_secs(0);
             simulation.command_history.clear();
-            simulation.error_messages.clear();
+            simulation.journal.clear();
             simulation.environment = environment.clone();
             agent = AiAgent::new(&mut simulation);
             self.trainer.train(
@@ -10,6 +10,7 @@ use super::AiAction;
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct AiState {
     pub cash: u64,
+    pub net_worth: i64,
     pub total_turnover: u64,
     pub planes: Vec<u64>,
     pub bases: Vec<u64>,
@@ -44,6 +45,7 @@ impl From<&crate::model::Environment> for AiState {
                 .total_expenses(environment.timestamp)) as u64;
         Self {
             cash,
+            net_worth: environment.metrics.net_worth(environment.timestamp) as i64,
             total_turnover,
             planes: environment.planes.iter().map(|plane| plane.id).collect(),
             bases: environment.bases.iter().map(|base| base.id).collect(),
@@ -63,7 +65,7 @@ impl State for AiState {
 
     fn reward(&self) -> f64 {
         let mut reward = 0.0;
-        reward += self.cash as f64 / 1_000_000.0;
+        reward += self.net_worth as f64 / 1_000_000.0;
         reward += self.total_turnover as f64 / 1_000_000.0;
         reward += (self.planes.len() * 5) as f64;
         reward += self.bases.len() as f64;
@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use rurel::{
     mdp::{Agent, State},
     strategy::explore::ExplorationStrategy,
@@ -14,6 +12,7 @@ pub struct ReplayAgent<'a> {
     simulation: &'a mut Simulation,
     state: AiState,
     timestamp: Timestamp,
+    next_command_index: usize,
 }
 
 impl<'a> ReplayAgent<'a> {
@@ -24,6 +23,7 @@ impl<'a> ReplayAgent<'a> {
             replay,
             simulation,
             timestamp: 0,
+            next_command_index: 0,
         }
     }
 }
@@ -36,32 +36,28 @@ impl<'a> Agent<AiState> for ReplayAgent<'a> {
     fn take_action(&mut self, action: &<AiState as State>::A) {
         println!("take_action: {:?} at {:#?}", action, self.state);
 
-        let command = self
-            .replay
-            .command_history
-            .iter()
-            .find(|command| command.timestamp == self.timestamp);
-        let next_command = self
+        let target_timestamp = self
             .replay
             .command_history
             .iter()
-            .find(|command| command.timestamp > self.timestamp);
-
-        let delta_time = match next_command {
-            Some(command) => command.timestamp - self.timestamp,
-            None => 1,
-        };
-
-        println!("delta_time: {} of {}", delta_time, self.timestamp);
+            .map(|command| command.timestamp)
+            .find(|timestamp| *timestamp > self.timestamp)
+            .unwrap_or(self.timestamp + 1);
+
+        log::debug!(
+            "target_timestamp: {} of {}",
+            target_timestamp,
+            self.timestamp
+        );
 
-        match command {
-            Some(command) => {
-                self.simulation.add_command_timed(command.clone());
+        while let Some(command) = self.replay.command_history.get(self.next_command_index) {
+            if command.timestamp > target_timestamp {
+                break;
             }
-            None => {}
+            self.simulation.add_command_timed(command.clone());
+            self.next_command_index += 1;
         }
-        self.simulation
-            .update(Duration::from_millis(delta_time as u64));
+        self.simulation.run_until(target_timestamp);
         self.timestamp = self.simulation.environment.timestamp;
         self.state = (&self.simulation.environment).into();
     }
@@ -1,5 +1,13 @@
 use geo::{algorithm::vincenty_distance::VincentyDistance, Point};
 
+mod spatial_index;
+
+pub use spatial_index::{great_circle_distance, Neighbour, SpatialIndex, EARTH_RADIUS_METERS};
+
+/// Vincenty distances on the ellipsoid differ from great circle distances by
+/// less than this factor, so candidates of a radius query are searched with it.
+const GREAT_CIRCLE_MARGIN: f64 = 1.01;
+
 pub fn calculate_interest_score(
     lat: f64,
     lon: f64,
@@ -11,24 +19,57 @@ pub fn calculate_interest_score(
     let interest_score: f64 = points_of_interest
         .iter()
         .map(|&(poi_lat, poi_lon, poi_score)| {
-            let point2 = Point::new(poi_lon, poi_lat);
-            let distance = point1.vincenty_distance(&point2).unwrap_or_default();
-
-            if distance <= max_distance_meters {
-                // Scale the score based on distance, now using the square of the distance.
-                let distance_ratio = (distance / max_distance_meters).powi(2);
-                (1.0 - distance_ratio) * poi_score
-            } else {
-                0.0
-            }
+            poi_contribution(&point1, poi_lat, poi_lon, poi_score, max_distance_meters)
         })
-        .fold(0.0, |acc, score| acc + score)
-        / 10.0;
+        .fold(0.0, |acc, score| acc + score);
+
+    normalize_interest_score(interest_score)
+}
+
+/// Same as [`calculate_interest_score`], but only computes the distances to
+/// the points of interest the index finds near the location. The items of
+/// the index are the scores of the points of interest.
+pub fn calculate_interest_score_with_index(
+    lat: f64,
+    lon: f64,
+    points_of_interest: &SpatialIndex<f64>,
+    max_distance_meters: f64,
+) -> f32 {
+    let point1 = Point::new(lon, lat);
+
+    let interest_score: f64 = points_of_interest
+        .within_radius(lat, lon, max_distance_meters * GREAT_CIRCLE_MARGIN)
+        .iter()
+        .map(|poi| poi_contribution(&point1, poi.lat, poi.lon, *poi.item, max_distance_meters))
+        .fold(0.0, |acc, score| acc + score);
 
+    normalize_interest_score(interest_score)
+}
+
+fn poi_contribution(
+    point1: &Point<f64>,
+    poi_lat: f64,
+    poi_lon: f64,
+    poi_score: f64,
+    max_distance_meters: f64,
+) -> f64 {
+    let point2 = Point::new(poi_lon, poi_lat);
+    let distance = point1.vincenty_distance(&point2).unwrap_or_default();
+
+    if distance <= max_distance_meters {
+        // Scale the score based on distance, now using the square of the distance.
+        let distance_ratio = (distance / max_distance_meters).powi(2);
+        (1.0 - distance_ratio) * poi_score
+    } else {
+        0.0
+    }
+}
+
+fn normalize_interest_score(interest_score: f64) -> f32 {
     // Normalize the final score to lie within [0.0, 1.0] range.
     // The score may exceed 1.0 if there are multiple points of interest nearby,
     // each contributing a part of the score. We cap it at 1.0 for consistency.
-    interest_score.min(1.0) as f32
+    (interest_score / 10.0).min(1.0) as f32
 }
 
 #[cfg(test)]
@@ -77,4 +118,21 @@ mod tests {
         let score = calculate_interest_score(lat, lon, &pois, max_distance);
         assert!(score > 0.1 && score <= 0.2);
     }
+
+    #[test]
+    fn test_interest_score_with_index() {
+        let pois = vec![
+            (40.764936, -73.980862, 1.0),
+            (40.712776, -74.005974, 0.8),
+            (48.858370, 2.294481, 1.0),
+        ];
+        let index = SpatialIndex::new(pois.clone());
+
+        for max_distance in [1_000.0, 5_000.0, 10_000_000.0] {
+            let score = calculate_interest_score(40.748817, -73.985428, &pois, max_distance);
+            let indexed_score =
+                calculate_interest_score_with_index(40.748817, -73.985428, &index, max_distance);
+            assert!((score - indexed_score).abs() < 1e-6);
+        }
+    }
 }
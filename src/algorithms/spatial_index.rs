@@ -0,0 +1,302 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+/// Mean earth radius in meters.
+pub const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+struct Entry<T> {
+    position: [f64; 3],
+    lat: f64,
+    lon: f64,
+    item: T,
+}
+
+/// A k-d tree over points on the unit sphere, answering radius and nearest
+/// neighbour queries by great circle distance.
+///
+/// The entries are stored as an implicit tree: the median of a range is the
+/// node, the entries before and after it are its subtrees.
+pub struct SpatialIndex<T> {
+    entries: Vec<Entry<T>>,
+}
+
+/// An item found by a query with its great circle distance in meters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbour<'a, T> {
+    pub lat: f64,
+    pub lon: f64,
+    pub item: &'a T,
+    pub distance: f64,
+}
+
+impl<T> SpatialIndex<T> {
+    /// Builds the index from `(lat, lon, item)` tuples, coordinates in degrees.
+    pub fn new(items: impl IntoIterator<Item = (f64, f64, T)>) -> Self {
+        let mut entries: Vec<Entry<T>> = items
+            .into_iter()
+            .map(|(lat, lon, item)| Entry {
+                position: to_unit_sphere(lat, lon),
+                lat,
+                lon,
+                item,
+            })
+            .collect();
+        build(&mut entries, 0);
+        Self { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// All items within `radius` meters, in no particular order.
+    pub fn within_radius(&self, lat: f64, lon: f64, radius: f64) -> Vec<Neighbour<'_, T>> {
+        let target = to_unit_sphere(lat, lon);
+        let max_chord = distance_to_chord(radius);
+        let mut found = Vec::new();
+        self.visit_within(
+            &target,
+            max_chord * max_chord,
+            0,
+            self.entries.len(),
+            0,
+            &mut found,
+        );
+        found
+            .into_iter()
+            .map(|(index, squared_chord)| self.neighbour(index, squared_chord))
+            .collect()
+    }
+
+    /// The `k` nearest items, nearest first.
+    pub fn nearest(&self, lat: f64, lon: f64, k: usize) -> Vec<Neighbour<'_, T>> {
+        let target = to_unit_sphere(lat, lon);
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.visit_nearest(&target, k, 0, self.entries.len(), 0, &mut heap);
+        }
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|candidate| self.neighbour(candidate.index, candidate.squared_chord))
+            .collect()
+    }
+
+    fn neighbour(&self, index: usize, squared_chord: f64) -> Neighbour<'_, T> {
+        let entry = &self.entries[index];
+        Neighbour {
+            lat: entry.lat,
+            lon: entry.lon,
+            item: &entry.item,
+            distance: chord_to_distance(squared_chord.sqrt()),
+        }
+    }
+
+    fn visit_within(
+        &self,
+        target: &[f64; 3],
+        max_squared_chord: f64,
+        start: usize,
+        end: usize,
+        depth: usize,
+        found: &mut Vec<(usize, f64)>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let mid = start + (end - start) / 2;
+        let axis = depth % 3;
+        let position = &self.entries[mid].position;
+
+        let squared_chord = squared_distance(position, target);
+        if squared_chord <= max_squared_chord {
+            found.push((mid, squared_chord));
+        }
+
+        let delta = target[axis] - position[axis];
+        if delta <= 0.0 || delta * delta <= max_squared_chord {
+            self.visit_within(target, max_squared_chord, start, mid, depth + 1, found);
+        }
+        if delta >= 0.0 || delta * delta <= max_squared_chord {
+            self.visit_within(target, max_squared_chord, mid + 1, end, depth + 1, found);
+        }
+    }
+
+    fn visit_nearest(
+        &self,
+        target: &[f64; 3],
+        k: usize,
+        start: usize,
+        end: usize,
+        depth: usize,
+        heap: &mut BinaryHeap<Candidate>,
+    ) {
+        if start >= end {
+            return;
+        }
+        let mid = start + (end - start) / 2;
+        let axis = depth % 3;
+        let position = &self.entries[mid].position;
+
+        heap.push(Candidate {
+            squared_chord: squared_distance(position, target),
+            index: mid,
+        });
+        if heap.len() > k {
+            heap.pop();
+        }
+
+        let delta = target[axis] - position[axis];
+        let (near, far) = if delta <= 0.0 {
+            ((start, mid), (mid + 1, end))
+        } else {
+            ((mid + 1, end), (start, mid))
+        };
+        self.visit_nearest(target, k, near.0, near.1, depth + 1, heap);
+        let worst = heap.peek().map_or(f64::INFINITY, |c| c.squared_chord);
+        if heap.len() < k || delta * delta < worst {
+            self.visit_nearest(target, k, far.0, far.1, depth + 1, heap);
+        }
+    }
+}
+
+/// Candidate of a nearest neighbour query, ordered by distance so the heap
+/// keeps the farthest of the nearest candidates on top.
+#[derive(PartialEq)]
+struct Candidate {
+    squared_chord: f64,
+    index: usize,
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.squared_chord
+            .total_cmp(&other.squared_chord)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+fn build<T>(entries: &mut [Entry<T>], depth: usize) {
+    if entries.len() <= 1 {
+        return;
+    }
+    let axis = depth % 3;
+    let mid = entries.len() / 2;
+    entries.select_nth_unstable_by(mid, |a, b| a.position[axis].total_cmp(&b.position[axis]));
+    let (left, right) = entries.split_at_mut(mid);
+    build(left, depth + 1);
+    build(&mut right[1..], depth + 1);
+}
+
+fn to_unit_sphere(lat: f64, lon: f64) -> [f64; 3] {
+    let (lat, lon) = (lat.to_radians(), lon.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+fn squared_distance(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    (0..3).map(|axis| (a[axis] - b[axis]).powi(2)).sum()
+}
+
+/// Length of the chord through the unit sphere for a great circle distance.
+fn distance_to_chord(distance: f64) -> f64 {
+    let angle = (distance / EARTH_RADIUS_METERS).min(std::f64::consts::PI);
+    2.0 * (angle / 2.0).sin()
+}
+
+fn chord_to_distance(chord: f64) -> f64 {
+    2.0 * (chord / 2.0).min(1.0).asin() * EARTH_RADIUS_METERS
+}
+
+/// Great circle distance in meters between two coordinates in degrees.
+pub fn great_circle_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let chord = squared_distance(&to_unit_sphere(lat1, lon1), &to_unit_sphere(lat2, lon2)).sqrt();
+    chord_to_distance(chord)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Vec<(f64, f64, usize)> {
+        let mut points = Vec::new();
+        for lat in (-80..=80).step_by(10) {
+            for lon in (-180..180).step_by(10) {
+                points.push((lat as f64 + 0.3, lon as f64 + 0.7, points.len()));
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_within_radius_matches_brute_force() {
+        let points = grid();
+        let index = SpatialIndex::new(points.clone());
+        assert_eq!(index.len(), points.len());
+
+        for (lat, lon, radius) in [(50.0, 8.5, 1_500_000.0), (-33.9, 151.2, 800_000.0)] {
+            let mut found: Vec<usize> = index
+                .within_radius(lat, lon, radius)
+                .iter()
+                .map(|neighbour| *neighbour.item)
+                .collect();
+            found.sort();
+
+            let expected: Vec<usize> = points
+                .iter()
+                .filter(|(p_lat, p_lon, _)| {
+                    great_circle_distance(lat, lon, *p_lat, *p_lon) <= radius
+                })
+                .map(|(_, _, id)| *id)
+                .collect();
+            assert!(!expected.is_empty());
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let points = grid();
+        let index = SpatialIndex::new(points.clone());
+
+        let nearest = index.nearest(40.6, -73.8, 5);
+        let mut expected = points.clone();
+        expected.sort_by(|a, b| {
+            great_circle_distance(40.6, -73.8, a.0, a.1)
+                .total_cmp(&great_circle_distance(40.6, -73.8, b.0, b.1))
+        });
+
+        assert_eq!(nearest.len(), 5);
+        for (neighbour, expected) in nearest.iter().zip(&expected) {
+            assert_eq!(*neighbour.item, expected.2);
+        }
+        assert!(nearest
+            .windows(2)
+            .all(|pair| pair[0].distance <= pair[1].distance));
+    }
+
+    #[test]
+    fn test_empty_index() {
+        let index: SpatialIndex<()> = SpatialIndex::new(Vec::new());
+
+        assert!(index.is_empty());
+        assert!(index.nearest(0.0, 0.0, 3).is_empty());
+        assert!(index.within_radius(0.0, 0.0, 1_000.0).is_empty());
+    }
+
+    #[test]
+    fn test_great_circle_distance() {
+        // Frankfurt to Paris Charles de Gaulle
+        let distance = great_circle_distance(50.033333, 8.570556, 49.012798, 2.55);
+        assert!((distance - 450_000.0).abs() < 2_000.0);
+    }
+}
@@ -1,90 +1,122 @@
 use std::collections::HashMap;
 
-use csv::{ByteRecord, ReaderBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::model::Aerodrome;
+use crate::model::{split_code, Aerodrome};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct AerodromeConfig(pub Value);
+use super::parser::{
+    csv_field, optional_csv_field, parse_csv_field, parse_csv_field_in_range, parse_csv_rows,
+    parse_latitude, parse_longitude, parse_optional_csv_field, ParseError, ParseMode, Parsed,
+    UniqueColumn,
+};
 
-pub fn parse_airport_csv(input: &str) -> Vec<Aerodrome> {
-    let mut rdr = ReaderBuilder::new()
-        .has_headers(false)
-        .delimiter(b',')
-        .from_reader(input.as_bytes());
+const METERS_PER_FOOT: f32 = 0.3048;
 
-    let mut aerodromes = Vec::new();
-    let mut record = ByteRecord::new();
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AerodromeConfig(pub Value);
 
-    while rdr.read_byte_record(&mut record).unwrap() {
-        let id = std::str::from_utf8(record.get(0).unwrap())
-            .unwrap()
-            .parse::<u64>()
-            .unwrap();
-        let name = std::str::from_utf8(record.get(1).unwrap())
-            .unwrap()
-            .to_string();
-        let lat = std::str::from_utf8(record.get(6).unwrap())
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
-        let lon = std::str::from_utf8(record.get(7).unwrap())
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
-        let iata = std::str::from_utf8(record.get(4).unwrap()).unwrap();
-        let icao = std::str::from_utf8(record.get(5).unwrap()).unwrap();
+pub fn parse_airport_csv(input: &str, mode: ParseMode) -> Result<Parsed<Aerodrome>, ParseError> {
+    let mut ids = UniqueColumn::new(0, "id");
+    let mut codes = UniqueColumn::new(4, "code");
+
+    parse_csv_rows(input, false, mode, |record| {
+        let id = parse_csv_field(record, 0, "id")?;
+        let name = csv_field(record, 1, "name")?.to_string();
+        let city = optional_csv_field(record, 2, "city")?.unwrap_or_default();
+        let country = optional_csv_field(record, 3, "country")?.unwrap_or_default();
+        let lat = parse_latitude(record, 6)?;
+        let lon = parse_longitude(record, 7)?;
+        let iata = csv_field(record, 4, "IATA code")?;
+        let icao = csv_field(record, 5, "ICAO code")?;
+        let altitude: Option<f32> = parse_optional_csv_field(record, 8, "altitude")?;
+        let utc_offset = parse_optional_csv_field(record, 9, "UTC offset")?;
 
         let code = format!("{}/{}", iata, icao);
 
-        let aerodrome = Aerodrome {
+        ids.check(record, &id)?;
+        codes.check(record, &code)?;
+        ids.insert(record, id);
+        codes.insert(record, code.clone());
+
+        let (iata, icao) = split_code(&code);
+        Ok(Aerodrome {
             id,
             lat,
             lon,
             name,
             code,
             interest_score: 1.0,
+            category_scores: Default::default(),
             passengers: None,
-        };
-        aerodromes.push(aerodrome);
-    }
-    aerodromes
+            runway_length: None,
+            city: city.to_string(),
+            country: country.to_string(),
+            iata,
+            icao,
+            altitude: altitude.unwrap_or_default() * METERS_PER_FOOT,
+            utc_offset,
+        })
+    })
 }
 
-pub fn parse_passengers_csv(input: &str) -> HashMap<String, u64> {
-    let mut rdr = ReaderBuilder::new()
-        .has_headers(true)
-        .delimiter(b',')
-        .from_reader(input.as_bytes());
+pub fn parse_passengers_csv(
+    input: &str,
+    mode: ParseMode,
+) -> Result<Parsed<(String, u64)>, ParseError> {
+    parse_csv_rows(input, true, mode, |record| {
+        let code = csv_field(record, 4, "code")?;
+        // Airports without passenger numbers count as having none.
+        let count = parse_optional_csv_field(record, 5, "passengers")?.unwrap_or(0);
+        Ok((code.to_string(), count))
+    })
+}
 
-    let mut passengers = HashMap::new();
-    let mut record = ByteRecord::new();
+/// Sets the passenger numbers of the aerodromes by their code.
+pub fn assign_passengers(aerodromes: &mut [Aerodrome], passengers: Vec<(String, u64)>) {
+    let passengers: HashMap<String, u64> = passengers.into_iter().collect();
 
-    while rdr.read_byte_record(&mut record).unwrap() {
-        let code = std::str::from_utf8(record.get(4).unwrap()).unwrap();
-        let count = std::str::from_utf8(record.get(5).unwrap())
-            .unwrap()
-            .parse::<u64>();
-        match count {
-            Ok(count) => passengers.insert(code.to_string(), count),
-            Err(_) => passengers.insert(code.to_string(), 0),
-        };
+    for aerodrome in aerodromes.iter_mut() {
+        let count = passengers.get(&aerodrome.code);
+        aerodrome.passengers = count.copied();
     }
-    passengers
 }
 
-pub fn load_airports(airports_csv: &str, passengers_csv: &str) -> Vec<Aerodrome> {
-    let mut aerodromes = parse_airport_csv(airports_csv);
-    let passengers = parse_passengers_csv(passengers_csv);
+/// Parses the longest runway length in meters per aerodrome code.
+pub fn parse_runways_csv(
+    input: &str,
+    mode: ParseMode,
+) -> Result<Parsed<(String, f32)>, ParseError> {
+    let mut codes = UniqueColumn::new(0, "code");
+
+    parse_csv_rows(input, true, mode, |record| {
+        let code = csv_field(record, 0, "code")?.to_string();
+        let length = parse_csv_field_in_range(record, 1, "runway length", 0.0..=f64::MAX)?;
+
+        codes.check(record, &code)?;
+        codes.insert(record, code.clone());
+
+        Ok((code, length as f32))
+    })
+}
+
+/// Sets the runway lengths of the aerodromes by their code.
+pub fn assign_runway_lengths(aerodromes: &mut [Aerodrome], runways: Vec<(String, f32)>) {
+    let runways: HashMap<String, f32> = runways.into_iter().collect();
 
     for aerodrome in aerodromes.iter_mut() {
-        let count = passengers.get(&aerodrome.code);
-        aerodrome.passengers = count.copied();
+        aerodrome.runway_length = runways.get(&aerodrome.code).copied();
     }
+}
 
-    aerodromes
+pub fn load_airports(
+    airports_csv: &str,
+    passengers_csv: &str,
+) -> Result<Vec<Aerodrome>, ParseError> {
+    let mut aerodromes = parse_airport_csv(airports_csv, ParseMode::Strict)?.items;
+    let passengers = parse_passengers_csv(passengers_csv, ParseMode::Strict)?.items;
+    assign_passengers(&mut aerodromes, passengers);
+    Ok(aerodromes)
 }
 
 #[cfg(test)]
@@ -95,7 +127,9 @@ mod tests {
     fn test_parse_csv() {
         let csv_data = include_str!("../../assets/airports.dat");
 
-        let aerodromes = parse_airport_csv(&csv_data);
+        let aerodromes = parse_airport_csv(&csv_data, ParseMode::Strict)
+            .unwrap()
+            .items;
 
         assert_eq!(aerodromes.len(), 7698);
 
@@ -104,6 +138,12 @@ mod tests {
         assert_eq!(aerodromes[0].lat, -6.081689834590001);
         assert_eq!(aerodromes[0].lon, 145.391998291);
         assert_eq!(aerodromes[0].code, "GKA/AYGA");
+        assert_eq!(aerodromes[0].city, "Goroka");
+        assert_eq!(aerodromes[0].country, "Papua New Guinea");
+        assert_eq!(aerodromes[0].iata.as_deref(), Some("GKA"));
+        assert_eq!(aerodromes[0].icao.as_deref(), Some("AYGA"));
+        assert!((aerodromes[0].altitude - 1609.9).abs() < 0.1);
+        assert_eq!(aerodromes[0].utc_offset, Some(10.0));
 
         assert_eq!(aerodromes[1].id, 2);
         assert_eq!(aerodromes[1].name, "Madang Airport");
@@ -115,11 +155,90 @@ mod tests {
     fn test_parse_passengers_csv() {
         let csv_data = include_str!("../../assets/passengers.csv");
 
-        let passengers = parse_passengers_csv(&csv_data);
+        let passengers: HashMap<_, _> = parse_passengers_csv(&csv_data, ParseMode::Strict)
+            .unwrap()
+            .items
+            .into_iter()
+            .collect();
 
         assert_eq!(passengers.len(), 69);
 
         assert_eq!(passengers.get("GKA/AYGA"), None);
         assert_eq!(passengers.get("HND/RJTT"), Some(&79699762));
     }
+
+    #[test]
+    fn test_parse_invalid_passengers_csv() {
+        let csv_data = "Rank,Airport,Location,Country,Code,Passengers,Year\n\
+            1,Frankfurt,Frankfurt,Germany,FRA/EDDF,many,2020\n\
+            2,Paris,Paris,France,CDG/LFPG,,2020\n";
+
+        let error = parse_passengers_csv(csv_data, ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 6: invalid passengers 'many'"
+        );
+
+        let parsed = parse_passengers_csv(csv_data, ParseMode::Lenient).unwrap();
+        assert_eq!(parsed.items, vec![("CDG/LFPG".to_string(), 0)]);
+        assert_eq!(parsed.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_runways_csv() {
+        let mut aerodromes = vec![Aerodrome::frankfurt(), Aerodrome::default()];
+        let runways =
+            parse_runways_csv(include_str!("../../assets/runways.csv"), ParseMode::Strict).unwrap();
+
+        assign_runway_lengths(&mut aerodromes, runways.items);
+
+        assert_eq!(aerodromes[0].runway_length, Some(4000.0));
+        assert_eq!(aerodromes[1].runway_length, None);
+
+        let error =
+            parse_runways_csv("code,runway_length\nFRA/EDDF,-1\n", ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: runway length -1 is out of range"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_csv() {
+        let csv_data =
+            "1,\"Goroka Airport\",\"Goroka\",\"Papua New Guinea\",\"GKA\",\"AYGA\",north,145.39";
+
+        let error = parse_airport_csv(csv_data, ParseMode::Strict).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 7: invalid latitude 'north'"
+        );
+        assert_eq!(error.line(), Some(1));
+        assert_eq!(error.column(), Some(7));
+    }
+
+    #[test]
+    fn test_parse_broken_csv() {
+        let csv_data = include_str!("../../tests/fixtures/broken/airports.dat");
+
+        let error = parse_airport_csv(csv_data, ParseMode::Strict).unwrap_err();
+        assert_eq!(error.line(), Some(2));
+
+        let parsed = parse_airport_csv(csv_data, ParseMode::Lenient).unwrap();
+        let ids: Vec<u64> = parsed.items.iter().map(|aerodrome| aerodrome.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+
+        let skipped: Vec<String> = parsed.skipped.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            skipped,
+            vec![
+                "line 2, column 7: latitude 95 is out of range",
+                "line 4, column 8: invalid longitude 'east'",
+                "line 5, column 1: duplicate id '1', first on line 1",
+                "line 6, column 5: duplicate code 'GKA/AYGA', first on line 1",
+                "line 7, column 8: missing longitude",
+            ]
+        );
+    }
 }
@@ -1,63 +1,84 @@
-use csv::{ByteRecord, ReaderBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::model::Attraction;
 
+use super::parser::{
+    csv_field, parse_csv_field, parse_csv_rows, parse_latitude, parse_longitude,
+    parse_optional_csv_field, ParseError, ParseMode, Parsed, UniqueColumn,
+};
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AttractionConfig(pub Value);
 
-pub fn parse_attractions_csv(input: &str) -> Vec<Attraction> {
-    let mut rdr = ReaderBuilder::new()
-        .has_headers(true)
-        .delimiter(b',')
-        .from_reader(input.as_bytes());
+pub fn parse_attractions_csv(
+    input: &str,
+    mode: ParseMode,
+) -> Result<Parsed<Attraction>, ParseError> {
+    let mut ids = UniqueColumn::new(0, "id");
 
-    let mut sites = Vec::new();
-    let mut record = ByteRecord::new();
+    parse_csv_rows(input, true, mode, |record| {
+        let id = parse_csv_field(record, 0, "id")?;
+        let name = csv_field(record, 1, "name")?.to_string();
+        let description = csv_field(record, 4, "description")?.to_string();
+        let lat = parse_latitude(record, 2)?;
+        let lon = parse_longitude(record, 3)?;
+        // Attractions without a category count as culture.
+        let category = parse_optional_csv_field(record, 5, "category")?.unwrap_or_default();
 
-    while rdr.read_byte_record(&mut record).unwrap() {
-        let id = std::str::from_utf8(record.get(0).unwrap())
-            .unwrap()
-            .parse::<u64>()
-            .unwrap();
-        let name = std::str::from_utf8(record.get(1).unwrap())
-            .unwrap()
-            .to_string();
-        let description = std::str::from_utf8(record.get(4).unwrap())
-            .unwrap()
-            .to_string();
-        let lat = std::str::from_utf8(record.get(2).unwrap())
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
-        let lon = std::str::from_utf8(record.get(3).unwrap())
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
+        ids.check(record, &id)?;
+        ids.insert(record, id);
 
-        let site = Attraction {
+        Ok(Attraction {
             id,
             lat,
             lon,
             name,
             description,
-        };
-        sites.push(site);
-    }
-    sites
+            category,
+        })
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::PoiCategory;
 
     #[test]
     fn test_parse_csv() {
         let csv_data = include_str!("../../assets/attractions.csv");
 
-        let sites = parse_attractions_csv(&csv_data);
+        let sites = parse_attractions_csv(&csv_data, ParseMode::Strict)
+            .unwrap()
+            .items;
 
         assert!(sites.len() > 4);
+        assert_eq!(sites[0].category, PoiCategory::Nature);
+        assert!(sites
+            .iter()
+            .any(|site| site.category == PoiCategory::Business));
+        assert!(sites.iter().any(|site| site.category == PoiCategory::Beach));
+    }
+
+    #[test]
+    fn test_parse_broken_csv() {
+        let csv_data = include_str!("../../tests/fixtures/broken/attractions.csv");
+
+        let error = parse_attractions_csv(csv_data, ParseMode::Strict).unwrap_err();
+        assert_eq!(error.to_string(), "line 3, column 1: invalid id 'two'");
+
+        let parsed = parse_attractions_csv(csv_data, ParseMode::Lenient).unwrap();
+        assert_eq!(parsed.items.len(), 1);
+        assert_eq!(parsed.items[0].category, PoiCategory::Culture);
+        assert_eq!(parsed.skipped.len(), 3);
+        assert_eq!(
+            parsed.skipped[1].to_string(),
+            "line 4, column 4: longitude -200 is out of range"
+        );
+        assert_eq!(
+            parsed.skipped[2].to_string(),
+            "line 5, column 1: duplicate id '0', first on line 2"
+        );
     }
 }
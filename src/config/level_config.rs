@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    model::{
+        commands::{
+            BuyLandingRightsCommand, BuyPlaneCommand, Command, CommandError, CreateBaseCommand,
+        },
+        world_data::WorldDataSource,
+        Environment, EnvironmentConfig, Objective, Timestamp, WorldDataGateway,
+    },
+    simulation::{Simulation, DAY},
+};
+
+/// Name of the level built from [`LevelConfig::default`].
+pub const DEFAULT_LEVEL: &str = "default";
+
+/// First id of the starting assets of each kind. Generated ids stay below it,
+/// so a level always gives its starting assets the same ids and a replay of it
+/// can be checked against the level.
+const STARTING_ASSET_ID: u64 = 1 << 32;
+
+/// Levels shipped with the game, the files in `assets/levels`.
+const EMBEDDED_LEVELS: [(&str, &str); 3] = [
+    ("level1", include_str!("../../assets/levels/level1.yaml")),
+    ("level2", include_str!("../../assets/levels/level2.yaml")),
+    ("level3", include_str!("../../assets/levels/level3.yaml")),
+];
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LevelConfig {
+    pub name: String,
+    pub description: String,
+    pub environment: EnvironmentConfig,
+    #[serde(default)]
+    pub starting_assets: StartingAssets,
+    #[serde(default)]
+    pub objectives: Vec<Objective>,
+    #[serde(default)]
+    pub time_limit_days: Option<u64>,
+}
+
+/// Assets the company owns when the level starts. Aerodromes are referenced
+/// by their code, e.g. `FRA`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct StartingAssets {
+    pub bases: Vec<String>,
+    pub landing_rights: Vec<String>,
+    pub planes: Vec<StartingPlane>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct StartingPlane {
+    pub plane_type: String,
+    /// Code of the aerodrome of one of the starting bases.
+    pub base: String,
+}
+
+#[derive(Debug, Error)]
+pub enum LevelError {
+    #[error("Unknown level {0}")]
+    UnknownLevel(String),
+    #[error("Failed to parse level {level}: {source}")]
+    Parse {
+        level: String,
+        source: serde_yaml::Error,
+    },
+    #[error("Unknown aerodrome {0}")]
+    UnknownAerodrome(String),
+    #[error("Unknown plane type {0}")]
+    UnknownPlaneType(String),
+    #[error("No starting base at {0}")]
+    NoStartingBase(String),
+    #[error("Invalid starting asset: {0}")]
+    InvalidStartingAsset(#[from] CommandError),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LevelOutcome {
+    #[default]
+    Running,
+    Won,
+    Lost(LossReason),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossReason {
+    Bankrupt,
+    TimeLimitExceeded,
+}
+
+impl Default for LevelConfig {
+    fn default() -> Self {
+        Self {
+            name: String::from("Default"),
+            description: String::from("Default level"),
+            environment: Default::default(),
+            starting_assets: Default::default(),
+            objectives: vec![],
+            time_limit_days: None,
+        }
+    }
+}
+
+impl LevelConfig {
+    pub fn from_yaml(level: &str, yaml: &str) -> Result<Self, LevelError> {
+        serde_yaml::from_str(yaml).map_err(|source| LevelError::Parse {
+            level: level.to_string(),
+            source,
+        })
+    }
+
+    /// Loads a level by its file name without extension, e.g. `level1`.
+    pub fn load(level: &str, source: &WorldDataSource) -> Result<Self, LevelError> {
+        if level == DEFAULT_LEVEL {
+            return Ok(Self::default());
+        }
+        available_levels(source)
+            .into_iter()
+            .find(|(name, _)| name == level)
+            .map(|(_, level_config)| level_config)
+            .ok_or_else(|| LevelError::UnknownLevel(level.to_string()))
+    }
+
+    /// Time at which the level is lost if the objectives are not reached.
+    pub fn time_limit(&self) -> Option<Timestamp> {
+        self.time_limit_days
+            .map(|days| days as u128 * DAY.as_millis())
+    }
+
+    /// Commands creating the starting assets, in the order they have to be
+    /// executed. Their ids count up from `STARTING_ASSET_ID`.
+    pub fn starting_commands(
+        &self,
+        world_data_gateway: &dyn WorldDataGateway,
+    ) -> Result<Vec<Box<dyn Command>>, LevelError> {
+        let find_aerodrome = |code: &String| {
+            world_data_gateway
+                .aerodromes()
+                .iter()
+                .find(|aerodrome| aerodrome.has_code(code))
+                .cloned()
+                .ok_or_else(|| LevelError::UnknownAerodrome(code.clone()))
+        };
+
+        let mut commands: Vec<Box<dyn Command>> = vec![];
+        let mut base_ids = HashMap::new();
+        for (index, code) in self.starting_assets.bases.iter().enumerate() {
+            let base_id = STARTING_ASSET_ID + index as u64;
+            base_ids.insert(code.clone(), base_id);
+            commands.push(Box::new(CreateBaseCommand {
+                base_id,
+                aerodrome: find_aerodrome(code)?,
+            }));
+        }
+        for (index, code) in self.starting_assets.landing_rights.iter().enumerate() {
+            commands.push(Box::new(BuyLandingRightsCommand {
+                landing_rights_id: STARTING_ASSET_ID + index as u64,
+                aerodrome: find_aerodrome(code)?,
+            }));
+        }
+        for (index, plane) in self.starting_assets.planes.iter().enumerate() {
+            let plane_type = world_data_gateway
+                .plane_types()
+                .iter()
+                .find(|plane_type| plane_type.name == plane.plane_type)
+                .cloned()
+                .ok_or_else(|| LevelError::UnknownPlaneType(plane.plane_type.clone()))?;
+            let home_base_id = *base_ids
+                .get(&plane.base)
+                .ok_or_else(|| LevelError::NoStartingBase(plane.base.clone()))?;
+            commands.push(Box::new(BuyPlaneCommand {
+                plane_id: STARTING_ASSET_ID + index as u64,
+                plane_type,
+                home_base_id,
+            }));
+        }
+        Ok(commands)
+    }
+
+    /// Creates a simulation of the level.
+    ///
+    /// The starting assets are given to the company for free before the
+    /// first tick, see [`Simulation::add_starting_assets`], so the cash starts
+    /// at the start capital and they are recorded in replays.
+    pub fn create_simulation(
+        &self,
+        mut world_data_gateway: Box<dyn WorldDataGateway>,
+    ) -> Result<Simulation, LevelError> {
+        // The starting assets take their interest scores from the world data.
+        world_data_gateway.set_interest_model(self.environment.interest);
+        let commands = self.starting_commands(world_data_gateway.as_ref())?;
+
+        let mut simulation = Simulation::new(self.environment.clone(), world_data_gateway);
+        simulation.add_starting_assets(commands)?;
+        Ok(simulation)
+    }
+
+    /// Won once all objectives are met, lost when the time limit is reached
+    /// before.
+    pub fn evaluate(&self, environment: &Environment) -> LevelOutcome {
+        if !self.objectives.is_empty()
+            && self
+                .objectives
+                .iter()
+                .all(|objective| objective.is_met(environment))
+        {
+            LevelOutcome::Won
+        } else if self
+            .time_limit()
+            .is_some_and(|time_limit| environment.timestamp >= time_limit)
+        {
+            LevelOutcome::Lost(LossReason::TimeLimitExceeded)
+        } else {
+            LevelOutcome::Running
+        }
+    }
+}
+
+/// All levels of the data source by file name, sorted by it. Falls back to
+/// the embedded levels if the levels directory cannot be read.
+pub fn available_levels(source: &WorldDataSource) -> Vec<(String, LevelConfig)> {
+    source
+        .levels_directory()
+        .and_then(|directory| read_levels_dir(&directory))
+        .unwrap_or_else(embedded_levels)
+}
+
+pub fn embedded_levels() -> Vec<(String, LevelConfig)> {
+    EMBEDDED_LEVELS
+        .iter()
+        .filter_map(|(name, yaml)| parse_level(name, yaml))
+        .collect()
+}
+
+fn read_levels_dir(path: &std::path::Path) -> Option<Vec<(String, LevelConfig)>> {
+    let mut levels: Vec<_> = std::fs::read_dir(path)
+        .ok()?
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "yaml" {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            let yaml = std::fs::read_to_string(&path).ok()?;
+            parse_level(&name, &yaml)
+        })
+        .collect();
+    levels.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Some(levels)
+}
+
+fn parse_level(name: &str, yaml: &str) -> Option<(String, LevelConfig)> {
+    match LevelConfig::from_yaml(name, yaml) {
+        Ok(level_config) => Some((name.to_string(), level_config)),
+        Err(error) => {
+            log::error!("{}", error);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{commands::SellPlaneCommand, Environment, StringBasedWorldData};
+
+    use super::*;
+
+    #[test]
+    fn test_all_level_files_are_embedded() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/levels");
+        let levels = read_levels_dir(std::path::Path::new(dir)).unwrap();
+
+        assert_eq!(levels, embedded_levels());
+    }
+
+    #[test]
+    fn test_create_simulation_with_starting_assets() {
+        for (name, level_config) in embedded_levels() {
+            let mut simulation = level_config
+                .create_simulation(Box::new(StringBasedWorldData::default()))
+                .unwrap_or_else(|error| panic!("{}: {}", name, error));
+            assert!(simulation.command_history.is_empty());
+
+            let environment = &simulation.environment;
+            let assets = &level_config.starting_assets;
+            assert_eq!(environment.bases.len(), assets.bases.len());
+            assert_eq!(
+                environment.landing_rights.len(),
+                assets.landing_rights.len()
+            );
+            assert_eq!(environment.planes.len(), assets.planes.len());
+            let cash = environment.company_finances.cash(environment.timestamp);
+            assert!((cash - level_config.environment.start_capital).abs() < 1e-6);
+
+            // Starting assets are free, so selling them refunds nothing.
+            for plane_id in environment
+                .planes
+                .iter()
+                .map(|plane| plane.id)
+                .collect::<Vec<_>>()
+            {
+                simulation.add_command(Box::new(SellPlaneCommand { plane_id }));
+            }
+            simulation.tick();
+            let environment = &simulation.environment;
+            assert!(environment.planes.is_empty());
+            let cash = environment.company_finances.cash(environment.timestamp);
+            assert!((cash - level_config.environment.start_capital).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_unknown_starting_aerodrome() {
+        let level_config = LevelConfig {
+            starting_assets: StartingAssets {
+                bases: vec!["XXX".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = level_config.create_simulation(Box::new(StringBasedWorldData::default()));
+        assert!(matches!(result, Err(LevelError::UnknownAerodrome(..))));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let level_config = LevelConfig {
+            objectives: vec![Objective::ReachCash {
+                amount: 2_000_000.0,
+            }],
+            time_limit_days: Some(1),
+            ..Default::default()
+        };
+        let mut environment = Environment::default();
+        assert_eq!(level_config.evaluate(&environment), LevelOutcome::Running);
+
+        environment.timestamp = level_config.time_limit().unwrap();
+        assert_eq!(
+            level_config.evaluate(&environment),
+            LevelOutcome::Lost(LossReason::TimeLimitExceeded)
+        );
+
+        environment.company_finances.add_income(0, 1_000_000.0);
+        assert_eq!(level_config.evaluate(&environment), LevelOutcome::Won);
+    }
+}
@@ -1,13 +1,25 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 
-use crate::model::{EnvironmentConfig, PlaneType};
+use crate::model::PlaneType;
 
 mod aerodrome_config;
 mod attraction_config;
+mod level_config;
+mod parser;
 mod world_heritage_site_config;
 
-pub use aerodrome_config::{load_airports, AerodromeConfig};
+pub use aerodrome_config::{
+    assign_passengers, assign_runway_lengths, load_airports, parse_airport_csv,
+    parse_passengers_csv, parse_runways_csv, AerodromeConfig,
+};
 pub use attraction_config::parse_attractions_csv;
+pub use level_config::{
+    available_levels, embedded_levels, LevelConfig, LevelError, LevelOutcome, LossReason,
+    StartingAssets, StartingPlane, DEFAULT_LEVEL,
+};
+pub use parser::{ParseError, ParseMode, Parsed};
 pub use world_heritage_site_config::parse_world_heritage_site_csv;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -15,21 +27,45 @@ pub struct PlanesConfig {
     pub planes: Vec<PlaneType>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct LevelConfig {
-    pub name: String,
-    pub description: String,
-    pub environment: EnvironmentConfig,
+#[derive(Deserialize)]
+struct PlaneEntries {
+    planes: Vec<serde_yaml::Value>,
 }
 
-impl Default for LevelConfig {
-    fn default() -> Self {
-        Self {
-            name: String::from("Default"),
-            description: String::from("Default level"),
-            environment: Default::default(),
-        }
+/// Parses the plane types of a planes file. In lenient mode, every entry of
+/// `planes` is parsed on its own so an invalid entry only skips that plane.
+pub fn parse_planes_yaml(input: &str, mode: ParseMode) -> Result<Parsed<PlaneType>, ParseError> {
+    let entries: Vec<Result<PlaneType, ParseError>> = match mode {
+        ParseMode::Strict => serde_yaml::from_str::<PlanesConfig>(input)?
+            .planes
+            .into_iter()
+            .map(Ok)
+            .collect(),
+        ParseMode::Lenient => serde_yaml::from_str::<PlaneEntries>(input)?
+            .planes
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                serde_yaml::from_value(entry)
+                    .map_err(|source| ParseError::InvalidEntry { index, source })
+            })
+            .collect(),
+    };
+
+    let mut ids = HashSet::new();
+    let mut parsed = Parsed::default();
+    for (index, plane) in entries.into_iter().enumerate() {
+        let plane = plane.and_then(|plane| match ids.insert(plane.id) {
+            true => Ok(plane),
+            false => Err(ParseError::DuplicateEntry {
+                index,
+                field: "id",
+                value: plane.id.to_string(),
+            }),
+        });
+        parsed.push(plane, mode)?;
     }
+    Ok(parsed)
 }
 
 #[cfg(test)]
@@ -42,9 +78,29 @@ mod tests {
         let yaml_content = include_str!("../../assets/planes.yaml");
 
         let planes: PlanesConfig = serde_yaml::from_str(yaml_content).unwrap();
-        assert_eq!(planes.planes.len(), 3);
+        assert_eq!(planes.planes.len(), 12);
         assert_eq!(planes.planes[0].name, "Small Plane");
         assert_eq!(planes.planes[0].cost, 300000.0);
         assert_eq!(planes.planes[0].monthly_income, 0.0);
+        for plane in &planes.planes {
+            assert_eq!(plane.cabin().seats(), plane.seats, "{}", plane.name);
+        }
+    }
+
+    #[test]
+    fn test_parse_broken_planes() {
+        let yaml_content = include_str!("../../tests/fixtures/broken/planes.yaml");
+
+        let error = parse_planes_yaml(yaml_content, ParseMode::Strict).unwrap_err();
+        assert_eq!(error.line(), Some(10));
+
+        let parsed = parse_planes_yaml(yaml_content, ParseMode::Lenient).unwrap();
+        let ids: Vec<u32> = parsed.items.iter().map(|plane| plane.id).collect();
+        assert_eq!(ids, vec![0, 3]);
+        assert_eq!(parsed.skipped.len(), 2);
+        assert!(parsed.skipped[0]
+            .to_string()
+            .starts_with("entry 1: missing field `seats`"));
+        assert_eq!(parsed.skipped[1].to_string(), "entry 2: duplicate id '0'");
     }
 }
@@ -0,0 +1,275 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, ops::RangeInclusive, str::FromStr};
+
+use csv::{ByteRecord, ReaderBuilder};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("line {line}, column {column}: missing {field}")]
+    MissingField {
+        line: u64,
+        column: u64,
+        field: &'static str,
+    },
+    #[error("line {line}, column {column}: invalid {field} '{value}'")]
+    InvalidField {
+        line: u64,
+        column: u64,
+        field: &'static str,
+        value: String,
+    },
+    #[error("line {line}, column {column}: {field} {value} is out of range")]
+    OutOfRange {
+        line: u64,
+        column: u64,
+        field: &'static str,
+        value: f64,
+    },
+    #[error(
+        "line {line}, column {column}: duplicate {field} '{value}', first on line {first_line}"
+    )]
+    Duplicate {
+        line: u64,
+        column: u64,
+        field: &'static str,
+        value: String,
+        first_line: u64,
+    },
+    #[error("entry {index}: {source}")]
+    InvalidEntry {
+        index: usize,
+        source: serde_yaml::Error,
+    },
+    #[error("entry {index}: duplicate {field} '{value}'")]
+    DuplicateEntry {
+        index: usize,
+        field: &'static str,
+        value: String,
+    },
+}
+
+impl ParseError {
+    /// Line of the file the error was found on, starting at 1.
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            ParseError::Csv(error) => error.position().map(|position| position.line()),
+            ParseError::Yaml(error) => error.location().map(|location| location.line() as u64),
+            ParseError::MissingField { line, .. }
+            | ParseError::InvalidField { line, .. }
+            | ParseError::OutOfRange { line, .. }
+            | ParseError::Duplicate { line, .. } => Some(*line),
+            ParseError::InvalidEntry { .. } | ParseError::DuplicateEntry { .. } => None,
+        }
+    }
+
+    /// Column of the error, the field number for CSV files, starting at 1.
+    pub fn column(&self) -> Option<u64> {
+        match self {
+            ParseError::Yaml(error) => error.location().map(|location| location.column() as u64),
+            ParseError::MissingField { column, .. }
+            | ParseError::InvalidField { column, .. }
+            | ParseError::OutOfRange { column, .. }
+            | ParseError::Duplicate { column, .. } => Some(*column),
+            ParseError::Csv(_)
+            | ParseError::InvalidEntry { .. }
+            | ParseError::DuplicateEntry { .. } => None,
+        }
+    }
+}
+
+/// How rows that cannot be parsed are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// The first invalid row fails the whole file.
+    #[default]
+    Strict,
+    /// Invalid rows are skipped and reported.
+    Lenient,
+}
+
+/// Items parsed from a file together with the rows skipped in lenient mode.
+#[derive(Debug)]
+pub struct Parsed<T> {
+    pub items: Vec<T>,
+    pub skipped: Vec<ParseError>,
+}
+
+impl<T> Parsed<T> {
+    /// Collects the result of parsing one row or entry according to `mode`.
+    pub(crate) fn push(
+        &mut self,
+        item: Result<T, ParseError>,
+        mode: ParseMode,
+    ) -> Result<(), ParseError> {
+        match (item, mode) {
+            (Ok(item), _) => self.items.push(item),
+            (Err(error), ParseMode::Lenient) => self.skipped.push(error),
+            (Err(error), ParseMode::Strict) => return Err(error),
+        }
+        Ok(())
+    }
+}
+
+impl<T> Default for Parsed<T> {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            skipped: Vec::new(),
+        }
+    }
+}
+
+/// Parses every row of a CSV file with `parse_row`.
+pub(crate) fn parse_csv_rows<T>(
+    input: &str,
+    has_headers: bool,
+    mode: ParseMode,
+    mut parse_row: impl FnMut(&ByteRecord) -> Result<T, ParseError>,
+) -> Result<Parsed<T>, ParseError> {
+    let mut rdr = ReaderBuilder::new()
+        .has_headers(has_headers)
+        .flexible(true)
+        .delimiter(b',')
+        .from_reader(input.as_bytes());
+
+    let mut parsed = Parsed::default();
+    let mut record = ByteRecord::new();
+
+    while rdr.read_byte_record(&mut record)? {
+        parsed.push(parse_row(&record), mode)?;
+    }
+    Ok(parsed)
+}
+
+fn line(record: &ByteRecord) -> u64 {
+    record.position().map_or(0, |position| position.line())
+}
+
+/// Returns the field at `index` of a CSV record as text.
+pub(crate) fn csv_field<'a>(
+    record: &'a ByteRecord,
+    index: usize,
+    field: &'static str,
+) -> Result<&'a str, ParseError> {
+    let bytes = record.get(index).ok_or(ParseError::MissingField {
+        line: line(record),
+        column: index as u64 + 1,
+        field,
+    })?;
+    std::str::from_utf8(bytes).map_err(|_| ParseError::InvalidField {
+        line: line(record),
+        column: index as u64 + 1,
+        field,
+        value: String::from_utf8_lossy(bytes).into_owned(),
+    })
+}
+
+/// Parses the field at `index` of a CSV record.
+pub(crate) fn parse_csv_field<T: FromStr>(
+    record: &ByteRecord,
+    index: usize,
+    field: &'static str,
+) -> Result<T, ParseError> {
+    let value = csv_field(record, index, field)?;
+    value.parse().map_err(|_| ParseError::InvalidField {
+        line: line(record),
+        column: index as u64 + 1,
+        field,
+        value: value.to_string(),
+    })
+}
+
+/// Returns the field at `index` of a CSV record, `None` if it is missing,
+/// empty or `\\N`.
+pub(crate) fn optional_csv_field<'a>(
+    record: &'a ByteRecord,
+    index: usize,
+    field: &'static str,
+) -> Result<Option<&'a str>, ParseError> {
+    if record.get(index).is_none() {
+        return Ok(None);
+    }
+    let value = csv_field(record, index, field)?;
+    Ok((!value.is_empty() && value != "\\N").then_some(value))
+}
+
+/// Parses the field at `index` of a CSV record if it is given.
+pub(crate) fn parse_optional_csv_field<T: FromStr>(
+    record: &ByteRecord,
+    index: usize,
+    field: &'static str,
+) -> Result<Option<T>, ParseError> {
+    match optional_csv_field(record, index, field)? {
+        Some(_) => parse_csv_field(record, index, field).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Parses a number that has to be within `range`.
+pub(crate) fn parse_csv_field_in_range(
+    record: &ByteRecord,
+    index: usize,
+    field: &'static str,
+    range: RangeInclusive<f64>,
+) -> Result<f64, ParseError> {
+    let value: f64 = parse_csv_field(record, index, field)?;
+    if !range.contains(&value) {
+        return Err(ParseError::OutOfRange {
+            line: line(record),
+            column: index as u64 + 1,
+            field,
+            value,
+        });
+    }
+    Ok(value)
+}
+
+/// Parses a latitude in degrees between -90 and 90.
+pub(crate) fn parse_latitude(record: &ByteRecord, index: usize) -> Result<f64, ParseError> {
+    parse_csv_field_in_range(record, index, "latitude", -90.0..=90.0)
+}
+
+/// Parses a longitude in degrees between -180 and 180.
+pub(crate) fn parse_longitude(record: &ByteRecord, index: usize) -> Result<f64, ParseError> {
+    parse_csv_field_in_range(record, index, "longitude", -180.0..=180.0)
+}
+
+/// Remembers the lines values of a unique column were seen on.
+pub(crate) struct UniqueColumn<K> {
+    index: usize,
+    field: &'static str,
+    seen: HashMap<K, u64>,
+}
+
+impl<K: Eq + Hash + Display> UniqueColumn<K> {
+    pub(crate) fn new(index: usize, field: &'static str) -> Self {
+        Self {
+            index,
+            field,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Fails if an earlier record had the value.
+    pub(crate) fn check(&self, record: &ByteRecord, value: &K) -> Result<(), ParseError> {
+        match self.seen.get(value) {
+            Some(first_line) => Err(ParseError::Duplicate {
+                line: line(record),
+                column: self.index as u64 + 1,
+                field: self.field,
+                value: value.to_string(),
+                first_line: *first_line,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Remembers the value of a record that passed [`UniqueColumn::check`].
+    pub(crate) fn insert(&mut self, record: &ByteRecord, value: K) {
+        self.seen.insert(value, line(record));
+    }
+}
@@ -1,51 +1,43 @@
-use csv::{ByteRecord, ReaderBuilder};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::model::WorldHeritageSite;
+use crate::model::{PoiCategory, WorldHeritageSite};
+
+use super::parser::{
+    csv_field, parse_csv_field, parse_csv_rows, parse_latitude, parse_longitude, ParseError,
+    ParseMode, Parsed, UniqueColumn,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WorldHeritageSiteConfig(pub Value);
 
-pub fn parse_world_heritage_site_csv(input: &str) -> Vec<WorldHeritageSite> {
-    let mut rdr = ReaderBuilder::new()
-        .has_headers(true)
-        .delimiter(b',')
-        .from_reader(input.as_bytes());
+pub fn parse_world_heritage_site_csv(
+    input: &str,
+    mode: ParseMode,
+) -> Result<Parsed<WorldHeritageSite>, ParseError> {
+    let mut ids = UniqueColumn::new(4, "id");
 
-    let mut sites = Vec::new();
-    let mut record = ByteRecord::new();
+    parse_csv_rows(input, true, mode, |record| {
+        let id = parse_csv_field(record, 4, "id")?;
+        let name = csv_field(record, 6, "name")?.to_string();
+        let description = csv_field(record, 7, "description")?.to_string();
+        let lat = parse_latitude(record, 15)?;
+        let lon = parse_longitude(record, 14)?;
+        let category = PoiCategory::from_heritage_category(csv_field(record, 0, "category")?)
+            .unwrap_or_default();
 
-    while rdr.read_byte_record(&mut record).unwrap() {
-        let id = std::str::from_utf8(record.get(4).unwrap())
-            .unwrap()
-            .parse::<u64>()
-            .unwrap();
-        let name = std::str::from_utf8(record.get(6).unwrap())
-            .unwrap()
-            .to_string();
-        let description = std::str::from_utf8(record.get(7).unwrap())
-            .unwrap()
-            .to_string();
-        let lat = std::str::from_utf8(record.get(15).unwrap())
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
-        let lon = std::str::from_utf8(record.get(14).unwrap())
-            .unwrap()
-            .parse::<f64>()
-            .unwrap();
+        ids.check(record, &id)?;
+        ids.insert(record, id);
 
-        let site = WorldHeritageSite {
+        Ok(WorldHeritageSite {
             id,
             lat,
             lon,
             name,
             description,
-        };
-        sites.push(site);
-    }
-    sites
+            category,
+        })
+    })
 }
 
 #[cfg(test)]
@@ -56,7 +48,9 @@ mod tests {
     fn test_parse_csv() {
         let csv_data = include_str!("../../assets/whc-sites-2019.csv");
 
-        let sites = parse_world_heritage_site_csv(&csv_data);
+        let sites = parse_world_heritage_site_csv(&csv_data, ParseMode::Strict)
+            .unwrap()
+            .items;
 
         assert_eq!(sites.len(), 1121);
 
@@ -67,5 +61,9 @@ mod tests {
         );
         assert_eq!(sites[0].lat, 34.84694);
         assert_eq!(sites[0].lon, 67.82525);
+        assert_eq!(sites[0].category, PoiCategory::Culture);
+        assert!(sites
+            .iter()
+            .any(|site| site.category == PoiCategory::Nature));
     }
 }
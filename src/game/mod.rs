@@ -18,73 +18,222 @@ pub mod manager;
 
 pub use game_state::GameState;
 
-use crate::config::LevelConfig;
+use std::collections::HashMap;
+
+use crate::config::{LevelConfig, LevelOutcome, LossReason};
+use crate::model::identity::IdentityTrait;
+use crate::model::world_data::WorldDataSource;
+use crate::model::WorldDataGateway;
+use crate::simulation::high_scores::{HighScore, HighScores};
+use crate::simulation::replay::ReplayBranch;
+use crate::simulation::replay_player::ReplayPlayer;
 use crate::{simulation::Simulation, ui, Replay};
 
 #[derive(Resource)]
 pub struct GameResource {
     pub level: String,
+    pub level_config: LevelConfig,
+    pub outcome: LevelOutcome,
     pub simulation: Simulation,
     pub replay: Option<Replay>,
+    /// Playback controls of the replay, if one is played.
+    pub replay_player: Option<ReplayPlayer>,
+    /// Replay this game was branched from after taking control of it.
+    pub branch: Option<ReplayBranch>,
     pub game_over_cash: f64,
+    pub data_source: WorldDataSource,
+}
+
+/// Loads the world data of `data_source`, or the embedded world data if it
+/// cannot be loaded.
+fn world_data_gateway(data_source: &WorldDataSource) -> Box<dyn WorldDataGateway> {
+    data_source.load().unwrap_or_else(|error| {
+        log::error!("{}", error);
+        WorldDataSource::Embedded
+            .load()
+            .expect("Embedded world data is valid")
+    })
 }
 
 impl GameResource {
+    /// Creates the game for a level in `assets/levels` by its file name, or
+    /// the default level if it cannot be loaded.
     pub fn new(level: String) -> Self {
-        let level_config: LevelConfig = match level.as_str() {
-            "default" => LevelConfig::default(),
-            "level1" => serde_yaml::from_str(include_str!("../../assets/levels/level1.yaml"))
-                .expect("Failed to load level1"),
-            _ => panic!("Unknown level {}", level),
-        };
-        let world_data_gateway = {
-            #[cfg(not(feature = "web3"))]
-            {
-                crate::model::world_data::StringBasedWorldData::default()
-            }
-            #[cfg(feature = "web3")]
-            {
-                crate::model::world_data::web3_world_data::Web3WorldData::default()
-            }
-        };
+        Self::with_data_source(level, WorldDataSource::default())
+    }
+
+    /// Creates the game for a level of `data_source` by its file name, or the
+    /// default level if it cannot be loaded.
+    pub fn with_data_source(level: String, data_source: WorldDataSource) -> Self {
+        let level_config = LevelConfig::load(&level, &data_source).unwrap_or_else(|error| {
+            log::error!("{}", error);
+            LevelConfig::default()
+        });
+        Self::from_level(level, level_config, data_source)
+    }
+
+    pub fn from_level(
+        level: String,
+        level_config: LevelConfig,
+        data_source: WorldDataSource,
+    ) -> Self {
+        let simulation = level_config
+            .create_simulation(world_data_gateway(&data_source))
+            .unwrap_or_else(|error| {
+                log::error!("Failed to set up level {}: {}", level, error);
+                Simulation::new(
+                    level_config.environment.clone(),
+                    world_data_gateway(&data_source),
+                )
+            });
 
         Self {
             level,
-            simulation: Simulation::new(level_config.environment, Box::new(world_data_gateway)),
+            level_config,
+            outcome: LevelOutcome::Running,
+            simulation,
             replay: None,
+            replay_player: None,
+            branch: None,
             game_over_cash: 10_000.0,
+            data_source,
         }
     }
 
-    pub fn from_replay(replay: Replay) -> Self {
-        let world_data_gateway = {
-            #[cfg(not(feature = "web3"))]
-            {
-                crate::model::world_data::StringBasedWorldData::default()
-            }
-            #[cfg(feature = "web3")]
-            {
-                crate::model::world_data::web3_world_data::Web3WorldData::default()
-            }
+    pub fn from_replay(replay: Replay, data_source: WorldDataSource) -> Self {
+        let mut simulation = replay
+            .create_simulation(world_data_gateway(&data_source))
+            .unwrap_or_else(|error| {
+                log::error!("Failed to set up replay: {}", error);
+                Simulation::new(
+                    replay.initial_config.clone(),
+                    world_data_gateway(&data_source),
+                )
+            });
+        let replay_player = ReplayPlayer::new(&replay, &mut simulation);
+
+        let level_config = LevelConfig {
+            name: String::from("Replay"),
+            description: String::from("Replay of a recorded game"),
+            environment: replay.initial_config.clone(),
+            ..Default::default()
         };
-        let mut simulation =
-            Simulation::new(replay.initial_config.clone(), Box::new(world_data_gateway));
-        for timestamped_command in &replay.command_history {
-            simulation.add_command_timed(timestamped_command.clone());
-        }
 
         Self {
             level: String::from("replay"),
+            level_config,
+            outcome: LevelOutcome::Running,
             simulation,
             replay: Some(replay),
-            ..Default::default()
+            replay_player: Some(replay_player),
+            branch: None,
+            game_over_cash: 10_000.0,
+            data_source,
+        }
+    }
+
+    /// Stops playing the replay and continues it as a normal game from the
+    /// current timestamp.
+    pub fn take_control(&mut self) {
+        let Some(replay) = self.replay.take() else {
+            return;
+        };
+        self.replay_player = None;
+        self.simulation.take_control();
+        self.branch = Some(ReplayBranch {
+            timestamp: self.simulation.environment.timestamp,
+            parent: Box::new(replay),
+        });
+    }
+
+    /// Replay of the game so far, recording the replay it was branched from.
+    pub fn create_replay(&self) -> Replay {
+        Replay {
+            branch: self.branch.clone(),
+            ..Replay::from_simulation(&self.simulation)
         }
     }
+
+    /// Ends the level when the cash of the company is below `game_over_cash`
+    /// or the level is won or lost.
+    pub fn update_outcome(&mut self) -> LevelOutcome {
+        let environment = &self.simulation.environment;
+        let cash = environment.company_finances.cash(environment.timestamp);
+        self.outcome = if cash < self.game_over_cash {
+            LevelOutcome::Lost(LossReason::Bankrupt)
+        } else {
+            self.level_config.evaluate(environment)
+        };
+        self.outcome
+    }
 }
 
 impl Default for GameResource {
     fn default() -> Self {
-        Self::new(String::from("default"))
+        Self::new(String::from(crate::config::DEFAULT_LEVEL))
+    }
+}
+
+/// Persistent high scores and the verification results of their replays.
+#[derive(Default, Resource)]
+pub struct HighScoresResource {
+    pub high_scores: HighScores,
+    /// Rank of the score of the last finished game.
+    pub last_rank: Option<usize>,
+    /// Results of verifying the scores by rank.
+    pub verified: HashMap<usize, Result<(), String>>,
+}
+
+impl HighScoresResource {
+    /// Loads the stored high scores, or starts an empty table if they cannot
+    /// be loaded.
+    pub fn load() -> Self {
+        let high_scores = HighScores::load().unwrap_or_else(|error| {
+            log::error!("{}", error);
+            HighScores::default()
+        });
+        Self {
+            high_scores,
+            ..Default::default()
+        }
+    }
+
+    /// Records the score of a finished game, except for replays and games
+    /// branched from them, which have no level of their own.
+    pub fn record(&mut self, game_resource: &GameResource) {
+        if game_resource.replay.is_some() || game_resource.branch.is_some() {
+            return;
+        }
+        let simulation = &game_resource.simulation;
+        let replay = Replay::from_simulation(simulation);
+        let score = HighScore::new(
+            simulation.environment.identity.alias(),
+            game_resource.level.clone(),
+            game_resource.outcome == LevelOutcome::Won,
+            simulation,
+            &replay,
+        );
+        if let Err(error) = HighScores::save_replay(&score, &replay) {
+            log::error!("{}", error);
+        }
+        self.last_rank = self.high_scores.add(score);
+        self.verified.clear();
+        if let Err(error) = self.high_scores.save() {
+            log::error!("{}", error);
+        }
+    }
+
+    /// Verifies the score of `rank` by re-simulating its replay on the world
+    /// data of `data_source`.
+    pub fn verify(&mut self, rank: usize, data_source: &WorldDataSource) {
+        if let Some(score) = self.high_scores.scores.get(rank) {
+            let result = HighScores::load_replay(score)
+                .and_then(|replay| {
+                    score.verify(&replay, data_source, world_data_gateway(data_source))
+                })
+                .map_err(|error| error.to_string());
+            self.verified.insert(rank, result);
+        }
     }
 }
 
@@ -101,6 +250,7 @@ pub fn setup_game(app: &mut App, game_resource: GameResource) {
         .init_state::<GameState>()
         .insert_resource(game_resource)
         .insert_resource(ConfigResource::default())
+        .insert_resource(HighScoresResource::load())
         .add_systems(Startup, (setup_lights, load_config_assets))
         .add_systems(
             Update,
@@ -167,17 +317,17 @@ fn load_config_assets(mut config_resource: ResMut<ConfigResource>) {
 
 fn update_simulation_system(
     mut game_resource: ResMut<GameResource>,
+    mut high_scores: ResMut<HighScoresResource>,
     time: Res<Time>,
     mut game_state_next_state: ResMut<NextState<GameState>>,
 ) {
-    game_resource.simulation.update(time.delta());
-    if game_resource
-        .simulation
-        .environment
-        .company_finances
-        .cash(game_resource.simulation.environment.timestamp)
-        < game_resource.game_over_cash
-    {
+    let game_resource = &mut *game_resource;
+    match &mut game_resource.replay_player {
+        Some(replay_player) => replay_player.update(&mut game_resource.simulation, time.delta()),
+        None => game_resource.simulation.update(time.delta()),
+    }
+    if game_resource.update_outcome() != LevelOutcome::Running {
+        high_scores.record(&game_resource);
         game_state_next_state.set(GameState::GameOver);
     }
 }
@@ -8,12 +8,18 @@ pub mod simulation;
 pub mod ui;
 pub mod utils;
 
+pub use model::world_data::WorldDataSource;
 pub use simulation::replay::Replay;
 
 use bevy::prelude::*;
 
 #[cfg_attr(target_arch = "wasm32", wasm_bindgen::prelude::wasm_bindgen)]
 pub fn start() {
+    start_with_data_source(WorldDataSource::default());
+}
+
+/// Starts the game with the world data and levels of `data_source`.
+pub fn start_with_data_source(data_source: WorldDataSource) {
     let mut app = App::new();
     app.add_plugins(
         DefaultPlugins
@@ -31,7 +37,7 @@ pub fn start() {
     );
 
     let level = "level1".to_string();
-    let game_resource = game::GameResource::new(level);
+    let game_resource = game::GameResource::with_data_source(level, data_source);
 
     game::setup_game(&mut app, game_resource);
     log::info!("Starting game.");
@@ -39,6 +45,11 @@ pub fn start() {
 }
 
 pub fn start_from_replay(replay: Replay) {
+    start_from_replay_with_data_source(replay, WorldDataSource::default());
+}
+
+/// Plays `replay` on the world data of `data_source`.
+pub fn start_from_replay_with_data_source(replay: Replay, data_source: WorldDataSource) {
     let mut app = App::new();
     app.add_plugins(
         DefaultPlugins
@@ -55,7 +66,7 @@ pub fn start_from_replay(replay: Replay) {
             .set(AssetPlugin { ..default() }),
     );
 
-    let game_resource = game::GameResource::from_replay(replay);
+    let game_resource = game::GameResource::from_replay(replay, data_source);
 
     game::setup_game(&mut app, game_resource);
     log::info!("Starting game from replay.");
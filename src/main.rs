@@ -1,4 +1,4 @@
-use flyconomy::Replay;
+use flyconomy::{Replay, WorldDataSource};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -7,17 +7,42 @@ pub struct Opt {
     /// Starts the simulation by loading a replay from a file
     #[structopt(short = "r", long = "replay", parse(from_os_str))]
     replay: Option<std::path::PathBuf>,
+
+    /// Loads planes, airports and levels from a directory laid out like assets
+    #[structopt(short = "d", long = "data-dir", parse(from_os_str))]
+    data_dir: Option<std::path::PathBuf>,
+
+    /// Re-runs the replay without a window and checks it against its checkpoints
+    #[structopt(long = "verify", requires = "replay")]
+    verify: bool,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
     let opt = Opt::from_args();
+    let data_source = opt
+        .data_dir
+        .map_or(WorldDataSource::Embedded, WorldDataSource::Directory);
 
     if let Some(replay_path) = opt.replay {
         let replay = Replay::load_from_file(replay_path).expect("Failed to load replay from file");
-        flyconomy::start_from_replay(replay);
+        if opt.verify {
+            let world_data_gateway = data_source.load().expect("Failed to load world data");
+            match replay.verify(world_data_gateway) {
+                Ok(simulation) => println!(
+                    "Replay verified up to {} ms.",
+                    simulation.environment.timestamp
+                ),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+        } else {
+            flyconomy::start_from_replay_with_data_source(replay, data_source);
+        }
     } else {
-        flyconomy::start();
+        flyconomy::start_with_data_source(data_source);
     }
 }
 
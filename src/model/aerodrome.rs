@@ -1,53 +1,118 @@
 use serde::{Deserialize, Serialize};
 
+use super::{CategoryScores, Continent};
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Aerodrome {
     pub id: u64,
     pub lat: f64,
     pub lon: f64,
     pub name: String,
+    /// `IATA/ICAO` code, with `\N` for a missing part.
     pub code: String,
     pub interest_score: f32,
+    /// Interest in the points of interest nearby by their category.
+    #[serde(default)]
+    pub category_scores: CategoryScores,
     pub passengers: Option<u64>,
+    /// Length of the longest runway in meters, if known.
+    #[serde(default)]
+    pub runway_length: Option<f32>,
+    #[serde(default)]
+    pub city: String,
+    #[serde(default)]
+    pub country: String,
+    #[serde(default)]
+    pub iata: Option<String>,
+    #[serde(default)]
+    pub icao: Option<String>,
+    /// Elevation in meters.
+    #[serde(default)]
+    pub altitude: f32,
+    /// Offset of the local standard time to UTC in hours. Daylight saving
+    /// time is not modelled.
+    #[serde(default)]
+    pub utc_offset: Option<f32>,
 }
 
 impl Aerodrome {
     pub fn new(id: u64, lat: f64, lon: f64, name: String, code: String) -> Self {
+        let (iata, icao) = split_code(&code);
         Self {
             id,
             lat,
             lon,
             name,
             code,
-            interest_score: 0.0,
-            passengers: None,
+            iata,
+            icao,
+            ..Default::default()
         }
     }
 
     pub fn frankfurt() -> Self {
-        Self::new(
-            340,
-            50.033333,
-            8.570556,
-            "Frankfurt am Main Airport".to_string(),
-            "FRA/EDDF".to_string(),
-        )
+        Self {
+            city: "Frankfurt".to_string(),
+            country: "Germany".to_string(),
+            altitude: 111.0,
+            utc_offset: Some(1.0),
+            ..Self::new(
+                340,
+                50.033333,
+                8.570556,
+                "Frankfurt am Main Airport".to_string(),
+                "FRA/EDDF".to_string(),
+            )
+        }
     }
 
     pub fn paris() -> Self {
-        Self::new(
-            1382,
-            49.012798,
-            2.55,
-            "Charles de Gaulle International Airport".to_string(),
-            "CDG/LFPG".to_string(),
-        )
+        Self {
+            city: "Paris".to_string(),
+            country: "France".to_string(),
+            altitude: 119.0,
+            utc_offset: Some(1.0),
+            ..Self::new(
+                1382,
+                49.012798,
+                2.55,
+                "Charles de Gaulle International Airport".to_string(),
+                "CDG/LFPG".to_string(),
+            )
+        }
+    }
+
+    /// Whether `code` is the full `IATA/ICAO` code or one of its parts.
+    pub fn has_code(&self, code: &str) -> bool {
+        self.code == code
+            || self.iata.as_deref() == Some(code)
+            || self.icao.as_deref() == Some(code)
+    }
+
+    /// Whether the search `input` is part of the name, city, country or a code.
+    pub fn matches_search(&self, input: &str) -> bool {
+        let input = input.to_lowercase();
+        [&self.name, &self.city, &self.country, &self.code]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&input))
+    }
+
+    pub fn continent(&self) -> Continent {
+        Continent::from_coordinates(self.lat, self.lon)
     }
 }
 
+/// Splits an `IATA/ICAO` code into its parts, `\N` marks a missing part.
+pub fn split_code(code: &str) -> (Option<String>, Option<String>) {
+    let mut parts = code
+        .split('/')
+        .map(|part| (!part.is_empty() && part != "\\N").then(|| part.to_string()));
+    (parts.next().flatten(), parts.next().flatten())
+}
+
 #[cfg(test)]
 pub mod tests {
-    use super::Aerodrome;
+    use super::*;
 
     impl Aerodrome {
         pub fn san_francisco() -> Self {
@@ -70,4 +135,29 @@ pub mod tests {
             )
         }
     }
+
+    #[test]
+    fn test_split_code() {
+        assert_eq!(
+            split_code("FRA/EDDF"),
+            (Some("FRA".to_string()), Some("EDDF".to_string()))
+        );
+        assert_eq!(split_code("\\N/AYGA"), (None, Some("AYGA".to_string())));
+        assert_eq!(split_code("FRA"), (Some("FRA".to_string()), None));
+    }
+
+    #[test]
+    fn test_has_code_and_search() {
+        let frankfurt = Aerodrome::frankfurt();
+
+        assert!(frankfurt.has_code("FRA/EDDF"));
+        assert!(frankfurt.has_code("FRA"));
+        assert!(frankfurt.has_code("EDDF"));
+        assert!(!frankfurt.has_code("CDG"));
+
+        assert!(frankfurt.matches_search("germany"));
+        assert!(frankfurt.matches_search("Frankfurt"));
+        assert!(frankfurt.matches_search("eddf"));
+        assert!(!frankfurt.matches_search("Paris"));
+    }
 }
@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 
-use super::PlaneType;
+use super::{PlaneType, Timestamp};
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AirPlane {
     pub id: u64,
     pub base_id: u64,
     pub plane_type: PlaneType,
+    /// Timestamp the plane was bought at, to depreciate its value.
+    #[serde(default)]
+    pub purchased_at: Timestamp,
+    /// Price paid for the plane, refunded when it is sold.
+    #[serde(default)]
+    pub cost: f64,
 }
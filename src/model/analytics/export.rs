@@ -0,0 +1,321 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use serde::Serialize;
+use strum::{Display, EnumIter};
+use thiserror::Error;
+
+use crate::model::{Environment, FlightState, Timestamp};
+
+use super::{calculate_cash_history, route_name};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+pub enum ExportFormat {
+    #[default]
+    #[strum(serialize = "CSV")]
+    Csv,
+    #[strum(serialize = "JSON")]
+    Json,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Data sets that can be exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumIter)]
+pub enum ExportData {
+    #[strum(serialize = "Cash History")]
+    CashHistory,
+    Ledger,
+    Flights,
+    Fleet,
+}
+
+impl ExportData {
+    pub fn file_name(&self, format: ExportFormat) -> String {
+        let name = match self {
+            ExportData::CashHistory => "cash_history",
+            ExportData::Ledger => "ledger",
+            ExportData::Flights => "flights",
+            ExportData::Fleet => "fleet",
+        };
+        format!("{}.{}", name, format.extension())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Failed to write export: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to write CSV: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Failed to write JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CashRecord {
+    pub timestamp: Timestamp,
+    pub cash: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LedgerRecord {
+    pub timestamp: Timestamp,
+    /// `income` or `expense`.
+    pub kind: &'static str,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FlightRecord {
+    pub flight_id: u64,
+    pub airplane_id: u64,
+    pub plane_type: String,
+    /// Codes of the origin and the stopovers, e.g. `FRA/EDDF - CDG/LFPG`.
+    pub legs: String,
+    pub departure_time: Timestamp,
+    pub arrival_time: Option<Timestamp>,
+    pub state: &'static str,
+    pub distance: f64,
+    pub passengers: u32,
+    pub profit: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FleetRecord {
+    pub airplane_id: u64,
+    pub base_id: u64,
+    pub plane_type: String,
+    pub seats: u32,
+    pub speed: f32,
+    pub range: f32,
+    pub flights: usize,
+}
+
+pub fn cash_records(environment: &Environment) -> Vec<CashRecord> {
+    calculate_cash_history(environment)
+        .into_iter()
+        .map(|(timestamp, cash)| CashRecord { timestamp, cash })
+        .collect()
+}
+
+/// Income and expenses ordered by timestamp. They only contain merged hourly
+/// entries instead of the single bookings if
+/// [`crate::model::EnvironmentConfig::compact_history`] is set.
+pub fn ledger_records(environment: &Environment) -> Vec<LedgerRecord> {
+    let finances = &environment.company_finances;
+    let income = finances
+        .income
+        .entries()
+        .iter()
+        .map(|&(timestamp, amount)| LedgerRecord {
+            timestamp,
+            kind: "income",
+            amount,
+        });
+    let expenses = finances
+        .expenses
+        .entries()
+        .iter()
+        .map(|&(timestamp, amount)| LedgerRecord {
+            timestamp,
+            kind: "expense",
+            amount,
+        });
+    let mut records: Vec<LedgerRecord> = income.chain(expenses).collect();
+    records.sort_by_key(|record| record.timestamp);
+    records
+}
+
+pub fn flight_records(environment: &Environment) -> Vec<FlightRecord> {
+    environment
+        .flights
+        .iter()
+        .map(|flight| FlightRecord {
+            flight_id: flight.flight_id,
+            airplane_id: flight.airplane.id,
+            plane_type: flight.airplane.plane_type.name.clone(),
+            legs: route_name(flight),
+            departure_time: flight.departure_time,
+            arrival_time: flight.arrival_time,
+            state: match flight.state {
+                FlightState::Scheduled => "Scheduled",
+                FlightState::EnRoute { .. } => "EnRoute",
+                FlightState::Landed { .. } => "Landed",
+                FlightState::Finished => "Finished",
+            },
+            distance: flight.calculate_total_distance(),
+            passengers: flight.calculate_booked_seats(),
+            profit: flight.calculate_profit(),
+        })
+        .collect()
+}
+
+pub fn fleet_records(environment: &Environment) -> Vec<FleetRecord> {
+    environment
+        .planes
+        .iter()
+        .map(|plane| FleetRecord {
+            airplane_id: plane.id,
+            base_id: plane.base_id,
+            plane_type: plane.plane_type.name.clone(),
+            seats: plane.plane_type.seats,
+            speed: plane.plane_type.speed,
+            range: plane.plane_type.range,
+            flights: environment
+                .flights
+                .iter()
+                .filter(|flight| flight.airplane.id == plane.id)
+                .count(),
+        })
+        .collect()
+}
+
+fn write_records<W: Write, T: Serialize>(
+    mut writer: W,
+    records: &[T],
+    format: ExportFormat,
+) -> Result<(), ExportError> {
+    match format {
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            for record in records {
+                csv_writer.serialize(record)?;
+            }
+            csv_writer.flush()?;
+        }
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, records)?;
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_export<W: Write>(
+    writer: W,
+    environment: &Environment,
+    data: ExportData,
+    format: ExportFormat,
+) -> Result<(), ExportError> {
+    match data {
+        ExportData::CashHistory => write_records(writer, &cash_records(environment), format),
+        ExportData::Ledger => write_records(writer, &ledger_records(environment), format),
+        ExportData::Flights => write_records(writer, &flight_records(environment), format),
+        ExportData::Fleet => write_records(writer, &fleet_records(environment), format),
+    }
+}
+
+pub fn export_to_string(
+    environment: &Environment,
+    data: ExportData,
+    format: ExportFormat,
+) -> Result<String, ExportError> {
+    let mut buffer = vec![];
+    write_export(&mut buffer, environment, data, format)?;
+    Ok(String::from_utf8(buffer).expect("CSV and JSON are valid UTF-8"))
+}
+
+pub fn export_to_file(
+    filename: &str,
+    environment: &Environment,
+    data: ExportData,
+    format: ExportFormat,
+) -> Result<(), ExportError> {
+    let file = File::create(filename)?;
+    write_export(BufWriter::new(file), environment, data, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{AirPlane, EnvironmentConfig, Flight};
+
+    fn environment() -> Environment {
+        let mut environment = Environment::new(EnvironmentConfig {
+            start_capital: 1_000.0,
+            ..Default::default()
+        });
+        environment.timestamp = 100;
+        environment.add_expense(20, 300.0);
+        environment.add_income(10, 50.0);
+        environment.planes = vec![AirPlane {
+            id: 1,
+            ..Default::default()
+        }];
+        environment.flights = vec![Flight {
+            flight_id: 7,
+            airplane: AirPlane {
+                id: 1,
+                ..Default::default()
+            },
+            departure_time: 5,
+            arrival_time: Some(60),
+            state: FlightState::Finished,
+            ..Default::default()
+        }];
+        environment
+    }
+
+    #[test]
+    fn test_ledger_csv() {
+        let csv = export_to_string(&environment(), ExportData::Ledger, ExportFormat::Csv).unwrap();
+        assert_eq!(
+            csv,
+            "timestamp,kind,amount\n0,income,1000.0\n10,income,50.0\n20,expense,300.0\n"
+        );
+    }
+
+    #[test]
+    fn test_flights_csv() {
+        let csv = export_to_string(&environment(), ExportData::Flights, ExportFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(
+                "flight_id,airplane_id,plane_type,legs,departure_time,arrival_time,state,distance,passengers,profit"
+            )
+        );
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("7,1,Small Plane,FRA/EDDF - CDG/LFPG,5,60,Finished,"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn test_fleet_json() {
+        let json = export_to_string(&environment(), ExportData::Fleet, ExportFormat::Json).unwrap();
+        let fleet: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(fleet[0]["airplane_id"], 1);
+        assert_eq!(fleet[0]["plane_type"], "Small Plane");
+        assert_eq!(fleet[0]["flights"], 1);
+    }
+
+    #[test]
+    fn test_cash_history_json() {
+        let json =
+            export_to_string(&environment(), ExportData::CashHistory, ExportFormat::Json).unwrap();
+        let history: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(history.as_array().unwrap().len(), 100);
+        assert_eq!(history[99]["timestamp"], 100);
+        assert_eq!(history[99]["cash"], 750.0);
+    }
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(
+            ExportData::CashHistory.file_name(ExportFormat::Json),
+            "cash_history.json"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use crate::model::{Environment, Flight, Timestamp};
+
+use super::timestamp_samples;
+
+const HOUR: f64 = 60.0 * 60.0 * 1000.0;
+pub(super) const DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Delay after which a flight no longer counts as on time, like the industry
+/// standard of 15 minutes.
+pub const ON_TIME_TOLERANCE: Timestamp = 15 * 60 * 1000;
+
+/// Airline industry key performance indicators of the flights that landed in
+/// a period.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Kpis {
+    /// Available seat kilometers, seats times kilometers flown.
+    pub ask: f64,
+    /// Revenue passenger kilometers, booked seats times kilometers flown.
+    pub rpk: f64,
+    /// RPK per ASK.
+    pub load_factor: f64,
+    /// Revenue per ASK.
+    pub rask: f64,
+    /// Takeoff and fuel costs per ASK.
+    pub cask: f64,
+    /// Revenue per RPK.
+    pub yield_per_rpk: f64,
+    /// Hours in the air per aircraft of the fleet and day of the period.
+    pub block_hours_per_aircraft_day: f64,
+    /// Share of the flights that arrived within [`ON_TIME_TOLERANCE`] of
+    /// their planned arrival.
+    pub on_time_performance: f64,
+}
+
+/// Time a flight should take without delays, including the turnarounds.
+fn planned_duration(flight: &Flight) -> Timestamp {
+    let plane_type = &flight.airplane.plane_type;
+    let flight_time = flight.calculate_total_distance() / plane_type.speed.max(1.0) as f64 * HOUR;
+    flight_time as Timestamp + plane_type.turnaround_duration() * flight.stopovers.len() as u128
+}
+
+pub(super) fn is_on_time(flight: &Flight) -> bool {
+    flight.arrival_time.is_some_and(|arrival_time| {
+        arrival_time.saturating_sub(flight.departure_time)
+            <= planned_duration(flight) + ON_TIME_TOLERANCE
+    })
+}
+
+/// Hours the flight spent in the air.
+pub(super) fn block_hours(flight: &Flight) -> f64 {
+    flight.calculate_total_distance() / flight.airplane.plane_type.speed.max(1.0) as f64
+}
+
+pub(super) fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+/// KPIs of the flights that landed after `from` up to and including `to`.
+pub fn calculate_kpis(environment: &Environment, from: Timestamp, to: Timestamp) -> Kpis {
+    environment.metrics.kpis(from, to, environment.planes.len())
+}
+
+/// KPIs over the `window` before each of `samples` timestamps of the game.
+pub fn calculate_kpi_history(
+    environment: &Environment,
+    window: Timestamp,
+    samples: u128,
+) -> Vec<(Timestamp, Kpis)> {
+    timestamp_samples(environment.timestamp, samples)
+        .map(|timestamp| {
+            let from = timestamp.saturating_sub(window);
+            (timestamp, calculate_kpis(environment, from, timestamp))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{analytics::operating_costs, AirPlane, FlightState};
+
+    fn finished_flight(arrival_delay: Timestamp) -> Flight {
+        let flight = Flight {
+            departure_time: DAY / 2,
+            state: FlightState::Finished,
+            ..Default::default()
+        };
+        Flight {
+            arrival_time: Some(DAY / 2 + planned_duration(&flight) + arrival_delay),
+            ..flight
+        }
+    }
+
+    fn land(environment: &mut Environment, flight: Flight) {
+        let costs = operating_costs(environment, &flight);
+        environment
+            .metrics
+            .record_landing(flight.arrival_time.unwrap(), &flight, costs);
+        environment.flights.push(flight);
+    }
+
+    #[test]
+    fn test_calculate_kpis() {
+        let mut environment = Environment::default();
+        environment.timestamp = DAY;
+        environment.planes = vec![AirPlane::default(), AirPlane::default()];
+        land(&mut environment, finished_flight(0));
+        land(&mut environment, finished_flight(ON_TIME_TOLERANCE + 1));
+        environment.flights.push(Flight::default());
+
+        let flight = Flight::default();
+        let distance = flight.calculate_total_distance();
+        let kpis = calculate_kpis(&environment, 0, DAY);
+
+        assert!((kpis.ask - 2.0 * 150.0 * distance).abs() < 1e-6);
+        assert_eq!(kpis.rpk, kpis.ask);
+        assert_eq!(kpis.load_factor, 1.0);
+        assert!((kpis.rask - 2.0 * flight.calculate_profit() / kpis.ask).abs() < 1e-9);
+        assert!((kpis.cask - 2.0 * (500.0 + 0.5 * distance) / kpis.ask).abs() < 1e-9);
+        assert!((kpis.yield_per_rpk - kpis.rask).abs() < 1e-9);
+        assert!((kpis.block_hours_per_aircraft_day - distance / 800.0).abs() < 1e-6);
+        assert_eq!(kpis.on_time_performance, 0.5);
+
+        assert_eq!(calculate_kpis(&environment, 0, DAY / 2), Kpis::default());
+    }
+
+    #[test]
+    fn test_calculate_kpi_history() {
+        let mut environment = Environment::default();
+        environment.timestamp = 2 * DAY;
+        environment.planes = vec![AirPlane::default()];
+        land(&mut environment, finished_flight(0));
+
+        let history = calculate_kpi_history(&environment, DAY, 4);
+
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0].1.load_factor, 0.0);
+        assert_eq!(history[1].1.load_factor, 1.0);
+        assert_eq!(history[3].1.load_factor, 0.0);
+    }
+}
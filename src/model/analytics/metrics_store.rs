@@ -0,0 +1,169 @@
+use crate::model::{Flight, Ledger, Timestamp};
+
+use super::kpi::{block_hours, is_on_time, ratio, Kpis, DAY};
+
+/// Aggregates of the company's history, updated on landings so analytics do
+/// not need to scan all flights. The cash is read from the ledgers of the
+/// company finances.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsStore {
+    flight_distance: Ledger,
+    transported_passengers: Ledger,
+    flight_profit: Ledger,
+    landed_flights: Ledger,
+    available_seat_km: Ledger,
+    revenue_passenger_km: Ledger,
+    operating_costs: Ledger,
+    block_hours: Ledger,
+    on_time_flights: Ledger,
+    /// Snapshots of the net worth, which changes with time and can not be
+    /// summed up from its changes.
+    net_worth: Vec<(Timestamp, f64)>,
+}
+
+impl MetricsStore {
+    pub fn new(start_capital: f64) -> Self {
+        let mut store = Self::default();
+        store.record_net_worth(0, start_capital);
+        store
+    }
+
+    /// Records a finished flight with its takeoff and fuel costs at the
+    /// timestamp it arrived at.
+    pub fn record_landing(&mut self, timestamp: Timestamp, flight: &Flight, operating_costs: f64) {
+        let distance = flight.calculate_total_distance();
+        let booked_seats = flight.calculate_booked_seats() as f64;
+        self.flight_distance.push(timestamp, distance);
+        self.transported_passengers.push(timestamp, booked_seats);
+        self.flight_profit
+            .push(timestamp, flight.calculate_profit());
+        self.landed_flights.push(timestamp, 1.0);
+        self.available_seat_km.push(
+            timestamp,
+            flight.airplane.plane_type.seats as f64 * distance,
+        );
+        self.revenue_passenger_km
+            .push(timestamp, booked_seats * distance);
+        self.operating_costs.push(timestamp, operating_costs);
+        self.block_hours.push(timestamp, block_hours(flight));
+        if is_on_time(flight) {
+            self.on_time_flights.push(timestamp, 1.0);
+        }
+    }
+
+    pub fn record_net_worth(&mut self, timestamp: Timestamp, net_worth: f64) {
+        match self.net_worth.last_mut() {
+            Some(last) if last.0 == timestamp => last.1 = net_worth,
+            _ => self.net_worth.push((timestamp, net_worth)),
+        }
+    }
+
+    /// Net worth of the latest snapshot at or before `timestamp`.
+    pub fn net_worth(&self, timestamp: Timestamp) -> f64 {
+        match self
+            .net_worth
+            .partition_point(|snapshot| snapshot.0 <= timestamp)
+        {
+            0 => 0.0,
+            index => self.net_worth[index - 1].1,
+        }
+    }
+
+    pub fn flight_distance(&self, timestamp: Timestamp) -> f64 {
+        self.flight_distance.total_at(timestamp)
+    }
+
+    pub fn transported_passengers(&self, timestamp: Timestamp) -> u32 {
+        self.transported_passengers.total_at(timestamp).round() as u32
+    }
+
+    pub fn landed_flights(&self, timestamp: Timestamp) -> u32 {
+        self.landed_flights.total_at(timestamp).round() as u32
+    }
+
+    /// Average profit of the flights landed after `from` up to `to`.
+    pub fn average_profit_per_flight(&self, from: Timestamp, to: Timestamp) -> f64 {
+        let flights = self.landed_flights.total_between(from, to).round();
+        if flights > 0.0 {
+            self.flight_profit.total_between(from, to) / flights
+        } else {
+            0.0
+        }
+    }
+
+    /// KPIs of the flights landed after `from` up to `to` by a fleet of
+    /// `fleet_size` aircraft.
+    pub fn kpis(&self, from: Timestamp, to: Timestamp, fleet_size: usize) -> Kpis {
+        let ask = self.available_seat_km.total_between(from, to);
+        let rpk = self.revenue_passenger_km.total_between(from, to);
+        let revenue = self.flight_profit.total_between(from, to);
+        let costs = self.operating_costs.total_between(from, to);
+        let flights = self.landed_flights.total_between(from, to).round();
+        let on_time_flights = self.on_time_flights.total_between(from, to).round();
+        let aircraft_days = fleet_size as f64 * (to - from) as f64 / DAY as f64;
+        Kpis {
+            ask,
+            rpk,
+            load_factor: ratio(rpk, ask),
+            rask: ratio(revenue, ask),
+            cask: ratio(costs, ask),
+            yield_per_rpk: ratio(revenue, rpk),
+            block_hours_per_aircraft_day: ratio(
+                self.block_hours.total_between(from, to),
+                aircraft_days,
+            ),
+            on_time_performance: ratio(on_time_flights, flights),
+        }
+    }
+
+    /// Merges the history before `before` into buckets of `width`.
+    pub fn compact(&mut self, before: Timestamp, width: Timestamp) {
+        for series in [
+            &mut self.flight_distance,
+            &mut self.transported_passengers,
+            &mut self.flight_profit,
+            &mut self.landed_flights,
+            &mut self.available_seat_km,
+            &mut self.revenue_passenger_km,
+            &mut self.operating_costs,
+            &mut self.block_hours,
+            &mut self.on_time_flights,
+        ] {
+            series.compact(before, width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_store() {
+        let mut store = MetricsStore::new(1_000.0);
+        store.record_landing(20, &Flight::default(), 0.0);
+        store.record_landing(30, &Flight::default(), 0.0);
+
+        assert_eq!(store.landed_flights(30), 2);
+        assert_eq!(store.transported_passengers(25), 150);
+        assert!((store.flight_distance(30) - 1_800.0).abs() < 4.0);
+        assert_eq!(
+            store.average_profit_per_flight(20, 30),
+            Flight::default().calculate_profit()
+        );
+        assert_eq!(store.average_profit_per_flight(30, 40), 0.0);
+    }
+
+    #[test]
+    fn test_net_worth_snapshots() {
+        let mut store = MetricsStore::new(1_000.0);
+        store.record_net_worth(10, 900.0);
+        store.record_net_worth(20, 800.0);
+        store.record_net_worth(20, 850.0);
+
+        assert_eq!(store.net_worth(0), 1_000.0);
+        assert_eq!(store.net_worth(15), 900.0);
+        assert_eq!(store.net_worth(20), 850.0);
+        assert_eq!(store.net_worth(100), 850.0);
+    }
+}
@@ -1,7 +1,25 @@
-use super::{Environment, FlightState, Timestamp};
-
-#[cfg(feature = "rayon")]
-use rayon::iter::ParallelIterator;
+use super::{DemandFactors, Environment, Flight, Timestamp};
+
+mod export;
+mod kpi;
+mod metrics_store;
+mod reports;
+mod valuation;
+
+pub use export::{
+    cash_records, export_to_file, export_to_string, fleet_records, flight_records, ledger_records,
+    write_export, CashRecord, ExportData, ExportError, ExportFormat, FleetRecord, FlightRecord,
+    LedgerRecord,
+};
+pub use kpi::{calculate_kpi_history, calculate_kpis, Kpis, ON_TIME_TOLERANCE};
+pub use metrics_store::MetricsStore;
+pub use reports::{
+    aircraft_reports, base_reports, route_reports, ProfitabilityReport, ReportPeriod,
+};
+pub use valuation::{
+    calculate_valuation, depreciated_value, Valuation, AIRCRAFT_DEPRECIATION_PER_YEAR,
+    AIRCRAFT_RESIDUAL_VALUE, GOODWILL_PERIOD,
+};
 
 const SAMPLES: u128 = 100;
 
@@ -13,100 +31,133 @@ fn timestamp_samples(total_timestamps: u128, samples: u128) -> Box<dyn Iterator<
 }
 
 pub fn calculate_cash_history(environment: &Environment) -> Vec<(Timestamp, f64)> {
-    let mut cash_history = vec![];
-
-    for timestamp in timestamp_samples(environment.timestamp, SAMPLES) {
-        let cash = environment.company_finances.cash(timestamp);
-        cash_history.push((timestamp, cash));
-    }
+    timestamp_samples(environment.timestamp, SAMPLES)
+        .map(|timestamp| (timestamp, environment.company_finances.cash(timestamp)))
+        .collect()
+}
 
-    cash_history
+pub fn calculate_net_worth_history(environment: &Environment) -> Vec<(Timestamp, f64)> {
+    timestamp_samples(environment.timestamp, SAMPLES)
+        .map(|timestamp| (timestamp, environment.metrics.net_worth(timestamp)))
+        .collect()
 }
 
 pub fn calculate_total_flight_distance(environment: &Environment) -> Vec<(Timestamp, f64)> {
-    let mut flight_distance_history = vec![];
-
-    for timestamp in timestamp_samples(environment.timestamp, SAMPLES) {
-        let total_distance = environment
-            .iter_flights()
-            .filter(|flight| {
-                flight.state == FlightState::Finished && flight.arrival_time.unwrap() <= timestamp
-            })
-            .map(|flight| flight.calculate_total_distance())
-            .sum();
-        flight_distance_history.push((timestamp, total_distance));
-    }
-
-    flight_distance_history
+    timestamp_samples(environment.timestamp, SAMPLES)
+        .map(|timestamp| (timestamp, environment.metrics.flight_distance(timestamp)))
+        .collect()
 }
 
 pub fn calculate_transported_passengers(environment: &Environment) -> Vec<(Timestamp, u32)> {
-    let mut transported_passengers_history = vec![];
-
-    for timestamp in timestamp_samples(environment.timestamp, SAMPLES) {
-        let total_passengers = environment
-            .iter_flights()
-            .filter(|flight| {
-                flight.state == FlightState::Finished && flight.arrival_time.unwrap() <= timestamp
-            })
-            .map(|flight| flight.calculate_booked_seats())
-            .sum();
-        transported_passengers_history.push((timestamp, total_passengers));
-    }
-
-    transported_passengers_history
+    timestamp_samples(environment.timestamp, SAMPLES)
+        .map(|timestamp| {
+            (
+                timestamp,
+                environment.metrics.transported_passengers(timestamp),
+            )
+        })
+        .collect()
 }
 
 pub fn calculate_average_profit_per_flight(environment: &Environment) -> Vec<(Timestamp, f64)> {
-    let mut average_profit_history = vec![];
-
     let seven_days_in_timestamps = 7 * 24 * 60 * 60 * 1000;
 
-    for timestamp in timestamp_samples(environment.timestamp, SAMPLES) {
-        let flights_in_last_seven_days = environment.iter_flights().filter(|flight| {
-            flight.state == FlightState::Finished
-                && flight.arrival_time.unwrap() <= timestamp
-                && flight.arrival_time.unwrap() > timestamp - seven_days_in_timestamps
-        });
+    timestamp_samples(environment.timestamp, SAMPLES)
+        .map(|timestamp| {
+            let from = timestamp.saturating_sub(seven_days_in_timestamps);
+            (
+                timestamp,
+                environment
+                    .metrics
+                    .average_profit_per_flight(from, timestamp),
+            )
+        })
+        .collect()
+}
 
-        let flight_count = flights_in_last_seven_days.clone().count();
+/// Average load factor of a route and the demand factors behind it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteDemand {
+    /// Codes of the origin and the stopovers, e.g. `FRA/EDDF - CDG/LFPG`.
+    pub route: String,
+    pub flights: usize,
+    pub load_factor: f32,
+    pub demand: DemandFactors,
+}
 
-        let total_profit: f64 = flights_in_last_seven_days
-            .map(|flight| flight.calculate_profit())
-            .sum();
+/// Codes of the origin and the stopovers of a flight, e.g. `FRA/EDDF - CDG/LFPG`.
+fn route_name(flight: &Flight) -> String {
+    std::iter::once(&flight.origin_aerodrome)
+        .chain(&flight.stopovers)
+        .map(|aerodrome| aerodrome.code.as_str())
+        .collect::<Vec<_>>()
+        .join(" - ")
+}
 
-        let average_profit = if flight_count > 0 {
-            total_profit / flight_count as f64
-        } else {
-            0.0
-        };
+/// Takeoff and fuel costs of a flight.
+pub(crate) fn operating_costs(environment: &Environment, flight: &Flight) -> f64 {
+    environment.config.takeoff_cost
+        + environment.config.fuel_cost_per_km * flight.calculate_total_distance()
+}
 
-        average_profit_history.push((timestamp, average_profit));
+/// Demand of every route flown or scheduled, busiest route first.
+pub fn calculate_route_demand(environment: &Environment) -> Vec<RouteDemand> {
+    let mut routes: Vec<(String, Vec<&Flight>)> = Vec::new();
+    for flight in &environment.flights {
+        let route = route_name(flight);
+        match routes.iter_mut().find(|(name, _)| *name == route) {
+            Some((_, flights)) => flights.push(flight),
+            None => routes.push((route, vec![flight])),
+        }
     }
 
-    average_profit_history
+    let mut route_demand: Vec<RouteDemand> = routes
+        .into_iter()
+        .map(|(route, flights)| {
+            let count = flights.len() as f32;
+            let average = |factor: fn(&Flight) -> f32| {
+                flights.iter().map(|flight| factor(flight)).sum::<f32>() / count
+            };
+            RouteDemand {
+                route,
+                flights: flights.len(),
+                load_factor: average(Flight::load_factor),
+                demand: DemandFactors {
+                    season: average(|flight| flight.demand.season),
+                    weekday: average(|flight| flight.demand.weekday),
+                    time_of_day: average(|flight| flight.demand.time_of_day),
+                },
+            }
+        })
+        .collect();
+    route_demand.sort_by_key(|route| std::cmp::Reverse(route.flights));
+    route_demand
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::model::{CompanyFinances, Flight};
+    use crate::model::{Aerodrome, EnvironmentConfig, FlightState};
+
+    fn land_flights(environment: &mut Environment) {
+        for flight in &environment.flights {
+            let costs = operating_costs(environment, flight);
+            environment
+                .metrics
+                .record_landing(flight.arrival_time.unwrap(), flight, costs);
+        }
+    }
 
     #[test]
     fn test_calculate_cash_history() {
-        let company_finances = CompanyFinances::new(0.0);
-
-        let mut environment = Environment {
-            timestamp: 0,
-            company_finances,
+        let mut environment = Environment::new(EnvironmentConfig {
+            start_capital: 0.0,
             ..Default::default()
-        };
+        });
 
         for _ in 0..1000 {
             environment.timestamp += 1;
-            environment
-                .company_finances
-                .add_income(environment.timestamp, 1.0);
+            environment.add_income(environment.timestamp, 1.0);
         }
 
         let cash_history = calculate_cash_history(&environment);
@@ -137,6 +188,7 @@ mod tests {
                 ..Default::default()
             })
             .collect();
+        land_flights(&mut environment);
 
         let flight_distance_history = calculate_total_flight_distance(&environment);
 
@@ -166,6 +218,7 @@ mod tests {
                 ..Default::default()
             })
             .collect();
+        land_flights(&mut environment);
 
         let passengers_history = calculate_transported_passengers(&environment);
 
@@ -180,4 +233,34 @@ mod tests {
         // 20 times flight between Frankfurt and Paris with 150 passengers each
         assert_eq!(passengers_history.last().unwrap().1, 3000);
     }
+
+    #[test]
+    fn test_calculate_route_demand() {
+        let mut environment = Environment::default();
+        let night = DemandFactors {
+            time_of_day: 0.5,
+            ..Default::default()
+        };
+        environment.flights = vec![
+            Flight::default(),
+            Flight {
+                demand: night,
+                ..Default::default()
+            },
+            Flight {
+                stopovers: vec![Aerodrome::new_york()],
+                ..Default::default()
+            },
+        ];
+
+        let route_demand = calculate_route_demand(&environment);
+
+        assert_eq!(route_demand.len(), 2);
+        assert_eq!(route_demand[0].route, "FRA/EDDF - CDG/LFPG");
+        assert_eq!(route_demand[0].flights, 2);
+        assert_eq!(route_demand[0].demand.time_of_day, 0.75);
+        assert_eq!(route_demand[0].load_factor, 0.75);
+        assert_eq!(route_demand[1].flights, 1);
+        assert_eq!(route_demand[1].load_factor, 1.0);
+    }
 }
@@ -0,0 +1,287 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+use crate::model::{Environment, Flight, FlightState, Timestamp};
+
+use super::{operating_costs, route_name};
+
+const DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Time span covered by a report, ending at the current timestamp.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display, EnumIter)]
+pub enum ReportPeriod {
+    #[strum(serialize = "Last Day")]
+    LastDay,
+    #[strum(serialize = "Last Week")]
+    LastWeek,
+    #[strum(serialize = "Last 30 Days")]
+    LastMonth,
+    #[default]
+    #[strum(serialize = "All Time")]
+    AllTime,
+}
+
+impl ReportPeriod {
+    /// First and last timestamp of the period, both inclusive.
+    pub fn range(&self, now: Timestamp) -> (Timestamp, Timestamp) {
+        let from = match self {
+            ReportPeriod::LastDay => now.saturating_sub(DAY),
+            ReportPeriod::LastWeek => now.saturating_sub(7 * DAY),
+            ReportPeriod::LastMonth => now.saturating_sub(30 * DAY),
+            ReportPeriod::AllTime => 0,
+        };
+        (from, now)
+    }
+
+    pub fn contains(&self, now: Timestamp, timestamp: Timestamp) -> bool {
+        let (from, to) = self.range(now);
+        (from..=to).contains(&timestamp)
+    }
+}
+
+/// Profitability of a route, an aircraft or a base over a period.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProfitabilityReport {
+    pub name: String,
+    /// Income of the flights that landed.
+    pub revenue: f64,
+    /// Takeoff and fuel costs of the flights that departed.
+    pub costs: f64,
+    /// Finished flights.
+    pub flights: usize,
+    pub seats: u32,
+    pub booked_seats: u32,
+    /// Hours in the air of the finished flights.
+    pub utilisation_hours: f64,
+}
+
+impl ProfitabilityReport {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    fn add_flight(&mut self, environment: &Environment, flight: &Flight) {
+        let distance = flight.calculate_total_distance();
+        if flight.state != FlightState::Scheduled {
+            self.costs += operating_costs(environment, flight);
+        }
+        if flight.state == FlightState::Finished {
+            self.revenue += flight.calculate_profit();
+            self.flights += 1;
+            self.seats += flight.airplane.plane_type.seats;
+            self.booked_seats += flight.calculate_booked_seats();
+            self.utilisation_hours += distance / flight.airplane.plane_type.speed.max(1.0) as f64;
+        }
+    }
+
+    pub fn profit(&self) -> f64 {
+        self.revenue - self.costs
+    }
+
+    /// Profit per revenue, zero without revenue.
+    pub fn margin(&self) -> f64 {
+        if self.revenue > 0.0 {
+            self.profit() / self.revenue
+        } else {
+            0.0
+        }
+    }
+
+    /// Booked seats per seat of the finished flights.
+    pub fn load_factor(&self) -> f64 {
+        if self.seats > 0 {
+            self.booked_seats as f64 / self.seats as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Groups the flights departed in the period by `key` into reports, most
+/// profitable first. Groups in `empty` are reported even without flights.
+fn reports_by<K: Ord>(
+    environment: &Environment,
+    period: ReportPeriod,
+    empty: impl IntoIterator<Item = (K, String)>,
+    key: impl Fn(&Flight) -> (K, String),
+) -> Vec<ProfitabilityReport> {
+    let mut reports: BTreeMap<K, ProfitabilityReport> = empty
+        .into_iter()
+        .map(|(key, name)| (key, ProfitabilityReport::new(name)))
+        .collect();
+    for flight in environment
+        .flights
+        .iter()
+        .filter(|flight| period.contains(environment.timestamp, flight.departure_time))
+    {
+        let (key, name) = key(flight);
+        reports
+            .entry(key)
+            .or_insert_with(|| ProfitabilityReport::new(name))
+            .add_flight(environment, flight);
+    }
+
+    let mut reports: Vec<ProfitabilityReport> = reports.into_values().collect();
+    reports.sort_by(|a, b| b.profit().total_cmp(&a.profit()));
+    reports
+}
+
+/// Reports per route, named by the codes of the origin and the stopovers.
+pub fn route_reports(environment: &Environment, period: ReportPeriod) -> Vec<ProfitabilityReport> {
+    reports_by(environment, period, [], |flight| {
+        let route = route_name(flight);
+        (route.clone(), route)
+    })
+}
+
+/// Reports per aircraft, including the aircraft that did not fly.
+pub fn aircraft_reports(
+    environment: &Environment,
+    period: ReportPeriod,
+) -> Vec<ProfitabilityReport> {
+    let aircraft_name = |id: u64, plane_type: &str| format!("#{} {}", id, plane_type);
+    reports_by(
+        environment,
+        period,
+        environment
+            .planes
+            .iter()
+            .map(|plane| (plane.id, aircraft_name(plane.id, &plane.plane_type.name))),
+        |flight| {
+            let plane = &flight.airplane;
+            (plane.id, aircraft_name(plane.id, &plane.plane_type.name))
+        },
+    )
+}
+
+/// Reports per base by the base of the aircraft, including the bases
+/// without flights.
+pub fn base_reports(environment: &Environment, period: ReportPeriod) -> Vec<ProfitabilityReport> {
+    let base_name = |base_id: u64| {
+        environment
+            .bases
+            .iter()
+            .find(|base| base.id == base_id)
+            .map(|base| base.aerodrome.code.clone())
+            .unwrap_or_else(|| format!("Base #{}", base_id))
+    };
+    reports_by(
+        environment,
+        period,
+        environment
+            .bases
+            .iter()
+            .map(|base| (base.id, base.aerodrome.code.clone())),
+        |flight| (flight.airplane.base_id, base_name(flight.airplane.base_id)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Aerodrome, AirPlane, Base};
+
+    fn finished_flight(airplane_id: u64, departure_time: Timestamp) -> Flight {
+        Flight {
+            airplane: AirPlane {
+                id: airplane_id,
+                base_id: 1,
+                ..Default::default()
+            },
+            departure_time,
+            state: FlightState::Finished,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_report_period() {
+        let now = 10 * DAY;
+        assert_eq!(ReportPeriod::LastDay.range(now), (9 * DAY, now));
+        assert_eq!(ReportPeriod::LastWeek.range(now), (3 * DAY, now));
+        assert_eq!(ReportPeriod::LastMonth.range(now), (0, now));
+        assert!(ReportPeriod::AllTime.contains(now, 0));
+        assert!(!ReportPeriod::LastDay.contains(now, DAY));
+    }
+
+    #[test]
+    fn test_profitability_reports() {
+        let mut environment = Environment::default();
+        environment.timestamp = 10 * DAY;
+        environment.bases = vec![
+            Base {
+                id: 1,
+                aerodrome: Aerodrome::frankfurt(),
+                airplane_ids: vec![1, 2],
+                cost: 0.0,
+            },
+            Base {
+                id: 2,
+                aerodrome: Aerodrome::paris(),
+                airplane_ids: vec![],
+                cost: 0.0,
+            },
+        ];
+        environment.planes = vec![
+            AirPlane {
+                id: 1,
+                base_id: 1,
+                ..Default::default()
+            },
+            AirPlane {
+                id: 3,
+                base_id: 1,
+                ..Default::default()
+            },
+        ];
+        environment.flights = vec![
+            finished_flight(1, 0),
+            finished_flight(1, 9 * DAY + 1),
+            finished_flight(2, 9 * DAY + 2),
+            Flight {
+                departure_time: 11 * DAY,
+                ..finished_flight(2, 0)
+            },
+            Flight {
+                state: FlightState::Scheduled,
+                ..finished_flight(1, 9 * DAY + 3)
+            },
+        ];
+        let flight = Flight::default();
+        let revenue = flight.calculate_profit();
+        let costs = 500.0 + 0.5 * flight.calculate_total_distance();
+
+        let routes = route_reports(&environment, ReportPeriod::AllTime);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].name, "FRA/EDDF - CDG/LFPG");
+        assert_eq!(routes[0].flights, 3);
+        assert!((routes[0].revenue - 3.0 * revenue).abs() < 1e-6);
+        assert!((routes[0].costs - 3.0 * costs).abs() < 1e-6);
+        assert_eq!(routes[0].load_factor(), 1.0);
+        assert!((routes[0].margin() - (revenue - costs) / revenue).abs() < 1e-9);
+        assert!(
+            (routes[0].utilisation_hours - 3.0 * flight.calculate_total_distance() / 800.0).abs()
+                < 1e-6
+        );
+
+        let aircraft = aircraft_reports(&environment, ReportPeriod::LastDay);
+        assert_eq!(aircraft.len(), 3);
+        assert_eq!(aircraft[0].flights, 1);
+        assert_eq!(aircraft[1].flights, 1);
+        assert_eq!(aircraft[2].name, "#3 Small Plane");
+        assert_eq!(aircraft[2].flights, 0);
+        assert_eq!(aircraft[2].margin(), 0.0);
+
+        let bases = base_reports(&environment, ReportPeriod::LastDay);
+        assert_eq!(bases.len(), 2);
+        assert_eq!(bases[0].name, "FRA/EDDF");
+        assert_eq!(bases[0].flights, 2);
+        assert_eq!(bases[1].name, "CDG/LFPG");
+        assert_eq!(bases[1].revenue, 0.0);
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use crate::model::{Environment, FlightState, Timestamp};
+
+use super::{operating_costs, route_name};
+
+const YEAR: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Share of its price a plane loses in value per year.
+pub const AIRCRAFT_DEPRECIATION_PER_YEAR: f64 = 0.1;
+
+/// Share of its price a plane keeps regardless of its age.
+pub const AIRCRAFT_RESIDUAL_VALUE: f64 = 0.2;
+
+/// Time of route profits counted as goodwill.
+pub const GOODWILL_PERIOD: Timestamp = 30 * 24 * 60 * 60 * 1000;
+
+/// Value of the company split into its assets and liabilities.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Valuation {
+    /// Positive cash.
+    pub cash: f64,
+    /// Depreciated value of the planes.
+    pub aircraft: f64,
+    /// Price paid for the bases.
+    pub bases: f64,
+    /// Price paid for the landing rights.
+    pub landing_rights: f64,
+    /// Negative cash.
+    pub debt: f64,
+    /// Operating profit of the profitable routes in the last
+    /// [`GOODWILL_PERIOD`].
+    pub goodwill: f64,
+}
+
+impl Valuation {
+    pub fn net_worth(&self) -> f64 {
+        self.cash + self.aircraft + self.bases + self.landing_rights - self.debt + self.goodwill
+    }
+}
+
+/// Values a plane bought for `cost` at `purchased_at`, losing
+/// [`AIRCRAFT_DEPRECIATION_PER_YEAR`] of its price per year down to the
+/// [`AIRCRAFT_RESIDUAL_VALUE`].
+pub fn depreciated_value(cost: f64, purchased_at: Timestamp, timestamp: Timestamp) -> f64 {
+    let years = timestamp.saturating_sub(purchased_at) as f64 / YEAR;
+    cost * (1.0 - AIRCRAFT_DEPRECIATION_PER_YEAR * years).max(AIRCRAFT_RESIDUAL_VALUE)
+}
+
+/// Values the company at `timestamp` with the assets it currently owns.
+pub fn calculate_valuation(environment: &Environment, timestamp: Timestamp) -> Valuation {
+    let cash = environment.company_finances.cash(timestamp);
+    let aircraft = environment
+        .planes
+        .iter()
+        .filter(|plane| plane.purchased_at <= timestamp)
+        .map(|plane| depreciated_value(plane.cost, plane.purchased_at, timestamp))
+        .sum();
+
+    let mut route_profits: HashMap<String, f64> = HashMap::new();
+    for flight in environment.flights.iter().filter(|flight| {
+        flight.state == FlightState::Finished
+            && flight.arrival_time.is_some_and(|arrival_time| {
+                arrival_time > timestamp.saturating_sub(GOODWILL_PERIOD)
+                    && arrival_time <= timestamp
+            })
+    }) {
+        *route_profits.entry(route_name(flight)).or_default() +=
+            flight.calculate_profit() - operating_costs(environment, flight);
+    }
+
+    Valuation {
+        cash: cash.max(0.0),
+        aircraft,
+        bases: environment.bases.iter().map(|base| base.cost).sum(),
+        landing_rights: environment
+            .landing_rights
+            .iter()
+            .map(|landing_rights| landing_rights.cost)
+            .sum(),
+        debt: (-cash).max(0.0),
+        goodwill: route_profits.values().map(|profit| profit.max(0.0)).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Aerodrome, AirPlane, Base, EnvironmentConfig, Flight, LandingRights};
+
+    const DAY: Timestamp = 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn test_depreciated_value() {
+        assert_eq!(depreciated_value(1_000.0, 0, 0), 1_000.0);
+        assert!((depreciated_value(1_000.0, 0, 365 * DAY) - 900.0).abs() < 1e-6);
+        assert!((depreciated_value(1_000.0, 365 * DAY, 730 * DAY) - 900.0).abs() < 1e-6);
+        assert_eq!(depreciated_value(1_000.0, 0, 20 * 365 * DAY), 200.0);
+    }
+
+    #[test]
+    fn test_calculate_valuation() {
+        let mut environment = Environment::new(EnvironmentConfig {
+            start_capital: 100_000.0,
+            ..Default::default()
+        });
+        environment.timestamp = 365 * DAY;
+        environment.planes = vec![AirPlane {
+            cost: 100_000.0,
+            ..Default::default()
+        }];
+        environment.bases = vec![Base {
+            id: 1,
+            aerodrome: Aerodrome::frankfurt(),
+            airplane_ids: vec![0],
+            cost: 450_000.0,
+        }];
+        environment.landing_rights = vec![LandingRights {
+            id: 2,
+            aerodrome: Aerodrome::paris(),
+            cost: 100_000.0,
+        }];
+        let flight = Flight {
+            state: FlightState::Finished,
+            arrival_time: Some(360 * DAY),
+            ..Default::default()
+        };
+        let route_profit = flight.calculate_profit() - operating_costs(&environment, &flight);
+        environment.flights = vec![
+            flight.clone(),
+            Flight {
+                arrival_time: Some(300 * DAY),
+                ..flight
+            },
+        ];
+
+        let valuation = calculate_valuation(&environment, environment.timestamp);
+
+        assert_eq!(valuation.cash, 100_000.0);
+        assert!((valuation.aircraft - 90_000.0).abs() < 1e-6);
+        assert_eq!(valuation.bases, 450_000.0);
+        assert_eq!(valuation.landing_rights, 100_000.0);
+        assert_eq!(valuation.debt, 0.0);
+        assert!((valuation.goodwill - route_profit).abs() < 1e-6);
+        assert!(
+            (valuation.net_worth() - (740_000.0 + route_profit)).abs() < 1e-6,
+            "{:?}",
+            valuation
+        );
+
+        environment.add_expense(environment.timestamp, 150_000.0);
+        let valuation = calculate_valuation(&environment, environment.timestamp);
+        assert_eq!(valuation.cash, 0.0);
+        assert_eq!(valuation.debt, 50_000.0);
+    }
+}
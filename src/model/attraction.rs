@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::PoiCategory;
+
 #[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Attraction {
     pub id: u64,
@@ -7,6 +9,8 @@ pub struct Attraction {
     pub lon: f64,
     pub name: String,
     pub description: String,
+    #[serde(default)]
+    pub category: PoiCategory,
 }
 
 impl Attraction {
@@ -17,6 +21,7 @@ impl Attraction {
             lon,
             name,
             description,
+            category: PoiCategory::default(),
         }
     }
 }
@@ -5,4 +5,6 @@ pub struct Base {
     pub id: u64,
     pub aerodrome: Aerodrome,
     pub airplane_ids: Vec<u64>,
+    /// Price paid for the base, refunded when it is removed.
+    pub cost: f64,
 }
@@ -0,0 +1,158 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Aerodrome, Timestamp};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMAT: &str = "%a %Y-%m-%d %H:%M";
+
+/// Maps timestamps, milliseconds since the start of the game, to dates.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GameCalendar {
+    /// Date the game starts on at midnight UTC, e.g. `2000-01-01`.
+    #[serde(
+        serialize_with = "serialize_date",
+        deserialize_with = "deserialize_date"
+    )]
+    pub start_date: NaiveDate,
+}
+
+impl GameCalendar {
+    pub fn new(start_date: NaiveDate) -> Self {
+        Self { start_date }
+    }
+
+    /// Date and time in UTC.
+    pub fn date_time(&self, timestamp: Timestamp) -> NaiveDateTime {
+        self.start_date.and_hms_opt(0, 0, 0).unwrap() + Duration::milliseconds(timestamp as i64)
+    }
+
+    /// Date and time in the local standard time of the aerodrome, daylight
+    /// saving time is ignored.
+    pub fn local_date_time(&self, timestamp: Timestamp, aerodrome: &Aerodrome) -> NaiveDateTime {
+        self.date_time(timestamp) + Self::utc_offset(aerodrome)
+    }
+
+    /// Timestamp of a date and time in UTC, `None` if it is before the start.
+    pub fn timestamp(&self, date_time: NaiveDateTime) -> Option<Timestamp> {
+        let millis = (date_time - self.date_time(0)).num_milliseconds();
+        Timestamp::try_from(millis).ok()
+    }
+
+    pub fn year(&self, timestamp: Timestamp) -> i32 {
+        self.date_time(timestamp).year()
+    }
+
+    pub fn weekday(&self, timestamp: Timestamp) -> Weekday {
+        self.date_time(timestamp).weekday()
+    }
+
+    /// Formats the date and time in UTC, e.g. `Sat 2000-01-01 00:00`.
+    pub fn format(&self, timestamp: Timestamp) -> String {
+        self.date_time(timestamp)
+            .format(DATE_TIME_FORMAT)
+            .to_string()
+    }
+
+    /// Formats the local date and time of the aerodrome with its UTC offset,
+    /// e.g. `Sat 2000-01-01 01:00 (UTC+1)`.
+    pub fn format_local(&self, timestamp: Timestamp, aerodrome: &Aerodrome) -> String {
+        let offset = Self::utc_offset(aerodrome);
+        let hours = offset.num_minutes() as f64 / 60.0;
+        format!(
+            "{} (UTC{:+})",
+            self.local_date_time(timestamp, aerodrome)
+                .format(DATE_TIME_FORMAT),
+            hours
+        )
+    }
+
+    /// Offset of the aerodrome's local time to UTC, estimated from the
+    /// longitude for aerodromes without a known offset.
+    fn utc_offset(aerodrome: &Aerodrome) -> Duration {
+        let hours = aerodrome
+            .utc_offset
+            .map_or_else(|| (aerodrome.lon / 15.0).round(), f64::from);
+        Duration::minutes((hours * 60.0).round() as i64)
+    }
+}
+
+impl Default for GameCalendar {
+    fn default() -> Self {
+        Self::new(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap())
+    }
+}
+
+fn serialize_date<S: Serializer>(date: &NaiveDate, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&date.format(DATE_FORMAT).to_string())
+}
+
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+    let date = String::deserialize(deserializer)?;
+    NaiveDate::parse_from_str(&date, DATE_FORMAT).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let calendar = GameCalendar::default();
+        let day = 24 * 60 * 60 * 1000;
+
+        assert_eq!(calendar.format(0), "Sat 2000-01-01 00:00");
+        assert_eq!(
+            calendar.format(366 * day + 90 * 60 * 1000),
+            "Mon 2001-01-01 01:30"
+        );
+        assert_eq!(calendar.year(366 * day), 2001);
+        assert_eq!(calendar.weekday(2 * day), Weekday::Mon);
+    }
+
+    #[test]
+    fn test_local_time() {
+        let calendar = GameCalendar::new(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+        let new_york = Aerodrome {
+            lon: -73.78,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            calendar.format_local(0, &Aerodrome::frankfurt()),
+            "Fri 2024-03-01 01:00 (UTC+1)"
+        );
+        assert_eq!(
+            calendar.format_local(0, &new_york),
+            "Thu 2024-02-29 19:00 (UTC-5)"
+        );
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let calendar = GameCalendar::default();
+        let date_time = calendar.date_time(123_456_000);
+
+        assert_eq!(calendar.timestamp(date_time), Some(123_456_000));
+        assert_eq!(
+            calendar.timestamp(
+                NaiveDate::from_ymd_opt(1999, 12, 31)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let calendar: GameCalendar = serde_yaml::from_str("start_date: 2024-03-01").unwrap();
+
+        assert_eq!(
+            calendar.start_date,
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()
+        );
+        assert!(serde_yaml::from_str::<GameCalendar>("start_date: March").is_err());
+    }
+}
@@ -6,9 +6,12 @@ use std::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::model::{Aerodrome, Environment, LandingRights};
+use crate::model::{
+    events::{BuyLandingRightsEvent, SimulationEvent},
+    Aerodrome, Environment, LandingRights,
+};
 
-use super::Command;
+use super::{Command, CommandError, Preview, SellLandingRightsCommand, TypeName};
 
 static LANDING_RIGHTS_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -31,30 +34,68 @@ impl BuyLandingRightsCommand {
 pub enum BuyLandingRightsError {
     #[error("Insufficient funds to buy landing rights: needed {needed}, but have {has}")]
     InsufficientFunds { needed: f64, has: f64 },
+    #[error("Aerodrome {0} is not allowed in this level")]
+    AerodromeNotAllowed(String),
+}
+
+impl TypeName for BuyLandingRightsCommand {
+    fn type_name(&self) -> &'static str {
+        "BuyLandingRightsCommand"
+    }
 }
 
 impl Command for BuyLandingRightsCommand {
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        if environment.company_finances.cash(environment.timestamp)
-            < environment.config.landing_rights_cost
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        let mut violations: Vec<CommandError> = vec![];
+
+        if !environment
+            .config
+            .restrictions
+            .allows_aerodrome(&self.aerodrome)
         {
-            return Err(Box::new(BuyLandingRightsError::InsufficientFunds {
-                needed: environment.config.landing_rights_cost,
-                has: environment.company_finances.cash(environment.timestamp),
-            }));
+            violations.push(
+                BuyLandingRightsError::AerodromeNotAllowed(self.aerodrome.name.clone()).into(),
+            );
+        }
+
+        let cost = environment.config.landing_rights_cost;
+        let cash = environment.company_finances.cash(environment.timestamp);
+        if cash < cost {
+            violations.push(
+                BuyLandingRightsError::InsufficientFunds {
+                    needed: cost,
+                    has: cash,
+                }
+                .into(),
+            );
+        }
+
+        match CommandError::from_violations(violations) {
+            Some(error) => Err(error),
+            None => Ok(Preview::new(environment, cost)),
         }
-        environment.company_finances.add_expense(
-            environment.timestamp,
-            environment.config.landing_rights_cost,
-        );
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
+
+        environment.add_expense(environment.timestamp, preview.cost);
         environment.landing_rights.push(LandingRights {
             aerodrome: self.aerodrome.clone(),
-            id: self.landing_rights_id.try_into()?,
+            id: self.landing_rights_id,
+            cost: preview.cost,
         });
-        Ok(None)
+        Ok(vec![BuyLandingRightsEvent {
+            landing_rights_id: self.landing_rights_id,
+            aerodrome: self.aerodrome.clone(),
+        }
+        .into()])
+    }
+
+    fn inverse(&self, _environment: &Environment) -> Option<Box<dyn Command>> {
+        Some(Box::new(SellLandingRightsCommand {
+            landing_rights_id: self.landing_rights_id,
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -84,12 +125,11 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<BuyLandingRightsError>().unwrap();
-                assert!(matches!(
-                    *err,
-                    BuyLandingRightsError::InsufficientFunds { .. }
-                ));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::BuyLandingRights(BuyLandingRightsError::InsufficientFunds { .. })
+                )));
             }
             _ => panic!("Expected an error"),
         }
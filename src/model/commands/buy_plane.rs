@@ -6,9 +6,12 @@ use std::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::model::{AirPlane, Environment, PlaneType};
+use crate::model::{
+    events::{BuyPlaneEvent, SimulationEvent},
+    AirPlane, Environment, PlaneType,
+};
 
-use super::Command;
+use super::{Command, CommandError, Preview, SellPlaneCommand, TypeName};
 
 static PLANE_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -36,50 +39,101 @@ pub enum BuyPlaneError {
     BaseNotFound { base_id: u64 },
     #[error("No space at base: {name}")]
     NoSpaceAtBase { name: String },
+    #[error("Plane type {0} is not allowed in this level")]
+    PlaneTypeNotAllowed(String),
+}
+
+impl TypeName for BuyPlaneCommand {
+    fn type_name(&self) -> &'static str {
+        "BuyPlaneCommand"
+    }
 }
 
 impl Command for BuyPlaneCommand {
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        if environment.company_finances.cash(environment.timestamp) < self.plane_type.cost as f64 {
-            return Err(Box::new(BuyPlaneError::InsufficientFunds {
-                needed: self.plane_type.cost as f64,
-                has: environment.company_finances.cash(environment.timestamp),
-            }));
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        let mut violations: Vec<CommandError> = vec![];
+
+        let cash = environment.company_finances.cash(environment.timestamp);
+        if cash < self.plane_type.cost as f64 {
+            violations.push(
+                BuyPlaneError::InsufficientFunds {
+                    needed: self.plane_type.cost as f64,
+                    has: cash,
+                }
+                .into(),
+            );
+        }
+
+        if !environment
+            .config
+            .restrictions
+            .allows_plane_type(&self.plane_type)
+        {
+            violations
+                .push(BuyPlaneError::PlaneTypeNotAllowed(self.plane_type.name.clone()).into());
         }
-        let airplane = AirPlane {
-            id: self.plane_id,
-            base_id: self.home_base_id,
-            plane_type: self.plane_type.clone(),
-        };
 
         match environment
             .bases
-            .iter_mut()
+            .iter()
             .find(|base| base.id == self.home_base_id)
         {
             Some(base) => {
                 if base.airplane_ids.len() >= 5 {
-                    return Err(Box::new(BuyPlaneError::NoSpaceAtBase {
-                        name: base.aerodrome.name.clone(),
-                    }));
+                    violations.push(
+                        BuyPlaneError::NoSpaceAtBase {
+                            name: base.aerodrome.name.clone(),
+                        }
+                        .into(),
+                    );
                 }
-                base.airplane_ids.push(airplane.id);
             }
-            None => {
-                return Err(Box::new(BuyPlaneError::BaseNotFound {
+            None => violations.push(
+                BuyPlaneError::BaseNotFound {
                     base_id: self.home_base_id,
-                }))
-            }
+                }
+                .into(),
+            ),
+        }
+
+        match CommandError::from_violations(violations) {
+            Some(error) => Err(error),
+            None => Ok(Preview::new(environment, self.plane_type.cost as f64)),
+        }
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
+
+        let airplane = AirPlane {
+            id: self.plane_id,
+            base_id: self.home_base_id,
+            plane_type: self.plane_type.clone(),
+            purchased_at: environment.timestamp,
+            cost: preview.cost,
+        };
+        if let Some(base) = environment
+            .bases
+            .iter_mut()
+            .find(|base| base.id == self.home_base_id)
+        {
+            base.airplane_ids.push(airplane.id);
         }
         environment.planes.push(airplane);
 
-        environment
-            .company_finances
-            .add_expense(environment.timestamp, self.plane_type.cost.into());
-        Ok(None)
+        environment.add_expense(environment.timestamp, preview.cost);
+        Ok(vec![BuyPlaneEvent {
+            plane_id: self.plane_id,
+            plane_type: self.plane_type.clone(),
+            home_base_id: self.home_base_id,
+        }
+        .into()])
+    }
+
+    fn inverse(&self, _environment: &Environment) -> Option<Box<dyn Command>> {
+        Some(Box::new(SellPlaneCommand {
+            plane_id: self.plane_id,
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -114,9 +168,11 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<BuyPlaneError>().unwrap();
-                assert!(matches!(*err, BuyPlaneError::InsufficientFunds { .. }));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::BuyPlane(BuyPlaneError::InsufficientFunds { .. })
+                )));
             }
             _ => panic!("Expected an error"),
         }
@@ -135,9 +191,11 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<BuyPlaneError>().unwrap();
-                assert!(matches!(*err, BuyPlaneError::BaseNotFound { .. }));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::BuyPlane(BuyPlaneError::BaseNotFound { .. })
+                )));
             }
             _ => panic!("Expected an error"),
         }
@@ -160,6 +218,7 @@ mod tests {
                 ..Default::default()
             },
             airplane_ids: vec![],
+            cost: 0.0,
         };
 
         for _ in 0..5 {
@@ -175,11 +234,65 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<BuyPlaneError>().unwrap();
-                assert!(matches!(*err, BuyPlaneError::NoSpaceAtBase { .. }));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::BuyPlane(BuyPlaneError::NoSpaceAtBase { .. })
+                )));
             }
             _ => panic!("Expected an error"),
         }
     }
+
+    #[test]
+    fn test_buy_plane_validate() {
+        let mut environment = Environment::default();
+        environment.bases.push(Base {
+            id: 1,
+            aerodrome: Aerodrome::default(),
+            airplane_ids: vec![],
+            cost: 0.0,
+        });
+        let cash = environment.company_finances.cash(environment.timestamp);
+
+        let plane_type = PlaneType::default();
+        let cmd = BuyPlaneCommand {
+            plane_id: BuyPlaneCommand::generate_id(),
+            plane_type: plane_type.clone(),
+            home_base_id: 1,
+        };
+
+        let preview = cmd.validate(&environment).unwrap();
+        assert_eq!(preview.cost, plane_type.cost as f64);
+        assert_eq!(preview.cash_after, cash - plane_type.cost as f64);
+        assert!(environment.planes.is_empty());
+        assert_eq!(
+            environment.company_finances.cash(environment.timestamp),
+            cash
+        );
+    }
+
+    #[test]
+    fn test_buy_plane_validate_reports_all_violations() {
+        let mut environment = Environment::default();
+        environment.company_finances.income.clear();
+
+        let cmd = BuyPlaneCommand {
+            plane_id: BuyPlaneCommand::generate_id(),
+            plane_type: PlaneType::default(),
+            home_base_id: 0,
+        };
+
+        let err = cmd.validate(&environment).unwrap_err();
+        let violations = err.violations();
+        assert_eq!(violations.len(), 2);
+        assert!(matches!(
+            violations[0],
+            CommandError::BuyPlane(BuyPlaneError::InsufficientFunds { .. })
+        ));
+        assert!(matches!(
+            violations[1],
+            CommandError::BuyPlane(BuyPlaneError::BaseNotFound { .. })
+        ));
+    }
 }
@@ -0,0 +1,121 @@
+use std::any::Any;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::{
+    events::{CancelFlightEvent, SimulationEvent},
+    Environment, FlightState,
+};
+
+use super::{Command, CommandError, Preview, ScheduleFlightCommand, TypeName};
+
+/// Cancels a flight that has not departed yet and takes back the income
+/// booked when it was scheduled, the inverse of scheduling it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CancelFlightCommand {
+    pub flight_id: u64,
+}
+
+#[derive(Debug, Error)]
+pub enum CancelFlightError {
+    #[error("Flight does not exist")]
+    NotExist,
+    #[error("Flight {0} has already departed")]
+    AlreadyDeparted(u64),
+}
+
+impl TypeName for CancelFlightCommand {
+    fn type_name(&self) -> &'static str {
+        "CancelFlightCommand"
+    }
+}
+
+impl Command for CancelFlightCommand {
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        let Some(flight) = environment
+            .flights
+            .iter()
+            .find(|flight| flight.flight_id == self.flight_id)
+        else {
+            return Err(CancelFlightError::NotExist.into());
+        };
+        if flight.state != FlightState::Scheduled {
+            return Err(CancelFlightError::AlreadyDeparted(self.flight_id).into());
+        }
+        Ok(Preview::new(environment, flight.calculate_profit()))
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
+
+        let Some(index) = environment
+            .flights
+            .iter()
+            .position(|flight| flight.flight_id == self.flight_id)
+        else {
+            return Err(CancelFlightError::NotExist.into());
+        };
+        let flight = environment.flights.remove(index);
+
+        environment.add_income(environment.timestamp, -preview.cost);
+        Ok(vec![CancelFlightEvent { flight }.into()])
+    }
+
+    fn inverse(&self, environment: &Environment) -> Option<Box<dyn Command>> {
+        let flight = environment
+            .flights
+            .iter()
+            .find(|flight| flight.flight_id == self.flight_id)?;
+        Some(Box::new(ScheduleFlightCommand {
+            flight_id: flight.flight_id,
+            airplane: flight.airplane.clone(),
+            origin_aerodrome: flight.origin_aerodrome.clone(),
+            stopovers: flight.stopovers.clone(),
+            departure_time: flight.departure_time,
+        }))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Aerodrome, AirPlane, Flight};
+
+    use super::*;
+
+    #[test]
+    fn test_cancel_flight_already_departed() {
+        let mut environment = Environment::default();
+        environment.flights.push(Flight {
+            flight_id: 1,
+            airplane: AirPlane::default(),
+            origin_aerodrome: Aerodrome::default(),
+            stopovers: vec![Aerodrome::default()],
+            departure_time: 1,
+            segment_departure_time: 1,
+            arrival_time: None,
+            state: FlightState::Finished,
+            demand: Default::default(),
+        });
+
+        let cmd = CancelFlightCommand { flight_id: 1 };
+
+        match cmd.execute(&mut environment) {
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::CancelFlight(CancelFlightError::AlreadyDeparted(1))
+                )));
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use thiserror::Error;
+
+use super::{
+    BuyLandingRightsError, BuyPlaneError, CancelFlightError, CreateBaseError, RemoveBaseError,
+    ScheduleFlightError, SellLandingRightsError, SellPlaneError,
+};
+
+/// Error of any command, or all rules a command violates at once.
+#[derive(Debug, Error)]
+pub enum CommandError {
+    #[error(transparent)]
+    BuyPlane(#[from] BuyPlaneError),
+    #[error(transparent)]
+    SellPlane(#[from] SellPlaneError),
+    #[error(transparent)]
+    CreateBase(#[from] CreateBaseError),
+    #[error(transparent)]
+    BuyLandingRights(#[from] BuyLandingRightsError),
+    #[error(transparent)]
+    SellLandingRights(#[from] SellLandingRightsError),
+    #[error(transparent)]
+    ScheduleFlight(#[from] ScheduleFlightError),
+    #[error(transparent)]
+    RemoveBase(#[from] RemoveBaseError),
+    #[error(transparent)]
+    CancelFlight(#[from] CancelFlightError),
+    #[error("{}", join_messages(.0))]
+    Violations(Vec<CommandError>),
+}
+
+fn join_messages(violations: &[CommandError]) -> String {
+    violations
+        .iter()
+        .map(|violation| violation.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+impl CommandError {
+    /// Combines violated rules into a single error, or `None` if no rule is violated.
+    pub fn from_violations(mut violations: Vec<CommandError>) -> Option<Self> {
+        match violations.len() {
+            0 => None,
+            1 => violations.pop(),
+            _ => Some(CommandError::Violations(violations)),
+        }
+    }
+
+    /// All violated rules contained in this error.
+    pub fn violations(&self) -> Vec<&CommandError> {
+        match self {
+            CommandError::Violations(violations) => violations
+                .iter()
+                .flat_map(|violation| violation.violations())
+                .collect(),
+            _ => vec![self],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_violations() {
+        assert!(CommandError::from_violations(vec![]).is_none());
+
+        let error = CommandError::from_violations(vec![SellPlaneError::NotExist.into()]).unwrap();
+        assert!(matches!(
+            error,
+            CommandError::SellPlane(SellPlaneError::NotExist)
+        ));
+
+        let error = CommandError::from_violations(vec![
+            BuyPlaneError::BaseNotFound { base_id: 1 }.into(),
+            BuyPlaneError::InsufficientFunds {
+                needed: 2.0,
+                has: 1.0,
+            }
+            .into(),
+        ])
+        .unwrap();
+        assert_eq!(error.violations().len(), 2);
+        assert_eq!(
+            error.to_string(),
+            "Base not found; Insufficient funds: needed 2, but have 1"
+        );
+    }
+}
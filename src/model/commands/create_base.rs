@@ -6,9 +6,12 @@ use std::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::model::{Aerodrome, Base, Environment};
+use crate::model::{
+    events::{CreateBaseEvent, SimulationEvent},
+    Aerodrome, Base, Environment,
+};
 
-use super::Command;
+use super::{Command, CommandError, Preview, RemoveBaseCommand, TypeName};
 
 static BASE_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -27,10 +30,15 @@ impl CreateBaseCommand {
     }
 
     pub fn base_cost(&self, environment: &Environment) -> f64 {
-        match self.aerodrome.passengers {
-            Some(passengers) => environment.config.base_cost + passengers as f64 / 20.0,
-            None => environment.config.base_cost,
-        }
+        base_cost(environment, &self.aerodrome)
+    }
+}
+
+/// Price of a base at `aerodrome`, growing with its passengers.
+fn base_cost(environment: &Environment, aerodrome: &Aerodrome) -> f64 {
+    match aerodrome.passengers {
+        Some(passengers) => environment.config.base_cost + passengers as f64 / 20.0,
+        None => environment.config.base_cost,
     }
 }
 
@@ -40,38 +48,77 @@ pub enum CreateBaseError {
     InsufficientFunds { needed: f64, has: f64 },
     #[error("Base already exists for the given aerodrome: {0}")]
     BaseAlreadyExists(String),
+    #[error("Aerodrome {0} is not allowed in this level")]
+    AerodromeNotAllowed(String),
+}
+
+impl TypeName for CreateBaseCommand {
+    fn type_name(&self) -> &'static str {
+        "CreateBaseCommand"
+    }
 }
 
 impl Command for CreateBaseCommand {
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        let mut violations: Vec<CommandError> = vec![];
+
         if environment
             .bases
             .iter()
             .any(|base| base.aerodrome.code == self.aerodrome.code)
         {
-            return Err(Box::new(CreateBaseError::BaseAlreadyExists(
-                self.aerodrome.name.clone(),
-            )));
+            violations.push(CreateBaseError::BaseAlreadyExists(self.aerodrome.name.clone()).into());
         }
-        if environment.company_finances.cash(environment.timestamp) < self.base_cost(environment) {
-            return Err(Box::new(CreateBaseError::InsufficientFunds {
-                needed: self.base_cost(environment),
-                has: environment.company_finances.cash(environment.timestamp),
-            }));
+
+        if !environment
+            .config
+            .restrictions
+            .allows_aerodrome(&self.aerodrome)
+        {
+            violations
+                .push(CreateBaseError::AerodromeNotAllowed(self.aerodrome.name.clone()).into());
+        }
+
+        let cost = self.base_cost(environment);
+        let cash = environment.company_finances.cash(environment.timestamp);
+        if cash < cost {
+            violations.push(
+                CreateBaseError::InsufficientFunds {
+                    needed: cost,
+                    has: cash,
+                }
+                .into(),
+            );
         }
 
-        environment
-            .company_finances
-            .add_expense(environment.timestamp, self.base_cost(environment));
+        match CommandError::from_violations(violations) {
+            Some(error) => Err(error),
+            None => Ok(Preview::new(environment, cost)),
+        }
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let cost = self.validate(environment)?.cost;
+
+        environment.add_expense(environment.timestamp, cost);
         environment.bases.push(Base {
             id: self.base_id,
             aerodrome: self.aerodrome.clone(),
             airplane_ids: vec![],
+            cost,
         });
-        Ok(None)
+        Ok(vec![CreateBaseEvent {
+            base_id: self.base_id,
+            aerodrome: self.aerodrome.clone(),
+            cost,
+        }
+        .into()])
+    }
+
+    fn inverse(&self, _environment: &Environment) -> Option<Box<dyn Command>> {
+        Some(Box::new(RemoveBaseCommand {
+            base_id: self.base_id,
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -101,9 +148,11 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<CreateBaseError>().unwrap();
-                assert!(matches!(*err, CreateBaseError::InsufficientFunds { .. }));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::CreateBase(CreateBaseError::InsufficientFunds { .. })
+                )));
             }
             _ => panic!("Expected an error"),
         }
@@ -120,6 +169,7 @@ mod tests {
             id: 1,
             aerodrome: aerodrome.clone(),
             airplane_ids: vec![],
+            cost: 0.0,
         });
 
         let cmd = CreateBaseCommand {
@@ -128,9 +178,32 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<CreateBaseError>().unwrap();
-                assert!(matches!(*err, CreateBaseError::BaseAlreadyExists(..)));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::CreateBase(CreateBaseError::BaseAlreadyExists(..))
+                )));
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_create_base_aerodrome_not_allowed() {
+        let mut environment = Environment::default();
+        environment.config.restrictions.aerodromes = vec!["CDG".to_string()];
+
+        let cmd = CreateBaseCommand {
+            base_id: CreateBaseCommand::generate_id(),
+            aerodrome: Aerodrome::frankfurt(),
+        };
+
+        match cmd.execute(&mut environment) {
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::CreateBase(CreateBaseError::AerodromeNotAllowed(..))
+                )));
             }
             _ => panic!("Expected an error"),
         }
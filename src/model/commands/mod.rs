@@ -1,6 +1,9 @@
 mod buy_landing_rights;
 mod buy_plane;
+mod cancel_flight;
+mod command_error;
 mod create_base;
+mod remove_base;
 mod schedule_flight;
 mod sell_landing_rights;
 mod sell_plane;
@@ -10,20 +13,45 @@ use std::any::Any;
 
 pub use buy_landing_rights::{BuyLandingRightsCommand, BuyLandingRightsError};
 pub use buy_plane::{BuyPlaneCommand, BuyPlaneError};
+pub use cancel_flight::{CancelFlightCommand, CancelFlightError};
+pub use command_error::CommandError;
 pub use create_base::{CreateBaseCommand, CreateBaseError};
+pub use remove_base::{RemoveBaseCommand, RemoveBaseError};
 pub use schedule_flight::{ScheduleFlightCommand, ScheduleFlightError};
 pub use sell_landing_rights::{SellLandingRightsCommand, SellLandingRightsError};
 pub use sell_plane::{SellPlaneCommand, SellPlaneError};
 pub use timestamped_command::TimestampedCommand;
 
-use super::Environment;
+use super::{events::SimulationEvent, Environment};
 
-pub trait Command: Send + Sync + std::fmt::Debug {
+/// Effect a command would have on the company's cash.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    /// Cash spent by the command, negative if the command earns money.
+    pub cost: f64,
+    pub cash_after: f64,
+}
+
+impl Preview {
+    pub fn new(environment: &Environment, cost: f64) -> Self {
+        Self {
+            cost,
+            cash_after: environment.company_finances.cash(environment.timestamp) - cost,
+        }
+    }
+}
+
+pub trait Command: TypeName + Send + Sync + std::fmt::Debug {
     fn as_any(&self) -> &dyn Any;
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>>;
+    /// Checks the command against the environment without changing it and
+    /// reports either its effect or all violated rules.
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError>;
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError>;
+    /// Returns the command reverting this command, based on the environment
+    /// before this command is executed, or `None` if it cannot be undone.
+    fn inverse(&self, _environment: &Environment) -> Option<Box<dyn Command>> {
+        None
+    }
     fn clone_box(&self) -> Box<dyn Command>;
 }
 
@@ -33,6 +61,7 @@ impl Clone for Box<dyn Command> {
     }
 }
 
+/// Name of a command type, as used in replays.
 pub trait TypeName {
     fn type_name(&self) -> &'static str;
 }
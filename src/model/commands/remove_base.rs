@@ -0,0 +1,145 @@
+use std::any::Any;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::{
+    events::{RemoveBaseEvent, SimulationEvent},
+    Environment,
+};
+
+use super::{Command, CommandError, CreateBaseCommand, Preview, TypeName};
+
+/// Removes an empty base and refunds the price paid for it, the inverse of
+/// creating it.
+///
+/// The refund is taken from the base, so a hand-edited replay cannot choose
+/// it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoveBaseCommand {
+    pub base_id: u64,
+}
+
+#[derive(Debug, Error)]
+pub enum RemoveBaseError {
+    #[error("Base does not exist")]
+    NotExist,
+    #[error("Base at {0} still has airplanes")]
+    HasAirplanes(String),
+}
+
+impl TypeName for RemoveBaseCommand {
+    fn type_name(&self) -> &'static str {
+        "RemoveBaseCommand"
+    }
+}
+
+impl Command for RemoveBaseCommand {
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        let Some(base) = environment
+            .bases
+            .iter()
+            .find(|base| base.id == self.base_id)
+        else {
+            return Err(RemoveBaseError::NotExist.into());
+        };
+        if !base.airplane_ids.is_empty() {
+            return Err(RemoveBaseError::HasAirplanes(base.aerodrome.name.clone()).into());
+        }
+        Ok(Preview::new(environment, -base.cost))
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
+
+        let Some(index) = environment
+            .bases
+            .iter()
+            .position(|base| base.id == self.base_id)
+        else {
+            return Err(RemoveBaseError::NotExist.into());
+        };
+        let base = environment.bases.remove(index);
+
+        let refund = -preview.cost;
+        environment.add_income(environment.timestamp, refund);
+        Ok(vec![RemoveBaseEvent {
+            base_id: base.id,
+            aerodrome: base.aerodrome,
+            refund,
+        }
+        .into()])
+    }
+
+    fn inverse(&self, environment: &Environment) -> Option<Box<dyn Command>> {
+        let base = environment
+            .bases
+            .iter()
+            .find(|base| base.id == self.base_id)?;
+        Some(Box::new(CreateBaseCommand {
+            base_id: base.id,
+            aerodrome: base.aerodrome.clone(),
+        }))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn Command> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Aerodrome, Base};
+
+    use super::*;
+
+    #[test]
+    fn test_remove_base_has_airplanes() {
+        let mut environment = Environment::default();
+        environment.bases.push(Base {
+            id: 1,
+            aerodrome: Aerodrome::default(),
+            airplane_ids: vec![1],
+            cost: 0.0,
+        });
+
+        let cmd = RemoveBaseCommand { base_id: 1 };
+
+        match cmd.execute(&mut environment) {
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::RemoveBase(RemoveBaseError::HasAirplanes(..))
+                )));
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_remove_base_refunds_base_cost() {
+        let mut environment = Environment::default();
+        environment.bases.push(Base {
+            id: 1,
+            aerodrome: Aerodrome::default(),
+            airplane_ids: vec![],
+            cost: 250_000.0,
+        });
+        let refund = 250_000.0;
+        let cash = environment.company_finances.cash(environment.timestamp);
+
+        let command: RemoveBaseCommand =
+            serde_yaml::from_str("base_id: 1\nrefund: 1000000000.0\n").unwrap();
+        command.execute(&mut environment).unwrap();
+
+        assert!(environment.bases.is_empty());
+        assert_eq!(
+            environment.company_finances.cash(environment.timestamp),
+            cash + refund
+        );
+    }
+}
@@ -6,9 +6,12 @@ use std::{
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::model::{Aerodrome, AirPlane, Environment, Flight, FlightState, Timestamp};
+use crate::model::{
+    events::{ScheduleFlightEvent, SimulationEvent},
+    Aerodrome, AirPlane, Environment, Flight, FlightState, Timestamp,
+};
 
-use super::Command;
+use super::{CancelFlightCommand, Command, CommandError, Preview, TypeName};
 
 static FLIGHT_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -28,6 +31,25 @@ impl ScheduleFlightCommand {
             .try_into()
             .unwrap()
     }
+
+    fn flight(&self, environment: &Environment) -> Flight {
+        Flight {
+            flight_id: self.flight_id,
+            airplane: self.airplane.clone(),
+            origin_aerodrome: self.origin_aerodrome.clone(),
+            stopovers: self.stopovers.clone(),
+            departure_time: self.departure_time,
+            segment_departure_time: self.departure_time,
+            arrival_time: None,
+            state: FlightState::Scheduled,
+            demand: environment.config.demand.factors(
+                &environment.config.calendar,
+                &self.origin_aerodrome,
+                &self.stopovers,
+                self.departure_time,
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -38,38 +60,44 @@ pub enum ScheduleFlightError {
     DistanceBeyondRange,
     #[error("The airplane is not located at the origin aerodrome")]
     AirplaneNotLocatedAtOrigin,
+    #[error("The runway at {aerodrome} is too short for the airplane")]
+    RunwayTooShort { aerodrome: String },
+}
+
+impl TypeName for ScheduleFlightCommand {
+    fn type_name(&self) -> &'static str {
+        "ScheduleFlightCommand"
+    }
 }
 
 impl Command for ScheduleFlightCommand {
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        let mut violations: Vec<CommandError> = vec![];
         let airplane_id = self.airplane.id;
 
         let is_airplane_in_use = environment.flights.iter().any(|flight| {
             flight.airplane.id == airplane_id && flight.state != FlightState::Finished
         });
-
-        // If the airplane is in use, return an error
         if is_airplane_in_use {
-            return Err(Box::new(ScheduleFlightError::AirplaneInUse));
+            violations.push(ScheduleFlightError::AirplaneInUse.into());
         }
 
-        let flight = Flight {
-            flight_id: self.flight_id,
-            airplane: self.airplane.clone(),
-            origin_aerodrome: self.origin_aerodrome.clone(),
-            stopovers: self.stopovers.clone(),
-            departure_time: self.departure_time,
-            segment_departure_time: self.departure_time,
-            arrival_time: None,
-            state: FlightState::Scheduled,
-        };
-
         // Check if the distance is within the airplane's range
+        let flight = self.flight(environment);
         if !flight.is_plane_range_sufficient() {
-            return Err(Box::new(ScheduleFlightError::DistanceBeyondRange));
+            violations.push(ScheduleFlightError::DistanceBeyondRange.into());
+        }
+
+        let plane_type = &self.airplane.plane_type;
+        for aerodrome in std::iter::once(&self.origin_aerodrome).chain(&self.stopovers) {
+            if !plane_type.can_use(aerodrome) {
+                violations.push(
+                    ScheduleFlightError::RunwayTooShort {
+                        aerodrome: aerodrome.code.clone(),
+                    }
+                    .into(),
+                );
+            }
         }
 
         let is_airplane_located_at_origin = environment.bases.iter().any(|base| {
@@ -77,17 +105,29 @@ impl Command for ScheduleFlightCommand {
                 && base.airplane_ids.contains(&airplane_id)
         });
         if !is_airplane_located_at_origin {
-            return Err(Box::new(ScheduleFlightError::AirplaneNotLocatedAtOrigin));
+            violations.push(ScheduleFlightError::AirplaneNotLocatedAtOrigin.into());
         }
 
-        let profit = flight.calculate_profit();
+        match CommandError::from_violations(violations) {
+            Some(error) => Err(error),
+            None => Ok(Preview::new(environment, -flight.calculate_profit())),
+        }
+    }
 
-        environment.flights.push(flight);
-        environment
-            .company_finances
-            .add_income(environment.timestamp, profit);
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
 
-        Ok(None)
+        let flight = self.flight(environment);
+        environment.flights.push(flight.clone());
+        environment.add_income(environment.timestamp, -preview.cost);
+
+        Ok(vec![ScheduleFlightEvent { flight }.into()])
+    }
+
+    fn inverse(&self, _environment: &Environment) -> Option<Box<dyn Command>> {
+        Some(Box::new(CancelFlightCommand {
+            flight_id: self.flight_id,
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -101,7 +141,7 @@ impl Command for ScheduleFlightCommand {
 
 #[cfg(test)]
 mod tests {
-    use crate::model::{AirPlane, Flight, FlightState};
+    use crate::model::{AirPlane, DemandProfile, Flight, FlightState};
 
     use super::*;
 
@@ -122,6 +162,7 @@ mod tests {
             segment_departure_time: 1,
             arrival_time: None,
             state: FlightState::Scheduled,
+            demand: Default::default(),
         });
 
         let cmd = ScheduleFlightCommand {
@@ -133,14 +174,38 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<ScheduleFlightError>().unwrap();
-                assert!(matches!(*err, ScheduleFlightError::AirplaneInUse));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::ScheduleFlight(ScheduleFlightError::AirplaneInUse)
+                )));
             }
             _ => panic!("Expected an error"),
         }
     }
 
+    #[test]
+    fn test_schedule_flight_demand() {
+        let mut environment = Environment::default();
+        let cmd = ScheduleFlightCommand {
+            flight_id: ScheduleFlightCommand::generate_id(),
+            airplane: AirPlane::default(),
+            origin_aerodrome: Aerodrome::frankfurt(),
+            stopovers: vec![Aerodrome::paris()],
+            // 03:00 local time in Frankfurt
+            departure_time: 2 * 60 * 60 * 1000,
+        };
+
+        let night = cmd.flight(&environment);
+        assert_eq!(night.demand.time_of_day, 0.4);
+        assert!(night.calculate_booked_seats() < night.airplane.plane_type.seats);
+
+        environment.config.demand = DemandProfile::flat();
+        let flat = cmd.flight(&environment);
+        assert_eq!(flat.demand.total(), 1.0);
+        assert!(flat.calculate_profit() > night.calculate_profit());
+    }
+
     #[test]
     fn test_schedule_flight_distance_beyond_range() {
         let mut environment = Environment::default();
@@ -161,11 +226,54 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<ScheduleFlightError>().unwrap();
-                assert!(matches!(*err, ScheduleFlightError::DistanceBeyondRange));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::ScheduleFlight(ScheduleFlightError::DistanceBeyondRange)
+                )));
             }
             _ => panic!("Expected an error"),
         }
     }
+
+    #[test]
+    fn test_schedule_flight_runway_too_short() {
+        let mut environment = Environment::default();
+
+        let mut airplane = AirPlane::default();
+        airplane.plane_type.min_runway_length = 2500.0;
+        let origin_aerodrome = Aerodrome {
+            runway_length: Some(4000.0),
+            ..Aerodrome::frankfurt()
+        };
+        let destination_aerodrome = Aerodrome {
+            runway_length: Some(1500.0),
+            ..Aerodrome::paris()
+        };
+
+        let cmd = ScheduleFlightCommand {
+            flight_id: ScheduleFlightCommand::generate_id(),
+            airplane,
+            origin_aerodrome,
+            stopovers: vec![destination_aerodrome],
+            departure_time: 1,
+        };
+
+        let err = cmd.execute(&mut environment).unwrap_err();
+        let runway_violations: Vec<_> = err
+            .violations()
+            .into_iter()
+            .filter(|violation| {
+                matches!(
+                    violation,
+                    CommandError::ScheduleFlight(ScheduleFlightError::RunwayTooShort { .. })
+                )
+            })
+            .collect();
+        assert_eq!(runway_violations.len(), 1);
+        assert_eq!(
+            runway_violations[0].to_string(),
+            "The runway at CDG/LFPG is too short for the airplane"
+        );
+    }
 }
@@ -3,9 +3,12 @@ use std::any::Any;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::model::Environment;
+use crate::model::{
+    events::{SellLandingRightsEvent, SimulationEvent},
+    Environment,
+};
 
-use super::Command;
+use super::{BuyLandingRightsCommand, Command, CommandError, Preview, TypeName};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SellLandingRightsCommand {
@@ -18,28 +21,57 @@ pub enum SellLandingRightsError {
     NotExist,
 }
 
+impl TypeName for SellLandingRightsCommand {
+    fn type_name(&self) -> &'static str {
+        "SellLandingRightsCommand"
+    }
+}
+
 impl Command for SellLandingRightsCommand {
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        let landing_rights = environment
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        match environment
             .landing_rights
             .iter()
-            .find(|lr| lr.id == self.landing_rights_id);
-        if landing_rights.is_none() {
-            return Err(Box::new(SellLandingRightsError::NotExist));
+            .find(|lr| lr.id == self.landing_rights_id)
+        {
+            Some(landing_rights) => Ok(Preview::new(environment, -landing_rights.cost)),
+            None => Err(SellLandingRightsError::NotExist.into()),
         }
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
+
+        let Some(landing_rights) = environment
+            .landing_rights
+            .iter()
+            .find(|lr| lr.id == self.landing_rights_id)
+        else {
+            return Err(SellLandingRightsError::NotExist.into());
+        };
+        let event = SellLandingRightsEvent {
+            landing_rights_id: landing_rights.id,
+            aerodrome: landing_rights.aerodrome.clone(),
+            refund: -preview.cost,
+        };
 
         environment
             .landing_rights
             .retain(|lr| lr.id != self.landing_rights_id);
 
-        environment.company_finances.add_income(
-            environment.timestamp,
-            environment.config.landing_rights_cost,
-        );
-        Ok(None)
+        environment.add_income(environment.timestamp, -preview.cost);
+        Ok(vec![event.into()])
+    }
+
+    fn inverse(&self, environment: &Environment) -> Option<Box<dyn Command>> {
+        let landing_rights = environment
+            .landing_rights
+            .iter()
+            .find(|lr| lr.id == self.landing_rights_id)?;
+        Some(Box::new(BuyLandingRightsCommand {
+            landing_rights_id: landing_rights.id,
+            aerodrome: landing_rights.aerodrome.clone(),
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -64,9 +96,11 @@ mod tests {
         };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<SellLandingRightsError>().unwrap();
-                assert!(matches!(*err, SellLandingRightsError::NotExist));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::SellLandingRights(SellLandingRightsError::NotExist)
+                )));
             }
             _ => panic!("Expected an error"),
         }
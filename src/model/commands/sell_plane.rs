@@ -1,5 +1,8 @@
-use super::Command;
-use crate::model::Environment;
+use super::{BuyPlaneCommand, Command, CommandError, Preview, TypeName};
+use crate::model::{
+    events::{SellPlaneEvent, SimulationEvent},
+    Environment,
+};
 use serde::{Deserialize, Serialize};
 use std::any::Any;
 use thiserror::Error;
@@ -15,26 +18,65 @@ pub enum SellPlaneError {
     NotExist,
 }
 
+impl TypeName for SellPlaneCommand {
+    fn type_name(&self) -> &'static str {
+        "SellPlaneCommand"
+    }
+}
+
 impl Command for SellPlaneCommand {
-    fn execute(
-        &self,
-        environment: &mut Environment,
-    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        if let Some(airplane) = environment.planes.iter().find(|lr| lr.id == self.plane_id) {
-            environment
-                .company_finances
-                .add_income(environment.timestamp, airplane.plane_type.cost.into());
-            let base = environment
-                .bases
-                .iter_mut()
-                .find(|b| b.id == airplane.base_id)
-                .unwrap();
-            base.airplane_ids.retain(|lr| *lr != self.plane_id);
-            environment.planes.retain(|lr| lr.id != self.plane_id);
-        } else {
-            return Err(Box::new(SellPlaneError::NotExist));
+    fn validate(&self, environment: &Environment) -> Result<Preview, CommandError> {
+        match environment
+            .planes
+            .iter()
+            .find(|airplane| airplane.id == self.plane_id)
+        {
+            Some(airplane) => Ok(Preview::new(environment, -airplane.cost)),
+            None => Err(SellPlaneError::NotExist.into()),
+        }
+    }
+
+    fn execute(&self, environment: &mut Environment) -> Result<Vec<SimulationEvent>, CommandError> {
+        let preview = self.validate(environment)?;
+
+        let Some(airplane) = environment
+            .planes
+            .iter()
+            .find(|airplane| airplane.id == self.plane_id)
+        else {
+            return Err(SellPlaneError::NotExist.into());
+        };
+        let event = SellPlaneEvent {
+            plane_id: airplane.id,
+            plane_type: airplane.plane_type.clone(),
+            home_base_id: airplane.base_id,
+            refund: -preview.cost,
+        };
+
+        environment.add_income(environment.timestamp, -preview.cost);
+        if let Some(base) = environment
+            .bases
+            .iter_mut()
+            .find(|base| base.id == event.home_base_id)
+        {
+            base.airplane_ids.retain(|id| *id != self.plane_id);
         }
-        Ok(None)
+        environment
+            .planes
+            .retain(|airplane| airplane.id != self.plane_id);
+        Ok(vec![event.into()])
+    }
+
+    fn inverse(&self, environment: &Environment) -> Option<Box<dyn Command>> {
+        let airplane = environment
+            .planes
+            .iter()
+            .find(|airplane| airplane.id == self.plane_id)?;
+        Some(Box::new(BuyPlaneCommand {
+            plane_id: airplane.id,
+            plane_type: airplane.plane_type.clone(),
+            home_base_id: airplane.base_id,
+        }))
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -57,9 +99,11 @@ mod tests {
         let cmd = SellPlaneCommand { plane_id: 42 };
 
         match cmd.execute(&mut environment) {
-            Err(e) => {
-                let err = e.downcast::<SellPlaneError>().unwrap();
-                assert!(matches!(*err, SellPlaneError::NotExist));
+            Err(err) => {
+                assert!(err.violations().iter().any(|violation| matches!(
+                    violation,
+                    CommandError::SellPlane(SellPlaneError::NotExist)
+                )));
             }
             _ => panic!("Expected an error"),
         }
@@ -2,7 +2,7 @@ use crate::model::Timestamp;
 
 use super::Command;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct TimestampedCommand {
     pub timestamp: Timestamp,
     pub command: Box<dyn Command>,
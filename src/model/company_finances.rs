@@ -5,26 +5,142 @@ use std::slice::Iter;
 
 use super::Timestamp;
 
+/// Entries sorted by timestamp with the running total up to each of them, so
+/// the total up to a timestamp is found by binary search.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Ledger {
+    entries: Vec<(Timestamp, f64)>,
+    /// `totals[i]` is the sum of `entries[..=i]`.
+    totals: Vec<f64>,
+    /// Entries up to and including this timestamp are already compacted into
+    /// buckets.
+    compacted_until: Timestamp,
+}
+
+impl Ledger {
+    pub fn push(&mut self, timestamp: Timestamp, amount: f64) {
+        match self.entries.last() {
+            Some(last) if last.0 > timestamp => {
+                let index = self.entries.partition_point(|entry| entry.0 <= timestamp);
+                self.entries.insert(index, (timestamp, amount));
+                self.totals.truncate(index);
+                self.update_totals();
+            }
+            _ => {
+                self.entries.push((timestamp, amount));
+                self.totals.push(self.total() + amount);
+            }
+        }
+    }
+
+    /// Recalculates the running totals of the entries that have none.
+    fn update_totals(&mut self) {
+        let mut total = self.total();
+        for (_, amount) in &self.entries[self.totals.len()..] {
+            total += amount;
+            self.totals.push(total);
+        }
+    }
+
+    /// Sum of all entries up to and including `timestamp`.
+    pub fn total_at(&self, timestamp: Timestamp) -> f64 {
+        match self.entries.partition_point(|entry| entry.0 <= timestamp) {
+            0 => 0.0,
+            index => self.totals[index - 1],
+        }
+    }
+
+    /// Sum of the entries after `from` up to and including `to`.
+    pub fn total_between(&self, from: Timestamp, to: Timestamp) -> f64 {
+        self.total_at(to) - self.total_at(from)
+    }
+
+    pub fn total(&self) -> f64 {
+        self.totals.last().copied().unwrap_or(0.0)
+    }
+
+    pub fn entries(&self) -> &[(Timestamp, f64)] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.totals.clear();
+        self.compacted_until = 0;
+    }
+
+    /// Merges the entries up to `before`, rounded down to a multiple of
+    /// `width`, into one entry per bucket of `width`. A bucket ends at a
+    /// multiple of `width` and is booked there, so totals stay exact at the
+    /// bucket boundaries and lag behind inside a bucket. Entries at 0, like
+    /// the start capital, are kept as they are.
+    pub fn compact(&mut self, before: Timestamp, width: Timestamp) {
+        let width = width.max(1);
+        let before = before - before % width;
+        if before <= self.compacted_until {
+            return;
+        }
+        let start = self
+            .entries
+            .partition_point(|entry| entry.0 <= self.compacted_until);
+        let end = self.entries.partition_point(|entry| entry.0 <= before);
+
+        let mut compacted: Vec<(Timestamp, f64)> = Vec::with_capacity(self.entries.len());
+        compacted.extend_from_slice(&self.entries[..start]);
+        for &(timestamp, amount) in &self.entries[start..end] {
+            let boundary = timestamp.div_ceil(width) * width;
+            match compacted.last_mut() {
+                Some(last) if compacted.len() > start && last.0 == boundary => last.1 += amount,
+                _ => compacted.push((boundary, amount)),
+            }
+        }
+        compacted.extend_from_slice(&self.entries[end..]);
+
+        self.entries = compacted;
+        self.totals.truncate(start);
+        self.update_totals();
+        self.compacted_until = before;
+    }
+}
+
+impl FromIterator<(Timestamp, f64)> for Ledger {
+    fn from_iter<T: IntoIterator<Item = (Timestamp, f64)>>(iter: T) -> Self {
+        let mut ledger = Self::default();
+        for (timestamp, amount) in iter {
+            ledger.push(timestamp, amount);
+        }
+        ledger
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct CompanyFinances {
-    pub income: Vec<(Timestamp, f64)>,
-    pub expenses: Vec<(Timestamp, f64)>,
+    pub income: Ledger,
+    pub expenses: Ledger,
 }
 
 impl CompanyFinances {
     pub fn new(cash: f64) -> Self {
         Self {
-            income: vec![(0, cash)],
-            expenses: vec![],
+            income: Ledger::from_iter([(0, cash)]),
+            expenses: Ledger::default(),
         }
     }
 
     pub fn add_income(&mut self, timestamp: Timestamp, income: f64) {
-        self.income.push((timestamp, income));
+        self.income.push(timestamp, income);
     }
 
     pub fn add_expense(&mut self, timestamp: Timestamp, expense: f64) {
-        self.expenses.push((timestamp, expense));
+        self.expenses.push(timestamp, expense);
     }
 
     pub fn cash(&self, timestamp: Timestamp) -> f64 {
@@ -32,37 +148,37 @@ impl CompanyFinances {
     }
 
     pub fn total_income(&self, timestamp: Timestamp) -> f64 {
-        self.iter_income()
-            .filter(|(income_timestamp, _)| *income_timestamp <= timestamp)
-            .map(|(_, income)| income)
-            .sum::<f64>()
+        self.income.total_at(timestamp)
     }
 
     pub fn total_expenses(&self, timestamp: Timestamp) -> f64 {
-        self.iter_expenses()
-            .filter(|(expense_timestamp, _)| *expense_timestamp <= timestamp)
-            .map(|(_, expense)| expense)
-            .sum::<f64>()
+        self.expenses.total_at(timestamp)
+    }
+
+    /// Merges income and expenses before `before` into buckets of `width`.
+    pub fn compact(&mut self, before: Timestamp, width: Timestamp) {
+        self.income.compact(before, width);
+        self.expenses.compact(before, width);
     }
 
     #[cfg(not(feature = "rayon"))]
     pub fn iter_income(&self) -> Iter<(Timestamp, f64)> {
-        self.income.iter()
+        self.income.entries().iter()
     }
 
     #[cfg(feature = "rayon")]
     pub fn iter_income(&self) -> rayon::slice::Iter<(Timestamp, f64)> {
-        self.income.par_iter()
+        self.income.entries().par_iter()
     }
 
     #[cfg(not(feature = "rayon"))]
     pub fn iter_expenses(&self) -> Iter<(Timestamp, f64)> {
-        self.expenses.iter()
+        self.expenses.entries().iter()
     }
 
     #[cfg(feature = "rayon")]
     pub fn iter_expenses(&self) -> rayon::slice::Iter<(Timestamp, f64)> {
-        self.expenses.par_iter()
+        self.expenses.entries().par_iter()
     }
 }
 
@@ -99,4 +215,122 @@ mod tests {
         finances.add_expense(2, 30.0);
         assert_eq!(finances.cash(3), 120.0); // 100 initial + 50 income - 30 expenses
     }
+
+    /// Cash as calculated before the ledger, by scanning all entries.
+    fn scanned_cash(entries: &[(bool, Timestamp, f64)], timestamp: Timestamp) -> f64 {
+        let sum = |is_income: bool| {
+            entries
+                .iter()
+                .filter(|entry| entry.0 == is_income && entry.1 <= timestamp)
+                .map(|entry| entry.2)
+                .sum::<f64>()
+        };
+        sum(true) - sum(false)
+    }
+
+    #[test]
+    fn test_cash_matches_scan() {
+        // xorshift to generate reproducible pseudo random entries
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        // without compaction every timestamp is exact, with compaction only the
+        // bucket boundaries of the compacted history
+        const WIDTH: Timestamp = 10;
+        for compact in [false, true] {
+            for _ in 0..20 {
+                let mut finances = CompanyFinances::new(1_000.0);
+                let mut entries = vec![(true, 0, 1_000.0)];
+                let mut timestamp: Timestamp = 0;
+                let mut compacted_until: Timestamp = 0;
+                for _ in 0..200 {
+                    // mostly ascending timestamps, sometimes booked in the past
+                    timestamp = match next() % 10 {
+                        0 => timestamp.saturating_sub((next() % 50) as Timestamp),
+                        _ => timestamp + (next() % 20) as Timestamp,
+                    };
+                    let amount = (next() % 10_000) as f64 / 100.0;
+                    let is_income = next() % 2 == 0;
+                    if is_income {
+                        finances.add_income(timestamp, amount);
+                    } else {
+                        finances.add_expense(timestamp, amount);
+                    }
+                    entries.push((is_income, timestamp, amount));
+
+                    if compact && next() % 20 == 0 {
+                        let before = timestamp.saturating_sub(100);
+                        finances.compact(before, WIDTH);
+                        compacted_until = compacted_until.max(before - before % WIDTH);
+                    }
+                }
+
+                for query in 0..=timestamp + 5 {
+                    if query < compacted_until && query % WIDTH != 0 {
+                        continue;
+                    }
+                    let expected = scanned_cash(&entries, query);
+                    assert!(
+                        (finances.cash(query) - expected).abs() < 1e-6,
+                        "cash at {} was {} instead of {}",
+                        query,
+                        finances.cash(query),
+                        expected
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ledger_out_of_order() {
+        let ledger = Ledger::from_iter([(10, 1.0), (10, 2.0), (20, 4.0), (15, 8.0), (5, 16.0)]);
+        assert_eq!(ledger.len(), 5);
+        assert_eq!(ledger.total_at(4), 0.0);
+        assert_eq!(ledger.total_at(5), 16.0);
+        assert_eq!(ledger.total_at(12), 19.0);
+        assert_eq!(ledger.total_at(15), 27.0);
+        assert_eq!(ledger.total_between(5, 15), 11.0);
+        assert_eq!(ledger.total(), 31.0);
+        assert_eq!(
+            ledger
+                .entries()
+                .iter()
+                .map(|entry| entry.0)
+                .collect::<Vec<_>>(),
+            vec![5, 10, 10, 15, 20]
+        );
+    }
+
+    #[test]
+    fn test_compact() {
+        let mut finances = CompanyFinances::new(100.0);
+        for timestamp in 1..100 {
+            finances.add_income(timestamp, 1.0);
+            finances.add_expense(timestamp, 0.5);
+        }
+
+        finances.compact(55, 10);
+        assert_eq!(finances.income.len(), 1 + 5 + 49);
+        assert_eq!(finances.expenses.len(), 5 + 49);
+        assert_eq!(finances.income.entries()[0], (0, 100.0));
+        assert_eq!(finances.cash(0), 100.0);
+        assert_eq!(finances.cash(9), 100.0);
+        assert_eq!(finances.cash(10), 105.0);
+        assert_eq!(finances.cash(15), 105.0);
+        assert_eq!(finances.cash(50), 125.0);
+        assert_eq!(finances.cash(60), 130.0);
+        assert_eq!(finances.cash(99), 149.5);
+
+        finances.compact(75, 10);
+        assert_eq!(finances.income.len(), 1 + 7 + 29);
+        assert_eq!(finances.cash(69), 130.0);
+        assert_eq!(finances.cash(70), 135.0);
+        assert_eq!(finances.cash(99), 149.5);
+    }
 }
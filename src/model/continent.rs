@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, EnumIter)]
+pub enum Continent {
+    Africa,
+    Antarctica,
+    Asia,
+    Europe,
+    NorthAmerica,
+    Oceania,
+    SouthAmerica,
+}
+
+impl Continent {
+    /// Approximates the continent of a location by a few coordinate boundaries,
+    /// which is good enough for aerodromes.
+    pub fn from_coordinates(lat: f64, lon: f64) -> Self {
+        if lat < -60.0 {
+            Continent::Antarctica
+        } else if lon < -30.0 {
+            if lon < -130.0 && lat < 15.0 {
+                Continent::Oceania
+            } else if lat > 13.0 || (lon < -77.0 && lat > 7.0) {
+                Continent::NorthAmerica
+            } else {
+                Continent::SouthAmerica
+            }
+        } else if (lon > 110.0 && lat < -10.0) || (lon > 130.0 && lat < 0.0) {
+            Continent::Oceania
+        } else if (lat < 35.5 && lon < 12.0)
+            || (lat < 37.5 && (8.0..12.0).contains(&lon))
+            || (lat < 35.0 && lon < 34.0)
+            || (lat < 12.0 && lon < 52.0)
+            || (lat < 0.0 && lon < 60.0)
+        {
+            Continent::Africa
+        } else if lon < 26.0 || (lat > 42.0 && lon < 60.0) {
+            Continent::Europe
+        } else {
+            Continent::Asia
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_coordinates() {
+        let locations = [
+            (50.03, 8.57, Continent::Europe),          // Frankfurt
+            (55.97, 37.41, Continent::Europe),         // Moscow
+            (40.64, -73.78, Continent::NorthAmerica),  // New York
+            (9.07, -79.38, Continent::NorthAmerica),   // Panama City
+            (-23.43, -46.47, Continent::SouthAmerica), // Sao Paulo
+            (30.12, 31.41, Continent::Africa),         // Cairo
+            (36.85, 10.23, Continent::Africa),         // Tunis
+            (-33.96, 18.60, Continent::Africa),        // Cape Town
+            (25.25, 55.36, Continent::Asia),           // Dubai
+            (35.55, 139.78, Continent::Asia),          // Tokyo
+            (-33.95, 151.18, Continent::Oceania),      // Sydney
+            (-17.55, -149.61, Continent::Oceania),     // Tahiti
+        ];
+
+        for (lat, lon, continent) in locations {
+            assert_eq!(Continent::from_coordinates(lat, lon), continent);
+        }
+    }
+}
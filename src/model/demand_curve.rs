@@ -0,0 +1,314 @@
+use chrono::{Datelike, Timelike};
+use serde::{Deserialize, Serialize};
+
+use super::{Aerodrome, Continent, GameCalendar, PoiCategory, Timestamp};
+
+const DAYS_PER_YEAR: f32 = 365.0;
+const HOURS_PER_DAY: f32 = 24.0;
+const DAYS_PER_WEEK: f32 = 7.0;
+
+/// A periodic, piecewise linear curve of demand factors, e.g. over the
+/// hours of a day. The factor is 1.0 everywhere for a curve without points.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DemandCurve {
+    /// Length of a period in the unit of the positions.
+    pub period: f32,
+    /// Positions within the period with their factor.
+    pub points: Vec<(f32, f32)>,
+}
+
+impl DemandCurve {
+    pub fn new(period: f32, points: Vec<(f32, f32)>) -> Self {
+        Self { period, points }
+    }
+
+    pub fn flat(period: f32) -> Self {
+        Self::new(period, Vec::new())
+    }
+
+    /// Factor at a position, interpolated between the neighbouring points
+    /// and wrapping around at the end of the period.
+    pub fn factor(&self, position: f32) -> f32 {
+        let (Some(first), Some(last)) = (
+            self.points.iter().min_by(|a, b| a.0.total_cmp(&b.0)),
+            self.points.iter().max_by(|a, b| a.0.total_cmp(&b.0)),
+        ) else {
+            return 1.0;
+        };
+        let position = position.rem_euclid(self.period);
+
+        let before = self
+            .points
+            .iter()
+            .filter(|point| point.0 <= position)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .copied()
+            .unwrap_or((last.0 - self.period, last.1));
+        let after = self
+            .points
+            .iter()
+            .filter(|point| point.0 > position)
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .copied()
+            .unwrap_or((first.0 + self.period, first.1));
+
+        let width = after.0 - before.0;
+        if width <= 0.0 {
+            return before.1;
+        }
+        before.1 + (after.1 - before.1) * (position - before.0) / width
+    }
+}
+
+/// A curve over the days of the year for the aerodromes of a region or a
+/// category, e.g. the ski season in Europe.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SeasonalDemand {
+    pub name: String,
+    /// Region the curve applies to, all regions if not given.
+    #[serde(default)]
+    pub region: Option<Continent>,
+    /// Main category of the aerodromes the curve applies to, all aerodromes
+    /// if not given.
+    #[serde(default)]
+    pub category: Option<PoiCategory>,
+    /// Factors by the day of the year, starting at 0 for January 1st.
+    pub curve: DemandCurve,
+}
+
+impl SeasonalDemand {
+    pub fn applies_to(&self, aerodrome: &Aerodrome) -> bool {
+        self.region.map_or(true, |region| {
+            Continent::from_coordinates(aerodrome.lat, aerodrome.lon) == region
+        }) && self.category.map_or(true, |category| {
+            aerodrome.category_scores.main_category() == Some(category)
+        })
+    }
+}
+
+/// How demand changes over the year, the week and the day.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct DemandProfile {
+    pub seasonal: Vec<SeasonalDemand>,
+    /// Factors by the local hour of departure at the origin.
+    pub daily: DemandCurve,
+    /// Factors by the local day of departure at the origin, starting at 0 for
+    /// Monday.
+    pub weekly: DemandCurve,
+}
+
+impl Default for DemandProfile {
+    fn default() -> Self {
+        Self {
+            seasonal: vec![
+                SeasonalDemand {
+                    name: String::from("Ski season"),
+                    region: Some(Continent::Europe),
+                    category: Some(PoiCategory::Nature),
+                    curve: DemandCurve::new(
+                        DAYS_PER_YEAR,
+                        vec![(15.0, 1.3), (60.0, 1.3), (100.0, 1.0), (330.0, 1.0)],
+                    ),
+                },
+                SeasonalDemand {
+                    name: String::from("Summer holidays"),
+                    region: Some(Continent::Europe),
+                    category: Some(PoiCategory::Beach),
+                    curve: DemandCurve::new(
+                        DAYS_PER_YEAR,
+                        vec![(15.0, 0.7), (120.0, 1.0), (196.0, 1.4), (270.0, 1.0)],
+                    ),
+                },
+                SeasonalDemand {
+                    name: String::from("Christmas"),
+                    region: None,
+                    category: None,
+                    curve: DemandCurve::new(
+                        DAYS_PER_YEAR,
+                        vec![(3.0, 1.3), (10.0, 1.0), (345.0, 1.0), (356.0, 1.3)],
+                    ),
+                },
+            ],
+            daily: DemandCurve::new(
+                HOURS_PER_DAY,
+                vec![
+                    (0.0, 0.4),
+                    (5.0, 0.4),
+                    (8.0, 1.3),
+                    (12.0, 1.0),
+                    (18.0, 1.2),
+                    (22.0, 0.7),
+                ],
+            ),
+            weekly: DemandCurve::new(
+                DAYS_PER_WEEK,
+                vec![
+                    (0.0, 1.1),
+                    (1.0, 1.0),
+                    (3.0, 1.0),
+                    (4.0, 1.2),
+                    (5.0, 0.8),
+                    (6.0, 1.1),
+                ],
+            ),
+        }
+    }
+}
+
+impl DemandProfile {
+    /// A profile without any change of demand.
+    pub fn flat() -> Self {
+        Self {
+            seasonal: Vec::new(),
+            daily: DemandCurve::flat(HOURS_PER_DAY),
+            weekly: DemandCurve::flat(DAYS_PER_WEEK),
+        }
+    }
+
+    /// Demand factors of a route departing at `departure_time`, the season
+    /// is averaged over the aerodromes the flight lands on.
+    pub fn factors(
+        &self,
+        calendar: &GameCalendar,
+        origin: &Aerodrome,
+        stopovers: &[Aerodrome],
+        departure_time: Timestamp,
+    ) -> DemandFactors {
+        let local = calendar.local_date_time(departure_time, origin);
+        let day_of_year = calendar.date_time(departure_time).ordinal0() as f32;
+        let season = if stopovers.is_empty() {
+            1.0
+        } else {
+            stopovers
+                .iter()
+                .map(|aerodrome| self.season_factor(aerodrome, day_of_year))
+                .sum::<f32>()
+                / stopovers.len() as f32
+        };
+
+        DemandFactors {
+            season,
+            weekday: self
+                .weekly
+                .factor(local.weekday().num_days_from_monday() as f32),
+            time_of_day: self
+                .daily
+                .factor(local.hour() as f32 + local.minute() as f32 / 60.0),
+        }
+    }
+
+    /// Product of the seasonal curves applying to the aerodrome.
+    pub fn season_factor(&self, aerodrome: &Aerodrome, day_of_year: f32) -> f32 {
+        self.seasonal
+            .iter()
+            .filter(|seasonal| seasonal.applies_to(aerodrome))
+            .map(|seasonal| seasonal.curve.factor(day_of_year))
+            .product()
+    }
+}
+
+/// Factors the booked seats of a flight are scaled with.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DemandFactors {
+    pub season: f32,
+    pub weekday: f32,
+    pub time_of_day: f32,
+}
+
+impl DemandFactors {
+    pub fn total(&self) -> f32 {
+        self.season * self.weekday * self.time_of_day
+    }
+}
+
+impl Default for DemandFactors {
+    fn default() -> Self {
+        Self {
+            season: 1.0,
+            weekday: 1.0,
+            time_of_day: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::CategoryScores;
+    use chrono::NaiveDate;
+
+    const HOUR: Timestamp = 60 * 60 * 1000;
+
+    #[test]
+    fn test_curve_interpolates_and_wraps() {
+        let curve = DemandCurve::new(24.0, vec![(18.0, 2.0), (6.0, 1.0)]);
+
+        assert_eq!(curve.factor(6.0), 1.0);
+        assert_eq!(curve.factor(12.0), 1.5);
+        assert_eq!(curve.factor(18.0), 2.0);
+        assert_eq!(curve.factor(0.0), 1.5);
+        assert_eq!(curve.factor(30.0), 1.0);
+        assert_eq!(DemandCurve::flat(24.0).factor(7.0), 1.0);
+        assert_eq!(DemandCurve::new(7.0, vec![(2.0, 0.5)]).factor(5.0), 0.5);
+    }
+
+    #[test]
+    fn test_seasonal_demand_applies_to_region_and_category() {
+        let profile = DemandProfile::default();
+        let mallorca = Aerodrome {
+            lat: 39.55,
+            lon: 2.74,
+            category_scores: CategoryScores {
+                beach: 0.4,
+                culture: 0.1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let rio = Aerodrome {
+            lat: -22.8,
+            lon: -43.2,
+            ..mallorca.clone()
+        };
+
+        assert!(profile.season_factor(&mallorca, 196.0) > 1.3);
+        assert!(profile.season_factor(&mallorca, 15.0) < 0.8);
+        assert_eq!(profile.season_factor(&rio, 196.0), 1.0);
+        // Christmas applies everywhere
+        assert!(profile.season_factor(&rio, 360.0) > 1.2);
+    }
+
+    #[test]
+    fn test_factors_use_local_time_of_origin() {
+        let profile = DemandProfile::default();
+        // Monday
+        let calendar = GameCalendar::new(NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+        let frankfurt = Aerodrome::frankfurt();
+        let paris = Aerodrome::paris();
+
+        let night = profile.factors(&calendar, &frankfurt, &[paris.clone()], 2 * HOUR);
+        let morning = profile.factors(&calendar, &frankfurt, &[paris.clone()], 7 * HOUR);
+        assert_eq!(night.time_of_day, 0.4);
+        assert_eq!(morning.time_of_day, 1.3);
+        assert_eq!(morning.weekday, 1.1);
+        assert_eq!(morning.season, 1.0);
+        assert!(morning.total() > night.total());
+
+        let flat = DemandProfile::flat().factors(&calendar, &frankfurt, &[paris], 2 * HOUR);
+        assert_eq!(flat, DemandFactors::default());
+        assert_eq!(flat.total(), 1.0);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let yaml = "daily:\n  period: 24\n  points: [[0, 0.5], [12, 1.5]]\nseasonal:\n  - name: Ski\n    region: Europe\n    curve:\n      period: 365\n      points: [[30, 1.5]]\n";
+
+        let profile: DemandProfile = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(profile.daily.factor(6.0), 1.0);
+        assert_eq!(profile.seasonal[0].region, Some(Continent::Europe));
+        assert_eq!(profile.seasonal[0].category, None);
+        assert_eq!(profile.weekly, DemandProfile::default().weekly);
+    }
+}
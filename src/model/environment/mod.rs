@@ -1,6 +1,9 @@
 mod environment_iterators;
 
-use crate::model::CompanyFinances;
+use crate::model::{
+    analytics::{calculate_valuation, MetricsStore},
+    CompanyFinances,
+};
 
 use super::{
     identity::Identity, AirPlane, Base, EnvironmentConfig, Flight, LandingRights, Timestamp,
@@ -11,6 +14,8 @@ pub struct Environment {
     pub identity: Identity,
     pub config: EnvironmentConfig,
     pub company_finances: CompanyFinances,
+    /// Aggregates of the finances and landed flights for the analytics.
+    pub metrics: MetricsStore,
     pub planes: Vec<AirPlane>,
     pub bases: Vec<Base>,
     pub landing_rights: Vec<LandingRights>,
@@ -23,6 +28,7 @@ impl Environment {
         Self {
             identity: Identity::default(),
             company_finances: CompanyFinances::new(config.start_capital),
+            metrics: MetricsStore::new(config.start_capital),
             config,
             planes: vec![],
             bases: vec![],
@@ -33,6 +39,20 @@ impl Environment {
         }
     }
 
+    pub fn add_income(&mut self, timestamp: Timestamp, income: f64) {
+        self.company_finances.add_income(timestamp, income);
+    }
+
+    pub fn add_expense(&mut self, timestamp: Timestamp, expense: f64) {
+        self.company_finances.add_expense(timestamp, expense);
+    }
+
+    /// Cash, depreciated planes, bases, landing rights and route goodwill
+    /// minus debt, see [`calculate_valuation`].
+    pub fn net_worth(&self, timestamp: Timestamp) -> f64 {
+        calculate_valuation(self, timestamp).net_worth()
+    }
+
     pub fn calculate_errors_indicator(&self) -> u64 {
         let mut indicator = 0.0;
         for error in &self.last_errors {
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::{DemandProfile, GameCalendar, InterestModel, Restrictions};
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct EnvironmentConfig {
     pub start_capital: f64,
@@ -8,6 +10,18 @@ pub struct EnvironmentConfig {
     pub takeoff_cost: f64,
     pub fuel_cost_per_km: f64,
     pub income_per_km: f64,
+    #[serde(default)]
+    pub restrictions: Restrictions,
+    #[serde(default)]
+    pub calendar: GameCalendar,
+    #[serde(default)]
+    pub interest: InterestModel,
+    #[serde(default)]
+    pub demand: DemandProfile,
+    /// Whether the metrics and finances older than a day are merged into
+    /// hourly buckets, trading detail for memory in long games.
+    #[serde(default)]
+    pub compact_history: bool,
 }
 
 impl Default for EnvironmentConfig {
@@ -19,6 +33,11 @@ impl Default for EnvironmentConfig {
             takeoff_cost: 500.0,
             fuel_cost_per_km: 0.5,
             income_per_km: 5.0,
+            restrictions: Restrictions::default(),
+            calendar: GameCalendar::default(),
+            interest: InterestModel::default(),
+            demand: DemandProfile::default(),
+            compact_history: false,
         }
     }
 }
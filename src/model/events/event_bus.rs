@@ -0,0 +1,103 @@
+use crate::model::{Environment, Timestamp};
+
+use super::{Event, SimulationEvent, TimestampedEvent};
+
+type Subscriber = Box<dyn Fn(&mut Environment, &TimestampedEvent) + Send + Sync>;
+
+/// Collects published events and dispatches them to the handlers subscribed to
+/// their type.
+#[derive(Default)]
+pub struct EventBus {
+    events: Vec<TimestampedEvent>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl EventBus {
+    pub fn publish(&mut self, timestamp: Timestamp, event: impl Into<SimulationEvent>) {
+        self.events.push(TimestampedEvent::new(timestamp, event));
+    }
+
+    /// Subscribes `handler` to all events of type `E`.
+    pub fn subscribe<E: Event>(
+        &mut self,
+        handler: impl Fn(&mut Environment, &E) + Send + Sync + 'static,
+    ) {
+        self.subscribers
+            .push(Box::new(move |environment, timestamped_event| {
+                if let Some(event) = E::from_simulation_event(&timestamped_event.event) {
+                    handler(environment, event);
+                }
+            }));
+    }
+
+    pub fn pending_events(&self) -> &[TimestampedEvent] {
+        &self.events
+    }
+
+    pub fn clear_events(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn clear_subscribers(&mut self) {
+        self.subscribers.clear();
+    }
+
+    /// Dispatches all pending events in the order they were published and
+    /// returns them.
+    pub fn handle_events(&mut self, environment: &mut Environment) -> Vec<TimestampedEvent> {
+        let events = self.events.drain(..).collect::<Vec<_>>();
+        for event in events.iter() {
+            for subscriber in &self.subscribers {
+                subscriber(environment, event);
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use crate::model::{
+        events::{AirplaneLandedEvent, AirplaneTakeoffEvent},
+        Flight,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_subscribers_only_receive_their_event_type() {
+        let mut event_bus = EventBus::default();
+        let landings = Arc::new(AtomicUsize::new(0));
+
+        let counter = landings.clone();
+        event_bus.subscribe(move |_, _: &AirplaneLandedEvent| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        event_bus.publish(
+            1,
+            AirplaneTakeoffEvent {
+                flight: Flight::default(),
+            },
+        );
+        event_bus.publish(
+            2,
+            AirplaneLandedEvent {
+                flight: Flight::default(),
+            },
+        );
+        assert_eq!(event_bus.pending_events().len(), 2);
+
+        let handled = event_bus.handle_events(&mut Environment::default());
+
+        assert_eq!(landings.load(Ordering::SeqCst), 1);
+        assert_eq!(handled.len(), 2);
+        assert_eq!(handled[1].timestamp, 2);
+        assert!(event_bus.pending_events().is_empty());
+    }
+}
@@ -1,36 +1,23 @@
-use crate::model::Environment;
+use crate::model::{analytics::operating_costs, Environment};
 
-use super::Event;
+use super::{AirplaneLandedEvent, AirplaneTakeoffEvent};
 
-pub trait EventHandler {
-    fn handle(&self, environment: &mut Environment, event: &dyn Event);
+pub fn handle_airplane_landed(environment: &mut Environment, event: &AirplaneLandedEvent) {
+    environment.add_income(
+        environment.timestamp,
+        event.flight.calculate_profit() as f64,
+    );
+    let arrival_time = event.flight.arrival_time.unwrap_or(environment.timestamp);
+    let costs = operating_costs(environment, &event.flight);
+    environment
+        .metrics
+        .record_landing(arrival_time, &event.flight, costs);
 }
 
-pub struct AirplaneLandedEventHandler {}
+pub fn handle_airplane_takeoff(environment: &mut Environment, event: &AirplaneTakeoffEvent) {
+    let distance = event.flight.calculate_total_distance();
+    let fuel_cost = environment.config.fuel_cost_per_km * distance;
+    let takeoff_cost = environment.config.takeoff_cost;
 
-impl EventHandler for AirplaneLandedEventHandler {
-    fn handle(&self, environment: &mut Environment, event: &dyn Event) {
-        if let Some(event) = event.as_any().downcast_ref::<super::AirplaneLandedEvent>() {
-            environment.company_finances.add_income(
-                environment.timestamp,
-                event.flight.calculate_profit() as f64,
-            );
-        }
-    }
-}
-
-pub struct AirplaneTakeoffEventHandler {}
-
-impl EventHandler for AirplaneTakeoffEventHandler {
-    fn handle(&self, environment: &mut Environment, event: &dyn Event) {
-        if let Some(event) = event.as_any().downcast_ref::<super::AirplaneTakeoffEvent>() {
-            let distance = event.flight.calculate_total_distance();
-            let fuel_cost = environment.config.fuel_cost_per_km * distance;
-            let takeoff_cost = environment.config.takeoff_cost;
-
-            environment
-                .company_finances
-                .add_expense(environment.timestamp, takeoff_cost + fuel_cost);
-        }
-    }
+    environment.add_expense(environment.timestamp, takeoff_cost + fuel_cost);
 }
@@ -1,28 +1,97 @@
-use super::{Aerodrome, Flight, PlaneType};
-use std::any::Any;
+use serde::{Deserialize, Serialize};
 
+use super::{Aerodrome, Flight, PlaneType, Timestamp};
+
+mod event_bus;
 mod event_handlers;
-mod event_manager;
 
-pub use event_manager::EventManager;
+pub use event_bus::EventBus;
 
 pub use event_handlers::*;
 
-pub trait Event: Send + Sync {
-    fn as_any(&self) -> &dyn Any;
+/// A typed event which can be published on and subscribed to via the [`EventBus`].
+pub trait Event: Clone + Into<SimulationEvent> + Send + Sync + 'static {
+    /// Returns the event if `event` is of this type.
+    fn from_simulation_event(event: &SimulationEvent) -> Option<&Self>;
     fn message(&self) -> String;
 }
 
-#[derive(Clone)]
-pub struct AirplaneLandedEvent {
-    pub flight: Flight,
+macro_rules! simulation_events {
+    ($($variant:ident($event:ident)),* $(,)?) => {
+        /// All events that can happen in the simulation.
+        #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+        #[serde(tag = "type")]
+        pub enum SimulationEvent {
+            $($variant($event),)*
+        }
+
+        impl SimulationEvent {
+            pub fn message(&self) -> String {
+                match self {
+                    $(SimulationEvent::$variant(event) => event.message(),)*
+                }
+            }
+        }
+
+        $(
+            impl From<$event> for SimulationEvent {
+                fn from(event: $event) -> Self {
+                    SimulationEvent::$variant(event)
+                }
+            }
+
+            impl Event for $event {
+                fn from_simulation_event(event: &SimulationEvent) -> Option<&Self> {
+                    match event {
+                        SimulationEvent::$variant(event) => Some(event),
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+
+                fn message(&self) -> String {
+                    $event::message(self)
+                }
+            }
+        )*
+    };
+}
+
+simulation_events!(
+    AirplaneLanded(AirplaneLandedEvent),
+    AirplaneTakeoff(AirplaneTakeoffEvent),
+    BuyPlane(BuyPlaneEvent),
+    SellPlane(SellPlaneEvent),
+    CreateBase(CreateBaseEvent),
+    BuyLandingRights(BuyLandingRightsEvent),
+    SellLandingRights(SellLandingRightsEvent),
+    ScheduleFlight(ScheduleFlightEvent),
+    RemoveBase(RemoveBaseEvent),
+    CancelFlight(CancelFlightEvent),
+);
+
+/// A [`SimulationEvent`] together with the time it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimestampedEvent {
+    pub timestamp: Timestamp,
+    pub event: SimulationEvent,
 }
 
-impl Event for AirplaneLandedEvent {
-    fn as_any(&self) -> &dyn Any {
-        self
+impl TimestampedEvent {
+    pub fn new(timestamp: Timestamp, event: impl Into<SimulationEvent>) -> Self {
+        Self {
+            timestamp,
+            event: event.into(),
+        }
     }
+}
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AirplaneLandedEvent {
+    pub flight: Flight,
+}
+
+impl AirplaneLandedEvent {
     fn message(&self) -> String {
         format!(
             "Flight {} landed in {}",
@@ -32,16 +101,12 @@ impl Event for AirplaneLandedEvent {
     }
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AirplaneTakeoffEvent {
     pub flight: Flight,
 }
 
-impl Event for AirplaneTakeoffEvent {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
+impl AirplaneTakeoffEvent {
     fn message(&self) -> String {
         format!(
             "Flight {} started from {}",
@@ -50,44 +115,142 @@ impl Event for AirplaneTakeoffEvent {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BuyPlaneEvent {
+    pub plane_id: u64,
     pub plane_type: PlaneType,
+    pub home_base_id: u64,
 }
 
-impl Event for BuyPlaneEvent {
-    fn as_any(&self) -> &dyn Any {
-        self
+impl BuyPlaneEvent {
+    fn message(&self) -> String {
+        format!("Bought airplane {}", self.plane_type.name)
     }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SellPlaneEvent {
+    pub plane_id: u64,
+    pub plane_type: PlaneType,
+    pub home_base_id: u64,
+    pub refund: f64,
+}
 
+impl SellPlaneEvent {
     fn message(&self) -> String {
-        format!("Bought airplane {}", self.plane_type.name)
+        format!("Sold airplane {}", self.plane_type.name)
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CreateBaseEvent {
+    pub base_id: u64,
     pub aerodrome: Aerodrome,
+    pub cost: f64,
 }
 
-impl Event for CreateBaseEvent {
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
+impl CreateBaseEvent {
     fn message(&self) -> String {
         format!("Created base at {}", self.aerodrome.name)
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BuyLandingRightsEvent {
+    pub landing_rights_id: u64,
     pub aerodrome: Aerodrome,
 }
 
-impl Event for BuyLandingRightsEvent {
-    fn as_any(&self) -> &dyn Any {
-        self
+impl BuyLandingRightsEvent {
+    fn message(&self) -> String {
+        format!("Bought landing rights at {}", self.aerodrome.name)
     }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SellLandingRightsEvent {
+    pub landing_rights_id: u64,
+    pub aerodrome: Aerodrome,
+    pub refund: f64,
+}
 
+impl SellLandingRightsEvent {
     fn message(&self) -> String {
-        format!("Bought landing rights at {}", self.aerodrome.name)
+        format!("Sold landing rights at {}", self.aerodrome.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleFlightEvent {
+    pub flight: Flight,
+}
+
+impl ScheduleFlightEvent {
+    fn message(&self) -> String {
+        format!(
+            "Scheduled flight {} from {}",
+            self.flight.flight_id, self.flight.origin_aerodrome.name
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemoveBaseEvent {
+    pub base_id: u64,
+    pub aerodrome: Aerodrome,
+    pub refund: f64,
+}
+
+impl RemoveBaseEvent {
+    fn message(&self) -> String {
+        format!("Removed base at {}", self.aerodrome.name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CancelFlightEvent {
+    pub flight: Flight,
+}
+
+impl CancelFlightEvent {
+    fn message(&self) -> String {
+        format!(
+            "Cancelled flight {} from {}",
+            self.flight.flight_id, self.flight.origin_aerodrome.name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamped_event_serialization() {
+        let event = TimestampedEvent::new(
+            42,
+            CreateBaseEvent {
+                base_id: 1,
+                aerodrome: Aerodrome::frankfurt(),
+                cost: 400_000.0,
+            },
+        );
+
+        let yaml = serde_yaml::to_string(&event).unwrap();
+        assert!(yaml.contains("type: CreateBase"));
+
+        let deserialized: TimestampedEvent = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(deserialized, event);
+    }
+
+    #[test]
+    fn test_from_simulation_event() {
+        let event: SimulationEvent = AirplaneLandedEvent {
+            flight: Flight::default(),
+        }
+        .into();
+
+        assert!(AirplaneLandedEvent::from_simulation_event(&event).is_some());
+        assert!(AirplaneTakeoffEvent::from_simulation_event(&event).is_none());
     }
 }
@@ -1,11 +1,12 @@
-use crate::model::{Aerodrome, AirPlane};
+use crate::model::{Aerodrome, AirPlane, DemandFactors};
 use geo::{algorithm::vincenty_distance::VincentyDistance, Point};
+use serde::{Deserialize, Serialize};
 
 use super::Timestamp;
 
 const PROFIT_PER_KILOMETER: f64 = 1.0;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Flight {
     pub flight_id: u64,
     pub airplane: AirPlane,
@@ -15,6 +16,9 @@ pub struct Flight {
     pub segment_departure_time: Timestamp,
     pub arrival_time: Option<Timestamp>,
     pub state: FlightState,
+    /// Season, weekday and time of day the flight was scheduled for.
+    #[serde(default)]
+    pub demand: DemandFactors,
 }
 
 /// Default implementation for Flight
@@ -32,11 +36,12 @@ impl Default for Flight {
             segment_departure_time: 0,
             arrival_time: None,
             state: FlightState::Scheduled,
+            demand: DemandFactors::default(),
         }
     }
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum FlightState {
     #[default]
     Scheduled,
@@ -58,10 +63,18 @@ impl Flight {
         distance_in_meters / 1000.0
     }
 
+    /// Passengers booked by the demand for the route, at most the seats of
+    /// the airplane.
     pub fn calculate_booked_seats(&self) -> u32 {
-        let seats = self.airplane.plane_type.seats as f32;
-        let booked_seats: f32 = seats * (1.0 + 4.0 * self.interest_score() / 5.0);
-        booked_seats.round() as u32
+        let seats = self.airplane.plane_type.seats;
+        let booked_seats: f32 =
+            seats as f32 * (1.0 + 4.0 * self.interest_score() / 5.0) * self.demand.total();
+        (booked_seats.round() as u32).min(seats)
+    }
+
+    /// Booked seats per seat of the airplane.
+    pub fn load_factor(&self) -> f32 {
+        self.calculate_booked_seats() as f32 / self.airplane.plane_type.seats.max(1) as f32
     }
 
     pub fn interest_score(&self) -> f32 {
@@ -93,8 +106,8 @@ impl Flight {
                     self.state = FlightState::Landed {
                         next_stopover_index,
                     };
-                    self.segment_departure_time = current_time + 30 * 60 * 1000;
-                // 30 minutes
+                    self.segment_departure_time =
+                        current_time + self.airplane.plane_type.turnaround_duration();
                 } else {
                     self.state = FlightState::Finished;
                 }
@@ -111,6 +124,17 @@ impl Flight {
         }
     }
 
+    /// Returns the timestamp at which the flight will change its state next,
+    /// or `None` if the flight is finished.
+    pub fn next_transition_time(&self) -> Option<Timestamp> {
+        match self.state {
+            FlightState::Scheduled => Some(self.departure_time),
+            FlightState::EnRoute { .. } => self.arrival_time,
+            FlightState::Landed { .. } => Some(self.segment_departure_time),
+            FlightState::Finished => None,
+        }
+    }
+
     fn update_arrival_time(&mut self) {
         if let Some(next_aerodrome) = self.current_destination() {
             let distance =
@@ -251,6 +275,7 @@ mod tests {
             code: "SFO/KSFO".to_string(),
             interest_score: 0.0,
             passengers: None,
+            ..Default::default()
         };
 
         let destination_aerodrome = Aerodrome {
@@ -261,6 +286,7 @@ mod tests {
             code: "LAX/KLAX".to_string(),
             interest_score: 0.0,
             passengers: None,
+            ..Default::default()
         };
 
         let flight = Flight {
@@ -272,12 +298,34 @@ mod tests {
             arrival_time: None,
             state: Default::default(),
             stopovers: vec![destination_aerodrome],
+            demand: DemandFactors::default(),
         };
 
         let distance = flight.calculate_total_distance();
         let expected_profit = distance * airplane.plane_type.seats as f64;
         let profit = flight.calculate_profit();
         assert!((profit - expected_profit).abs() < 1.0);
+        assert_eq!(flight.load_factor(), 1.0);
+
+        let quiet = Flight {
+            demand: DemandFactors {
+                time_of_day: 0.5,
+                ..Default::default()
+            },
+            ..flight.clone()
+        };
+        assert_eq!(quiet.calculate_booked_seats(), 75);
+        assert!((quiet.calculate_profit() - 0.5 * expected_profit).abs() < 1.0);
+
+        let busy = Flight {
+            demand: DemandFactors {
+                time_of_day: 1.5,
+                ..Default::default()
+            },
+            ..flight
+        };
+        assert_eq!(busy.calculate_booked_seats(), 150);
+        assert_eq!(busy.load_factor(), 1.0);
     }
 
     #[test]
@@ -288,4 +336,27 @@ mod tests {
         let distance = Flight::calculate_distance_between(&frankfurt, &paris);
         assert!((distance - 450.0).abs() < 1.0);
     }
+
+    #[test]
+    fn test_next_transition_time() {
+        let mut flight = Flight {
+            departure_time: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(flight.next_transition_time(), Some(1_000));
+
+        flight.update_state(1_000);
+        assert!(flight.next_transition_time().unwrap() > 1_000);
+        assert_eq!(flight.next_transition_time(), flight.arrival_time);
+
+        let arrival_time = flight.arrival_time.unwrap();
+        flight.update_state(arrival_time);
+        assert_eq!(
+            flight.next_transition_time(),
+            Some(arrival_time + flight.airplane.plane_type.turnaround_duration())
+        );
+
+        flight.state = FlightState::Finished;
+        assert_eq!(flight.next_transition_time(), None);
+    }
 }
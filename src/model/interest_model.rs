@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, IntoEnumIterator};
+
+use super::{Aerodrome, CategoryScores, PoiCategory};
+
+/// Turns the category scores of aerodromes into travel demand. Seasons are
+/// modelled by the seasonal curves of the
+/// [`DemandProfile`](super::DemandProfile) only.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct InterestModel {
+    /// Weight of each category in the interest in an aerodrome.
+    pub weights: CategoryScores,
+}
+
+impl Default for InterestModel {
+    fn default() -> Self {
+        Self {
+            weights: CategoryScores::uniform(1.0),
+        }
+    }
+}
+
+impl InterestModel {
+    /// Weighted sum of the category scores, capped at 1.0.
+    pub fn weighted_score(&self, scores: &CategoryScores) -> f32 {
+        PoiCategory::iter()
+            .map(|category| self.weights.get(category) * scores.get(category))
+            .sum::<f32>()
+            .min(1.0)
+    }
+
+    /// Demand for a route, averaged over the aerodromes a flight lands on.
+    pub fn travel_demand(&self, stopovers: &[Aerodrome]) -> TravelDemand {
+        if stopovers.is_empty() {
+            return TravelDemand::default();
+        }
+        let sum = stopovers
+            .iter()
+            .map(|aerodrome| self.aerodrome_demand(aerodrome))
+            .fold(TravelDemand::default(), |sum, demand| TravelDemand {
+                business: sum.business + demand.business,
+                leisure: sum.leisure + demand.leisure,
+            });
+        let count = stopovers.len() as f32;
+        TravelDemand {
+            business: sum.business / count,
+            leisure: sum.leisure / count,
+        }
+    }
+
+    fn aerodrome_demand(&self, aerodrome: &Aerodrome) -> TravelDemand {
+        let mut demand = TravelDemand::default();
+        for category in PoiCategory::iter() {
+            let interest = self.weights.get(category) * aerodrome.category_scores.get(category);
+            if category.is_leisure() {
+                demand.leisure += interest;
+            } else {
+                demand.business += interest;
+            }
+        }
+        demand
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum TravelPurpose {
+    Business,
+    Leisure,
+}
+
+/// Weighted interest of business and leisure travellers in a route.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TravelDemand {
+    pub business: f32,
+    pub leisure: f32,
+}
+
+impl TravelDemand {
+    pub fn total(&self) -> f32 {
+        self.business + self.leisure
+    }
+
+    /// Share of business travellers, 0.0 for a route without demand.
+    pub fn business_share(&self) -> f32 {
+        if self.total() > 0.0 {
+            self.business / self.total()
+        } else {
+            0.0
+        }
+    }
+
+    /// The purpose most travellers on the route have.
+    pub fn purpose(&self) -> TravelPurpose {
+        if self.business > self.leisure {
+            TravelPurpose::Business
+        } else {
+            TravelPurpose::Leisure
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aerodrome(scores: CategoryScores) -> Aerodrome {
+        Aerodrome {
+            category_scores: scores,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_weighted_score() {
+        let scores = CategoryScores {
+            culture: 0.3,
+            nature: 0.2,
+            ..Default::default()
+        };
+        let model = InterestModel::default();
+        assert!((model.weighted_score(&scores) - 0.5).abs() < 1e-6);
+
+        let model = InterestModel {
+            weights: CategoryScores {
+                culture: 4.0,
+                ..Default::default()
+            },
+        };
+        assert_eq!(model.weighted_score(&scores), 1.0);
+    }
+
+    #[test]
+    fn test_travel_demand_prefers_business_or_leisure() {
+        let model = InterestModel::default();
+        let city = aerodrome(CategoryScores {
+            business: 0.8,
+            culture: 0.2,
+            ..Default::default()
+        });
+        let island = aerodrome(CategoryScores {
+            beach: 0.6,
+            ..Default::default()
+        });
+
+        let demand = model.travel_demand(&[city.clone()]);
+        assert_eq!(demand.purpose(), TravelPurpose::Business);
+        assert!(demand.business_share() > 0.7);
+
+        let demand = model.travel_demand(&[island.clone()]);
+        assert_eq!(demand.purpose(), TravelPurpose::Leisure);
+
+        let both = model.travel_demand(&[city, island]);
+        assert!(both.business > 0.0 && both.leisure > 0.0);
+
+        assert_eq!(model.travel_demand(&[]).business_share(), 0.0);
+    }
+}
@@ -4,4 +4,6 @@ use super::Aerodrome;
 pub struct LandingRights {
     pub id: u64,
     pub aerodrome: Aerodrome,
+    /// Price paid for the landing rights, refunded when they are sold.
+    pub cost: f64,
 }
@@ -3,28 +3,42 @@ mod airplane;
 pub mod analytics;
 mod attraction;
 mod base;
+mod calendar;
 pub mod commands;
 mod company_finances;
+mod continent;
+mod demand_curve;
 mod environment;
 mod environment_config;
 pub mod events;
 mod flight;
 pub mod identity;
+mod interest_model;
 mod landing_rights;
+mod objective;
 mod plane_type;
+mod poi_category;
+mod restrictions;
 pub mod world_data;
 mod world_heritage_site;
 
-pub use aerodrome::Aerodrome;
+pub use aerodrome::{split_code, Aerodrome};
 pub use airplane::AirPlane;
 pub use attraction::Attraction;
 pub use base::Base;
-pub use company_finances::CompanyFinances;
+pub use calendar::GameCalendar;
+pub use company_finances::{CompanyFinances, Ledger};
+pub use continent::Continent;
+pub use demand_curve::{DemandCurve, DemandFactors, DemandProfile, SeasonalDemand};
 pub use environment::Environment;
 pub use environment_config::EnvironmentConfig;
 pub use flight::{Flight, FlightState};
+pub use interest_model::{InterestModel, TravelDemand, TravelPurpose};
 pub use landing_rights::LandingRights;
-pub use plane_type::PlaneType;
+pub use objective::Objective;
+pub use plane_type::{CabinConfiguration, PlaneType};
+pub use poi_category::{CategoryScores, PoiCategory};
+pub use restrictions::Restrictions;
 pub use world_data::{StringBasedWorldData, WorldDataGateway};
 pub use world_heritage_site::WorldHeritageSite;
 
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Environment, FlightState};
+
+/// A goal of a level which has to be reached to win it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Objective {
+    ReachCash {
+        amount: f64,
+    },
+    /// Fly to or from aerodromes on `count` different continents.
+    ConnectContinents {
+        count: usize,
+    },
+    CarryPassengers {
+        count: u64,
+    },
+}
+
+impl Objective {
+    pub fn description(&self) -> String {
+        match self {
+            Objective::ReachCash { amount } => format!("Reach ${:.0} cash", amount),
+            Objective::ConnectContinents { count } => format!("Connect {} continents", count),
+            Objective::CarryPassengers { count } => format!("Carry {} passengers", count),
+        }
+    }
+
+    /// Current value of the objective's measure, to be compared with [`Objective::target`].
+    pub fn progress(&self, environment: &Environment) -> f64 {
+        match self {
+            Objective::ReachCash { .. } => environment.company_finances.cash(environment.timestamp),
+            Objective::ConnectContinents { .. } => {
+                let continents: HashSet<_> = environment
+                    .flights
+                    .iter()
+                    .filter(|flight| flight.state != FlightState::Scheduled)
+                    .flat_map(|flight| {
+                        std::iter::once(&flight.origin_aerodrome).chain(flight.stopovers.iter())
+                    })
+                    .map(|aerodrome| aerodrome.continent())
+                    .collect();
+                continents.len() as f64
+            }
+            Objective::CarryPassengers { .. } => environment
+                .flights
+                .iter()
+                .filter(|flight| flight.state == FlightState::Finished)
+                .map(|flight| flight.calculate_booked_seats() as f64)
+                .sum(),
+        }
+    }
+
+    pub fn target(&self) -> f64 {
+        match self {
+            Objective::ReachCash { amount } => *amount,
+            Objective::ConnectContinents { count } => *count as f64,
+            Objective::CarryPassengers { count } => *count as f64,
+        }
+    }
+
+    pub fn is_met(&self, environment: &Environment) -> bool {
+        self.progress(environment) >= self.target()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Aerodrome, Flight};
+
+    use super::*;
+
+    #[test]
+    fn test_objectives() {
+        let mut environment = Environment::default();
+        let flight = Flight {
+            origin_aerodrome: Aerodrome::paris(),
+            stopovers: vec![Aerodrome::new_york()],
+            state: FlightState::Finished,
+            ..Default::default()
+        };
+        let passengers = flight.calculate_booked_seats() as u64;
+        environment.flights.push(flight);
+
+        assert!(Objective::ReachCash { amount: 500_000.0 }.is_met(&environment));
+        assert!(!Objective::ReachCash {
+            amount: 2_000_000.0
+        }
+        .is_met(&environment));
+        assert!(Objective::ConnectContinents { count: 2 }.is_met(&environment));
+        assert!(!Objective::ConnectContinents { count: 3 }.is_met(&environment));
+        assert!(Objective::CarryPassengers { count: passengers }.is_met(&environment));
+        assert!(!Objective::CarryPassengers {
+            count: passengers + 1
+        }
+        .is_met(&environment));
+    }
+}
@@ -1,15 +1,78 @@
 use serde::{Deserialize, Serialize};
 
+use super::{Aerodrome, Timestamp};
+
+/// Seats per cabin class of a plane type.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct CabinConfiguration {
+    pub economy: u32,
+    pub business: u32,
+    pub first: u32,
+}
+
+impl CabinConfiguration {
+    pub fn seats(&self) -> u32 {
+        self.economy + self.business + self.first
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct PlaneType {
     pub id: u32,
     pub name: String,
+    #[serde(default)]
+    pub manufacturer: String,
+    #[serde(default)]
+    pub family: String,
     pub cost: f32,
     pub monthly_income: f32,
     pub speed: f32,
+    /// Range in kilometers.
     pub range: f32,
     pub seats: u32,
+    /// Seats per class, all seats are economy seats if not given.
+    #[serde(default)]
+    pub cabin: Option<CabinConfiguration>,
     pub fuel_consumption_per_km: f32,
+    /// Minimum runway length needed for takeoff and landing in meters.
+    #[serde(default)]
+    pub min_runway_length: f32,
+    /// Time on the ground between two flight segments in minutes.
+    #[serde(default = "default_turnaround_time")]
+    pub turnaround_time: u32,
+}
+
+fn default_turnaround_time() -> u32 {
+    30
+}
+
+impl PlaneType {
+    pub fn cabin(&self) -> CabinConfiguration {
+        self.cabin.unwrap_or(CabinConfiguration {
+            economy: self.seats,
+            ..Default::default()
+        })
+    }
+
+    pub fn turnaround_duration(&self) -> Timestamp {
+        self.turnaround_time as Timestamp * 60 * 1000
+    }
+
+    /// Runway length needed at an elevation of `altitude` meters, the thinner
+    /// air adds 7% for every 300 meters.
+    pub fn required_runway_length(&self, altitude: f32) -> f32 {
+        self.min_runway_length * (1.0 + 0.07 * altitude.max(0.0) / 300.0)
+    }
+
+    /// Whether the plane can take off and land at the aerodrome. Runway lengths
+    /// are only known for the major airports in `runways.csv`, all other
+    /// aerodromes are always usable.
+    pub fn can_use(&self, aerodrome: &Aerodrome) -> bool {
+        aerodrome.runway_length.map_or(true, |length| {
+            length >= self.required_runway_length(aerodrome.altitude)
+        })
+    }
 }
 
 impl Default for PlaneType {
@@ -17,12 +80,59 @@ impl Default for PlaneType {
         Self {
             id: 0,
             name: String::from("Small Plane"),
+            manufacturer: String::new(),
+            family: String::new(),
             cost: 100000.0,
             monthly_income: 0.0,
             speed: 800.0,
             range: 4000.0,
             seats: 150,
+            cabin: None,
             fuel_consumption_per_km: 3.0,
+            min_runway_length: 0.0,
+            turnaround_time: default_turnaround_time(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_defaults() {
+        let yaml = "id: 7\nname: Test Plane\ncost: 1000\nmonthly_income: 0\nspeed: 500\nrange: 1000\nseats: 10\nfuel_consumption_per_km: 1\n";
+
+        let plane_type: PlaneType = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(plane_type.turnaround_time, 30);
+        assert_eq!(plane_type.turnaround_duration(), 30 * 60 * 1000);
+        assert_eq!(plane_type.cabin().economy, 10);
+        assert_eq!(plane_type.cabin().seats(), plane_type.seats);
+        assert_eq!(plane_type.min_runway_length, 0.0);
+    }
+
+    #[test]
+    fn test_can_use() {
+        let plane_type = PlaneType {
+            min_runway_length: 2500.0,
+            ..Default::default()
+        };
+        let mut aerodrome = Aerodrome {
+            runway_length: Some(3000.0),
+            ..Default::default()
+        };
+        assert!(plane_type.can_use(&aerodrome));
+
+        aerodrome.altitude = 1500.0;
+        assert!((plane_type.required_runway_length(1500.0) - 3375.0).abs() < 1e-3);
+        assert!(!plane_type.can_use(&aerodrome));
+
+        aerodrome.runway_length = Some(2000.0);
+        aerodrome.altitude = 0.0;
+        assert!(!plane_type.can_use(&aerodrome));
+
+        aerodrome.runway_length = None;
+        assert!(plane_type.can_use(&aerodrome));
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+
+/// What travellers visit a point of interest for.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum PoiCategory {
+    #[default]
+    Culture,
+    Nature,
+    Business,
+    Beach,
+}
+
+impl PoiCategory {
+    /// Maps the category of the world heritage list, mixed sites count as
+    /// culture.
+    pub fn from_heritage_category(category: &str) -> Option<Self> {
+        match category {
+            "Cultural" | "Mixed" => Some(PoiCategory::Culture),
+            "Natural" => Some(PoiCategory::Nature),
+            _ => None,
+        }
+    }
+
+    /// Whether travellers visit the category on holidays.
+    pub fn is_leisure(&self) -> bool {
+        !matches!(self, PoiCategory::Business)
+    }
+}
+
+/// A value for each [`PoiCategory`], used for scores and weights.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct CategoryScores {
+    pub culture: f32,
+    pub nature: f32,
+    pub business: f32,
+    pub beach: f32,
+}
+
+impl CategoryScores {
+    /// The same value for every category.
+    pub fn uniform(value: f32) -> Self {
+        Self {
+            culture: value,
+            nature: value,
+            business: value,
+            beach: value,
+        }
+    }
+
+    pub fn get(&self, category: PoiCategory) -> f32 {
+        match category {
+            PoiCategory::Culture => self.culture,
+            PoiCategory::Nature => self.nature,
+            PoiCategory::Business => self.business,
+            PoiCategory::Beach => self.beach,
+        }
+    }
+
+    pub fn set(&mut self, category: PoiCategory, value: f32) {
+        match category {
+            PoiCategory::Culture => self.culture = value,
+            PoiCategory::Nature => self.nature = value,
+            PoiCategory::Business => self.business = value,
+            PoiCategory::Beach => self.beach = value,
+        }
+    }
+
+    /// The category with the highest score, `None` if all scores are zero.
+    pub fn main_category(&self) -> Option<PoiCategory> {
+        PoiCategory::iter()
+            .filter(|category| self.get(*category) > 0.0)
+            .max_by(|a, b| self.get(*a).total_cmp(&self.get(*b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_category() {
+        assert_eq!(PoiCategory::from_str("beach"), Ok(PoiCategory::Beach));
+        assert_eq!(PoiCategory::from_str("Business"), Ok(PoiCategory::Business));
+        assert!(PoiCategory::from_str("ski").is_err());
+        assert_eq!(
+            PoiCategory::from_heritage_category("Natural"),
+            Some(PoiCategory::Nature)
+        );
+        assert_eq!(
+            PoiCategory::from_heritage_category("Mixed"),
+            Some(PoiCategory::Culture)
+        );
+    }
+
+    #[test]
+    fn test_category_scores() {
+        let mut scores = CategoryScores::default();
+        for (value, category) in PoiCategory::iter().enumerate() {
+            scores.set(category, value as f32);
+        }
+
+        assert_eq!(scores.get(PoiCategory::Culture), 0.0);
+        assert_eq!(scores.get(PoiCategory::Beach), 3.0);
+        assert_eq!(CategoryScores::uniform(0.5).get(PoiCategory::Nature), 0.5);
+        assert_eq!(scores.main_category(), Some(PoiCategory::Beach));
+        assert_eq!(CategoryScores::default().main_category(), None);
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+
+use super::{Aerodrome, Continent, PlaneType};
+
+/// Limits which aerodromes and plane types may be used. Empty lists allow
+/// everything.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct Restrictions {
+    /// Codes of allowed aerodromes, e.g. `FRA` or `FRA/EDDF`.
+    pub aerodromes: Vec<String>,
+    pub regions: Vec<Continent>,
+    /// Names of allowed plane types.
+    pub plane_types: Vec<String>,
+}
+
+impl Restrictions {
+    /// An aerodrome is allowed if it is listed or lies in an allowed region.
+    pub fn allows_aerodrome(&self, aerodrome: &Aerodrome) -> bool {
+        if self.aerodromes.is_empty() && self.regions.is_empty() {
+            return true;
+        }
+        self.aerodromes.iter().any(|code| aerodrome.has_code(code))
+            || self.regions.contains(&aerodrome.continent())
+    }
+
+    pub fn allows_plane_type(&self, plane_type: &PlaneType) -> bool {
+        self.plane_types.is_empty() || self.plane_types.contains(&plane_type.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allows_aerodrome() {
+        assert!(Restrictions::default().allows_aerodrome(&Aerodrome::paris()));
+
+        let restrictions = Restrictions {
+            aerodromes: vec!["JFK".to_string()],
+            regions: vec![Continent::Europe],
+            ..Default::default()
+        };
+        assert!(restrictions.allows_aerodrome(&Aerodrome::paris()));
+        assert!(restrictions.allows_aerodrome(&Aerodrome::new_york()));
+        assert!(!restrictions.allows_aerodrome(&Aerodrome::san_francisco()));
+    }
+
+    #[test]
+    fn test_allows_plane_type() {
+        let restrictions = Restrictions {
+            plane_types: vec!["Small Plane".to_string()],
+            ..Default::default()
+        };
+        let mut plane_type = PlaneType {
+            name: "Small Plane".to_string(),
+            ..Default::default()
+        };
+        assert!(restrictions.allows_plane_type(&plane_type));
+
+        plane_type.name = "Large Plane".to_string();
+        assert!(!restrictions.allows_plane_type(&plane_type));
+    }
+}
@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::ParseMode,
+    model::{Aerodrome, Attraction, InterestModel, PlaneType, WorldHeritageSite},
+};
+
+use super::{StringBasedWorldData, WorldDataError, WorldDataFiles, WorldDataGateway};
+
+/// World data read from a directory, which is read again on every reload so
+/// the files can be edited while the game is running.
+pub struct FileBasedWorldData {
+    directory: PathBuf,
+    data: StringBasedWorldData,
+    interest_model: InterestModel,
+}
+
+impl FileBasedWorldData {
+    pub fn new(directory: impl Into<PathBuf>) -> Result<Self, WorldDataError> {
+        Self::with_mode(directory, ParseMode::Strict)
+    }
+
+    pub fn with_mode(
+        directory: impl Into<PathBuf>,
+        mode: ParseMode,
+    ) -> Result<Self, WorldDataError> {
+        let directory = directory.into();
+        let files = WorldDataFiles::read_from_directory(&directory)?;
+        let data = StringBasedWorldData::with_mode(files, mode)?;
+        Ok(Self {
+            directory,
+            data,
+            interest_model: InterestModel::default(),
+        })
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Rows skipped by the last load in lenient mode.
+    pub fn skipped(&self) -> &[WorldDataError] {
+        self.data.skipped()
+    }
+}
+
+impl WorldDataGateway for FileBasedWorldData {
+    fn aerodromes(&self) -> &Vec<Aerodrome> {
+        self.data.aerodromes()
+    }
+
+    fn attractions(&self) -> Vec<Attraction> {
+        self.data.attractions()
+    }
+
+    fn world_heritage_sites(&self) -> &Vec<WorldHeritageSite> {
+        self.data.world_heritage_sites()
+    }
+
+    fn plane_types(&self) -> &Vec<PlaneType> {
+        self.data.plane_types()
+    }
+
+    fn nearest_aerodromes(&self, lat: f64, lon: f64, k: usize) -> Vec<&Aerodrome> {
+        self.data.nearest_aerodromes(lat, lon, k)
+    }
+
+    fn aerodromes_within(&self, lat: f64, lon: f64, range: f64) -> Vec<&Aerodrome> {
+        self.data.aerodromes_within(lat, lon, range)
+    }
+
+    fn reload(&mut self) -> Result<(), WorldDataError> {
+        let files = WorldDataFiles::read_from_directory(&self.directory)?;
+        self.data = StringBasedWorldData::with_mode(files, self.data.mode())?;
+        self.data.set_interest_model(self.interest_model);
+        Ok(())
+    }
+
+    fn set_interest_model(&mut self, interest_model: InterestModel) {
+        self.interest_model = interest_model;
+        self.data.set_interest_model(interest_model);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    static TEMP_DIR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    /// Empty directory only used by the calling test, unique across test
+    /// threads and concurrent test runs.
+    fn temp_dir() -> PathBuf {
+        let directory = std::env::temp_dir().join(format!(
+            "flyconomy_file_based_world_data_{}_{}",
+            std::process::id(),
+            TEMP_DIR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn test_load_from_directory() {
+        let directory = temp_dir();
+        std::fs::write(
+            directory.join(WorldDataFiles::PLANES),
+            "planes:\n  - id: 0\n    name: \"Test Plane\"\n    cost: 1000\n    monthly_income: 0\n    speed: 500\n    range: 1000\n    seats: 10\n    fuel_consumption_per_km: 1\n",
+        )
+        .unwrap();
+
+        let mut data = FileBasedWorldData::new(&directory).unwrap();
+        assert_eq!(data.plane_types().len(), 1);
+        assert_eq!(data.plane_types()[0].name, "Test Plane");
+        assert_eq!(data.aerodromes().len(), 7698);
+
+        std::fs::write(directory.join(WorldDataFiles::PLANES), "planes: [").unwrap();
+        let error = data.reload().unwrap_err();
+        assert!(error.to_string().starts_with("Failed to parse planes.yaml"));
+        assert!(matches!(
+            error.location(),
+            Some((WorldDataFiles::PLANES, Some(_), Some(_)))
+        ));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_load_broken_directory() {
+        let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/broken");
+
+        let error = FileBasedWorldData::new(&directory).err().unwrap();
+        assert_eq!(
+            error.location(),
+            Some((WorldDataFiles::AIRPORTS, Some(2), Some(7)))
+        );
+
+        let data = FileBasedWorldData::with_mode(&directory, ParseMode::Lenient).unwrap();
+        assert_eq!(data.aerodromes().len(), 2);
+        assert_eq!(data.attractions().len(), 1);
+        assert_eq!(data.plane_types().len(), 2);
+        assert_eq!(data.world_heritage_sites().len(), 1121);
+        assert_eq!(data.skipped().len(), 10);
+        assert_eq!(
+            data.skipped()[0].to_string(),
+            "Failed to parse airports.dat: line 2, column 7: latitude 95 is out of range"
+        );
+    }
+}
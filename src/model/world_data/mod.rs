@@ -1,92 +1,235 @@
+use strum::IntoEnumIterator;
+use thiserror::Error;
+
 use crate::{
-    algorithms::calculate_interest_score,
-    config::{load_airports, parse_attractions_csv, parse_world_heritage_site_csv, PlanesConfig},
+    algorithms::{calculate_interest_score_with_index, SpatialIndex},
+    config::{
+        assign_passengers, assign_runway_lengths, parse_airport_csv, parse_attractions_csv,
+        parse_passengers_csv, parse_planes_yaml, parse_runways_csv, parse_world_heritage_site_csv,
+        ParseError, ParseMode, Parsed,
+    },
 };
 
+mod file_based_world_data;
+mod world_data_files;
+mod world_data_source;
+
 #[cfg(feature = "web3")]
 pub mod web3_world_data;
 
-use super::{Aerodrome, Attraction, PlaneType, WorldHeritageSite};
+pub use file_based_world_data::FileBasedWorldData;
+pub use world_data_files::WorldDataFiles;
+pub use world_data_source::WorldDataSource;
+
+use super::{Aerodrome, Attraction, InterestModel, PlaneType, PoiCategory, WorldHeritageSite};
 
 pub trait WorldDataGateway: Sync + Send {
-    fn reload(&mut self) -> Result<(), String>;
+    fn reload(&mut self) -> Result<(), WorldDataError>;
     fn aerodromes(&self) -> &Vec<Aerodrome>;
     fn plane_types(&self) -> &Vec<PlaneType>;
     fn world_heritage_sites(&self) -> &Vec<WorldHeritageSite>;
     fn attractions(&self) -> Vec<Attraction>;
+    /// The `k` aerodromes nearest to a location, nearest first.
+    fn nearest_aerodromes(&self, lat: f64, lon: f64, k: usize) -> Vec<&Aerodrome>;
+    /// Aerodromes within `range` kilometers of a location, nearest first.
+    fn aerodromes_within(&self, lat: f64, lon: f64, range: f64) -> Vec<&Aerodrome>;
+    /// Weights the interest scores of the aerodromes with `interest_model`.
+    fn set_interest_model(&mut self, interest_model: InterestModel);
 }
 
+#[derive(Debug, Error)]
+pub enum WorldDataError {
+    #[error("Failed to read {}: {source}", .path.display())]
+    Io {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse {file}: {source}")]
+    Parse {
+        file: &'static str,
+        source: ParseError,
+    },
+}
+
+impl WorldDataError {
+    /// File, line and column the error was found at, if it is a parse error.
+    pub fn location(&self) -> Option<(&'static str, Option<u64>, Option<u64>)> {
+        match self {
+            WorldDataError::Io { .. } => None,
+            WorldDataError::Parse { file, source } => Some((file, source.line(), source.column())),
+        }
+    }
+}
+
+/// World data parsed from the contents of the data files.
 pub struct StringBasedWorldData {
-    airports_csv: String,
-    passengers_csv: String,
-    attractions_csv: String,
-    world_heritage_sites_csv: String,
-    planes_yaml: String,
+    files: WorldDataFiles,
+    mode: ParseMode,
+    skipped: Vec<WorldDataError>,
     aerodromes: Vec<Aerodrome>,
+    /// Positions of `aerodromes` by their index.
+    aerodrome_index: SpatialIndex<usize>,
     attractions: Vec<Attraction>,
     world_heritage_sites: Vec<WorldHeritageSite>,
     plane_types: Vec<PlaneType>,
+    interest_model: InterestModel,
 }
 
 impl StringBasedWorldData {
+    pub fn new(files: WorldDataFiles) -> Result<Self, WorldDataError> {
+        Self::with_mode(files, ParseMode::Strict)
+    }
+
+    /// Parses the files, skipping invalid rows in [`ParseMode::Lenient`].
+    pub fn with_mode(files: WorldDataFiles, mode: ParseMode) -> Result<Self, WorldDataError> {
+        let mut data = Self {
+            files,
+            mode,
+            skipped: Vec::new(),
+            aerodromes: Vec::new(),
+            aerodrome_index: SpatialIndex::new(Vec::new()),
+            attractions: Vec::new(),
+            world_heritage_sites: Vec::new(),
+            plane_types: Vec::new(),
+            interest_model: InterestModel::default(),
+        };
+        data.load()?;
+        Ok(data)
+    }
+
+    fn load(&mut self) -> Result<(), WorldDataError> {
+        let files = &self.files;
+        let mode = self.mode;
+        let mut skipped = Vec::new();
+
+        let mut aerodromes = parsed(
+            WorldDataFiles::AIRPORTS,
+            parse_airport_csv(&files.airports_csv, mode),
+            &mut skipped,
+        )?;
+        let passengers = parsed(
+            WorldDataFiles::PASSENGERS,
+            parse_passengers_csv(&files.passengers_csv, mode),
+            &mut skipped,
+        )?;
+        assign_passengers(&mut aerodromes, passengers);
+        let runways = parsed(
+            WorldDataFiles::RUNWAYS,
+            parse_runways_csv(&files.runways_csv, mode),
+            &mut skipped,
+        )?;
+        assign_runway_lengths(&mut aerodromes, runways);
+        let attractions = parsed(
+            WorldDataFiles::ATTRACTIONS,
+            parse_attractions_csv(&files.attractions_csv, mode),
+            &mut skipped,
+        )?;
+        let world_heritage_sites = parsed(
+            WorldDataFiles::WORLD_HERITAGE_SITES,
+            parse_world_heritage_site_csv(&files.world_heritage_sites_csv, mode),
+            &mut skipped,
+        )?;
+        let plane_types = parsed(
+            WorldDataFiles::PLANES,
+            parse_planes_yaml(&files.planes_yaml, mode),
+            &mut skipped,
+        )?;
+
+        self.aerodrome_index = SpatialIndex::new(
+            aerodromes
+                .iter()
+                .enumerate()
+                .map(|(index, aerodrome)| (aerodrome.lat, aerodrome.lon, index)),
+        );
+        self.aerodromes = aerodromes;
+        self.attractions = attractions;
+        self.world_heritage_sites = world_heritage_sites;
+        self.plane_types = plane_types;
+        self.skipped = skipped;
+        self.calculate_aerodromes_interest_scores();
+        Ok(())
+    }
+
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
+    /// Rows skipped by the last load in lenient mode.
+    pub fn skipped(&self) -> &[WorldDataError] {
+        &self.skipped
+    }
+
+    /// Scores every aerodrome by the points of interest within 250 km, per
+    /// category and weighted with the configured [`InterestModel`].
     pub fn calculate_aerodromes_interest_scores(&mut self) {
-        let heritage_sites: Vec<(f64, f64, f64)> = self
+        let mut points_of_interest: Vec<(PoiCategory, f64, f64)> = self
             .world_heritage_sites()
             .iter()
-            .map(|site| (site.lat, site.lon, 1.0f64))
+            .map(|site| (site.category, site.lat, site.lon))
             .collect();
+        points_of_interest.extend(
+            self.attractions()
+                .iter()
+                .map(|attraction| (attraction.category, attraction.lat, attraction.lon)),
+        );
+        let indices: Vec<(PoiCategory, SpatialIndex<f64>)> = PoiCategory::iter()
+            .map(|category| {
+                let index = SpatialIndex::new(
+                    points_of_interest
+                        .iter()
+                        .filter(|(poi_category, _, _)| *poi_category == category)
+                        .map(|&(_, lat, lon)| (lat, lon, 1.0f64)),
+                );
+                (category, index)
+            })
+            .collect();
+        let interest_model = self.interest_model;
 
-        let mut points_of_interest = self
-            .attractions()
-            .iter()
-            .map(|attraction| (attraction.lat, attraction.lon, 1.0f64))
-            .collect::<Vec<(f64, f64, f64)>>();
-        points_of_interest.extend(heritage_sites);
-
-        #[cfg(feature = "rayon")]
-        {
-            use rayon::prelude::*;
-            self.aerodromes.par_iter_mut().for_each(|aerodrome| {
-                aerodrome.interest_score = calculate_interest_score(
+        let score = |aerodrome: &mut Aerodrome| {
+            for (category, index) in &indices {
+                let category_score = calculate_interest_score_with_index(
                     aerodrome.lat,
                     aerodrome.lon,
-                    &interest_places,
+                    index,
                     250_000.0,
                 );
-            });
+                aerodrome.category_scores.set(*category, category_score);
+            }
+            aerodrome.interest_score = interest_model.weighted_score(&aerodrome.category_scores);
+        };
+
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            self.aerodromes.par_iter_mut().for_each(score);
         }
 
         #[cfg(not(feature = "rayon"))]
         {
-            self.aerodromes.iter_mut().for_each(|aerodrome| {
-                aerodrome.interest_score = calculate_interest_score(
-                    aerodrome.lat,
-                    aerodrome.lon,
-                    &points_of_interest,
-                    250_000.0,
-                );
-            });
+            self.aerodromes.iter_mut().for_each(score);
         }
     }
 }
 
+/// Returns the parsed items of `file` and adds its skipped rows to `skipped`.
+fn parsed<T>(
+    file: &'static str,
+    result: Result<Parsed<T>, ParseError>,
+    skipped: &mut Vec<WorldDataError>,
+) -> Result<Vec<T>, WorldDataError> {
+    let parsed = result.map_err(|source| WorldDataError::Parse { file, source })?;
+    skipped.extend(
+        parsed
+            .skipped
+            .into_iter()
+            .map(|source| WorldDataError::Parse { file, source }),
+    );
+    Ok(parsed.items)
+}
+
 impl Default for StringBasedWorldData {
     fn default() -> Self {
-        let mut data = Self {
-            airports_csv: String::from(include_str!("../../../assets/airports.dat")),
-            attractions_csv: String::from(include_str!("../../../assets/attractions.csv")),
-            passengers_csv: String::from(include_str!("../../../assets/passengers.csv")),
-            world_heritage_sites_csv: String::from(include_str!(
-                "../../../assets/whc-sites-2019.csv"
-            )),
-            planes_yaml: String::from(include_str!("../../../assets/planes.yaml")),
-            aerodromes: Vec::new(),
-            attractions: Vec::new(),
-            world_heritage_sites: Vec::new(),
-            plane_types: Vec::new(),
-        };
-        data.reload().unwrap();
-        data
+        Self::new(WorldDataFiles::embedded()).expect("Embedded world data is valid")
     }
 }
 
@@ -107,20 +250,42 @@ impl WorldDataGateway for StringBasedWorldData {
         &self.plane_types
     }
 
-    fn reload(&mut self) -> Result<(), String> {
-        self.aerodromes = load_airports(&self.airports_csv, &self.passengers_csv);
-        self.attractions = parse_attractions_csv(&self.attractions_csv);
-        self.world_heritage_sites = parse_world_heritage_site_csv(&self.world_heritage_sites_csv);
-        self.calculate_aerodromes_interest_scores();
-        let planes: PlanesConfig = serde_yaml::from_str(&self.planes_yaml).unwrap();
-        self.plane_types = planes.planes;
-        Ok(())
+    fn reload(&mut self) -> Result<(), WorldDataError> {
+        self.load()
+    }
+
+    fn nearest_aerodromes(&self, lat: f64, lon: f64, k: usize) -> Vec<&Aerodrome> {
+        self.aerodrome_index
+            .nearest(lat, lon, k)
+            .iter()
+            .map(|neighbour| &self.aerodromes[*neighbour.item])
+            .collect()
+    }
+
+    fn aerodromes_within(&self, lat: f64, lon: f64, range: f64) -> Vec<&Aerodrome> {
+        let mut neighbours = self.aerodrome_index.within_radius(lat, lon, range * 1000.0);
+        neighbours.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        neighbours
+            .iter()
+            .map(|neighbour| &self.aerodromes[*neighbour.item])
+            .collect()
+    }
+
+    fn set_interest_model(&mut self, interest_model: InterestModel) {
+        if self.interest_model == interest_model {
+            return;
+        }
+        self.interest_model = interest_model;
+        for aerodrome in &mut self.aerodromes {
+            aerodrome.interest_score = interest_model.weighted_score(&aerodrome.category_scores);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::CategoryScores;
 
     #[test]
     fn test_default_initialization() {
@@ -129,6 +294,60 @@ mod tests {
         assert_eq!(data.aerodromes.len(), 7698);
         assert!(data.attractions.len() > 4);
         assert_eq!(data.world_heritage_sites.len(), 1121);
-        assert_eq!(data.plane_types.len(), 3);
+        assert_eq!(data.plane_types.len(), 12);
+    }
+
+    #[test]
+    fn test_nearest_aerodromes() {
+        let data = StringBasedWorldData::default();
+        let frankfurt = Aerodrome::frankfurt();
+
+        let nearest = data.nearest_aerodromes(frankfurt.lat, frankfurt.lon, 3);
+        assert_eq!(nearest.len(), 3);
+        assert_eq!(nearest[0].code, "FRA/EDDF");
+
+        let within = data.aerodromes_within(frankfurt.lat, frankfurt.lon, 500.0);
+        assert_eq!(within[0].code, "FRA/EDDF");
+        assert!(within.iter().any(|aerodrome| aerodrome.code == "CDG/LFPG"));
+        assert!(!within.iter().any(|aerodrome| aerodrome.code == "LHR/EGLL"));
+    }
+
+    #[test]
+    fn test_category_scores() {
+        let data = StringBasedWorldData::default();
+        let find = |code: &str| {
+            data.aerodromes()
+                .iter()
+                .find(|aerodrome| aerodrome.code == code)
+                .unwrap()
+        };
+
+        let frankfurt = find("FRA/EDDF");
+        assert!(frankfurt.category_scores.business > 0.0);
+        assert!(frankfurt.category_scores.culture > 0.0);
+        assert_eq!(frankfurt.category_scores.beach, 0.0);
+        assert_eq!(
+            frankfurt.interest_score,
+            InterestModel::default().weighted_score(&frankfurt.category_scores)
+        );
+
+        let palma = find("PMI/LEPA");
+        assert!(palma.category_scores.beach > palma.category_scores.business);
+    }
+
+    #[test]
+    fn test_set_interest_model() {
+        let mut data = StringBasedWorldData::default();
+        let interest_model = InterestModel {
+            weights: CategoryScores::uniform(0.0),
+            ..Default::default()
+        };
+
+        data.set_interest_model(interest_model);
+
+        assert!(data
+            .aerodromes()
+            .iter()
+            .all(|aerodrome| aerodrome.interest_score == 0.0));
     }
 }
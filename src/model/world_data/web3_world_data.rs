@@ -29,7 +29,7 @@ impl Default for Web3WorldData {
 }
 
 impl Web3WorldData {
-    pub fn reload_attractions(&mut self) -> Result<(), String> {
+    pub fn reload_attractions(&mut self) -> Result<(), WorldDataError> {
         let attractions_rwlock = Arc::clone(&self.attractions);
 
         block_on(async move {
@@ -55,6 +55,7 @@ impl Web3WorldData {
                         description: attraction.description,
                         lat: attraction.lat,
                         lon: attraction.lon,
+                        category: Default::default(),
                     })
                     .collect();
 
@@ -91,9 +92,23 @@ impl WorldDataGateway for Web3WorldData {
         self.string_based_world_data.plane_types()
     }
 
-    fn reload(&mut self) -> Result<(), String> {
+    fn nearest_aerodromes(&self, lat: f64, lon: f64, k: usize) -> Vec<&Aerodrome> {
+        self.string_based_world_data.nearest_aerodromes(lat, lon, k)
+    }
+
+    fn aerodromes_within(&self, lat: f64, lon: f64, range: f64) -> Vec<&Aerodrome> {
+        self.string_based_world_data
+            .aerodromes_within(lat, lon, range)
+    }
+
+    fn reload(&mut self) -> Result<(), WorldDataError> {
         self.string_based_world_data.reload()?;
         self.reload_attractions()?;
         Ok(())
     }
+
+    fn set_interest_model(&mut self, interest_model: InterestModel) {
+        self.string_based_world_data
+            .set_interest_model(interest_model);
+    }
 }
@@ -0,0 +1,63 @@
+use std::path::Path;
+
+use super::WorldDataError;
+
+/// Contents of the files the world data is parsed from.
+#[derive(Debug, Clone)]
+pub struct WorldDataFiles {
+    pub airports_csv: String,
+    pub passengers_csv: String,
+    pub runways_csv: String,
+    pub attractions_csv: String,
+    pub world_heritage_sites_csv: String,
+    pub planes_yaml: String,
+}
+
+impl WorldDataFiles {
+    pub const AIRPORTS: &'static str = "airports.dat";
+    pub const PASSENGERS: &'static str = "passengers.csv";
+    pub const RUNWAYS: &'static str = "runways.csv";
+    pub const ATTRACTIONS: &'static str = "attractions.csv";
+    pub const WORLD_HERITAGE_SITES: &'static str = "whc-sites-2019.csv";
+    pub const PLANES: &'static str = "planes.yaml";
+
+    /// The files compiled into the game.
+    pub fn embedded() -> Self {
+        Self {
+            airports_csv: String::from(include_str!("../../../assets/airports.dat")),
+            passengers_csv: String::from(include_str!("../../../assets/passengers.csv")),
+            runways_csv: String::from(include_str!("../../../assets/runways.csv")),
+            attractions_csv: String::from(include_str!("../../../assets/attractions.csv")),
+            world_heritage_sites_csv: String::from(include_str!(
+                "../../../assets/whc-sites-2019.csv"
+            )),
+            planes_yaml: String::from(include_str!("../../../assets/planes.yaml")),
+        }
+    }
+
+    /// Reads the files from `directory`. Files missing in the directory are
+    /// taken from the embedded files, so a directory only needs to contain
+    /// the files that are changed.
+    pub fn read_from_directory(directory: &Path) -> Result<Self, WorldDataError> {
+        let embedded = Self::embedded();
+        let read = |file: &str, embedded: String| -> Result<String, WorldDataError> {
+            let path = directory.join(file);
+            if !path.exists() {
+                return Ok(embedded);
+            }
+            std::fs::read_to_string(&path).map_err(|source| WorldDataError::Io { path, source })
+        };
+
+        Ok(Self {
+            airports_csv: read(Self::AIRPORTS, embedded.airports_csv)?,
+            passengers_csv: read(Self::PASSENGERS, embedded.passengers_csv)?,
+            runways_csv: read(Self::RUNWAYS, embedded.runways_csv)?,
+            attractions_csv: read(Self::ATTRACTIONS, embedded.attractions_csv)?,
+            world_heritage_sites_csv: read(
+                Self::WORLD_HERITAGE_SITES,
+                embedded.world_heritage_sites_csv,
+            )?,
+            planes_yaml: read(Self::PLANES, embedded.planes_yaml)?,
+        })
+    }
+}
@@ -0,0 +1,48 @@
+use std::path::PathBuf;
+
+use crate::config::ParseMode;
+
+use super::{FileBasedWorldData, WorldDataError, WorldDataGateway};
+
+/// Where the world data and levels are loaded from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum WorldDataSource {
+    /// The data compiled into the game.
+    #[default]
+    Embedded,
+    /// A data directory laid out like `assets`, with levels in `levels`.
+    /// Invalid rows are skipped and logged.
+    Directory(PathBuf),
+}
+
+impl WorldDataSource {
+    pub fn load(&self) -> Result<Box<dyn WorldDataGateway>, WorldDataError> {
+        match self {
+            WorldDataSource::Embedded => {
+                #[cfg(not(feature = "web3"))]
+                {
+                    Ok(Box::new(super::StringBasedWorldData::default()))
+                }
+                #[cfg(feature = "web3")]
+                {
+                    Ok(Box::new(super::web3_world_data::Web3WorldData::default()))
+                }
+            }
+            WorldDataSource::Directory(directory) => {
+                let data = FileBasedWorldData::with_mode(directory.clone(), ParseMode::Lenient)?;
+                for skipped in data.skipped() {
+                    log::warn!("Skipped invalid row: {}", skipped);
+                }
+                Ok(Box::new(data))
+            }
+        }
+    }
+
+    /// Directory containing the level files, `None` for the embedded levels.
+    pub fn levels_directory(&self) -> Option<PathBuf> {
+        match self {
+            WorldDataSource::Embedded => None,
+            WorldDataSource::Directory(directory) => Some(directory.join("levels")),
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Environment, FlightState, Timestamp};
+
+/// Simulated time between two checkpoints.
+pub const CHECKPOINT_INTERVAL: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Summary of the simulation state at a timestamp, embedded into replays to
+/// detect when a replay no longer leads to the recorded game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub timestamp: Timestamp,
+    pub cash: f64,
+    /// Ids of the planes, sorted.
+    pub fleet: Vec<u64>,
+    pub bases: usize,
+    pub landing_rights: usize,
+    pub flights: usize,
+    /// Hash of the planes, bases, landing rights and flights.
+    pub hash: u64,
+}
+
+/// Field in which a checkpoint differs from the re-simulated state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub timestamp: Timestamp,
+    pub field: &'static str,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at {} is {} instead of {}",
+            self.field, self.timestamp, self.actual, self.expected
+        )
+    }
+}
+
+/// FNV-1a, which unlike the hasher of the standard library is stable across
+/// Rust versions and platforms.
+struct StateHasher(u64);
+
+impl StateHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, value: impl Into<u128>) {
+        for byte in value.into().to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+fn state_hash(environment: &Environment) -> u64 {
+    let mut hasher = StateHasher::new();
+    for plane in &environment.planes {
+        hasher.write(plane.id);
+        hasher.write(plane.base_id);
+        hasher.write(plane.plane_type.id);
+    }
+    for base in &environment.bases {
+        hasher.write(base.id);
+        hasher.write(base.aerodrome.id);
+    }
+    for landing_rights in &environment.landing_rights {
+        hasher.write(landing_rights.id);
+        hasher.write(landing_rights.aerodrome.id);
+    }
+    for flight in &environment.flights {
+        hasher.write(flight.flight_id);
+        hasher.write(flight.airplane.id);
+        hasher.write(flight.departure_time);
+        hasher.write(flight.arrival_time.unwrap_or_default());
+        let (state, stopover) = match flight.state {
+            FlightState::Scheduled => (0u8, 0),
+            FlightState::EnRoute {
+                next_stopover_index,
+            } => (1, next_stopover_index),
+            FlightState::Landed {
+                next_stopover_index,
+            } => (2, next_stopover_index),
+            FlightState::Finished => (3, 0),
+        };
+        hasher.write(state);
+        hasher.write(stopover as u64);
+    }
+    hasher.0
+}
+
+impl Checkpoint {
+    pub fn capture(environment: &Environment) -> Self {
+        let mut fleet: Vec<u64> = environment.planes.iter().map(|plane| plane.id).collect();
+        fleet.sort_unstable();
+        Self {
+            timestamp: environment.timestamp,
+            cash: environment.company_finances.cash(environment.timestamp),
+            fleet,
+            bases: environment.bases.len(),
+            landing_rights: environment.landing_rights.len(),
+            flights: environment.flights.len(),
+            hash: state_hash(environment),
+        }
+    }
+
+    /// Compares this recorded checkpoint with `actual` and returns the first
+    /// field that differs.
+    pub fn diverges_from(&self, actual: &Checkpoint) -> Option<Divergence> {
+        let divergence = |field, expected: &dyn std::fmt::Debug, actual: &dyn std::fmt::Debug| {
+            Some(Divergence {
+                timestamp: self.timestamp,
+                field,
+                expected: format!("{:?}", expected),
+                actual: format!("{:?}", actual),
+            })
+        };
+        if self.timestamp != actual.timestamp {
+            divergence("timestamp", &self.timestamp, &actual.timestamp)
+        } else if self.cash.to_bits() != actual.cash.to_bits() {
+            divergence("cash", &self.cash, &actual.cash)
+        } else if self.fleet != actual.fleet {
+            divergence("fleet", &self.fleet, &actual.fleet)
+        } else if self.bases != actual.bases {
+            divergence("bases", &self.bases, &actual.bases)
+        } else if self.landing_rights != actual.landing_rights {
+            divergence(
+                "landing rights",
+                &self.landing_rights,
+                &actual.landing_rights,
+            )
+        } else if self.flights != actual.flights {
+            divergence("flights", &self.flights, &actual.flights)
+        } else if self.hash != actual.hash {
+            divergence("hash", &self.hash, &actual.hash)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{AirPlane, Flight};
+
+    #[test]
+    fn test_capture() {
+        let mut environment = Environment::default();
+        environment.timestamp = 10;
+        environment.planes = vec![
+            AirPlane {
+                id: 2,
+                ..Default::default()
+            },
+            AirPlane {
+                id: 1,
+                ..Default::default()
+            },
+        ];
+
+        let checkpoint = Checkpoint::capture(&environment);
+
+        assert_eq!(checkpoint.timestamp, 10);
+        assert_eq!(checkpoint.cash, 1_000_000.0);
+        assert_eq!(checkpoint.fleet, vec![1, 2]);
+        assert_eq!(checkpoint.diverges_from(&checkpoint), None);
+    }
+
+    #[test]
+    fn test_diverges_from() {
+        let mut environment = Environment::default();
+        let recorded = Checkpoint::capture(&environment);
+
+        environment.flights.push(Flight::default());
+        let divergence = recorded
+            .diverges_from(&Checkpoint::capture(&environment))
+            .unwrap();
+        assert_eq!(divergence.field, "flights");
+        assert_eq!(divergence.to_string(), "flights at 0 is 1 instead of 0");
+
+        environment.flights[0].state = FlightState::Finished;
+        let mut recorded = Checkpoint::capture(&environment);
+        environment.flights[0].state = FlightState::Scheduled;
+        assert_eq!(
+            recorded
+                .diverges_from(&Checkpoint::capture(&environment))
+                .unwrap()
+                .field,
+            "hash"
+        );
+
+        recorded.cash += 1.0;
+        assert_eq!(
+            recorded
+                .diverges_from(&Checkpoint::capture(&environment))
+                .unwrap()
+                .field,
+            "cash"
+        );
+    }
+}
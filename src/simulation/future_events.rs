@@ -0,0 +1,90 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::model::Timestamp;
+
+/// Something that will change the simulation at a known point in time.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FutureEventKind {
+    Command,
+    Takeoff {
+        flight_id: u64,
+    },
+    Landing {
+        flight_id: u64,
+    },
+    /// Bucket boundary at which the net worth is recorded and older metrics
+    /// are compacted if enabled.
+    MetricsSnapshot,
+    /// Boundary at which a checkpoint of the state is recorded.
+    Checkpoint,
+}
+
+impl FutureEventKind {
+    /// Whether the event only records the state of the simulation without
+    /// changing it.
+    pub fn is_bookkeeping(&self) -> bool {
+        matches!(self, Self::MetricsSnapshot | Self::Checkpoint)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FutureEvent {
+    pub timestamp: Timestamp,
+    pub kind: FutureEventKind,
+}
+
+/// Priority queue of future events, yielding the earliest event first.
+#[derive(Debug, Default, Clone)]
+pub struct FutureEvents {
+    queue: BinaryHeap<Reverse<FutureEvent>>,
+}
+
+impl FutureEvents {
+    pub fn push(&mut self, timestamp: Timestamp, kind: FutureEventKind) {
+        self.queue.push(Reverse(FutureEvent { timestamp, kind }));
+    }
+
+    pub fn peek(&self) -> Option<&FutureEvent> {
+        self.queue.peek().map(|Reverse(event)| event)
+    }
+
+    pub fn pop(&mut self) -> Option<FutureEvent> {
+        self.queue.pop().map(|Reverse(event)| event)
+    }
+
+    /// Iterates over the events in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &FutureEvent> {
+        self.queue.iter().map(|Reverse(event)| event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yields_earliest_event_first() {
+        let mut events = FutureEvents::default();
+        events.push(3_000, FutureEventKind::Checkpoint);
+        events.push(1_000, FutureEventKind::Landing { flight_id: 1 });
+        events.push(2_000, FutureEventKind::Command);
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(events.peek().unwrap().timestamp, 1_000);
+        assert_eq!(
+            events.pop().unwrap().kind,
+            FutureEventKind::Landing { flight_id: 1 }
+        );
+        assert_eq!(events.pop().unwrap().kind, FutureEventKind::Command);
+        assert_eq!(events.pop().unwrap().kind, FutureEventKind::Checkpoint);
+        assert!(events.is_empty());
+    }
+}
@@ -0,0 +1,498 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    config::{LevelConfig, LevelError, LevelOutcome},
+    model::{
+        commands::TimestampedCommand, world_data::WorldDataSource, Environment, Timestamp,
+        WorldDataGateway,
+    },
+};
+
+use super::{
+    replay::{Replay, ReplayError},
+    Simulation,
+};
+
+/// Number of scores kept in the table.
+pub const MAX_HIGH_SCORES: usize = 10;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub const HIGH_SCORES_FILE: &str = "highscores.yaml";
+
+#[cfg(target_arch = "wasm32")]
+pub const HIGH_SCORES_STORAGE_KEY: &str = "flyconomy.highscores";
+
+/// Directory the replays of the scores are stored in, one file per replay
+/// named after its key.
+#[cfg(not(target_arch = "wasm32"))]
+pub const HIGH_SCORE_REPLAYS_DIR: &str = "highscore_replays";
+
+/// Prefix of the local storage keys of the replays of the scores.
+#[cfg(target_arch = "wasm32")]
+pub const HIGH_SCORE_REPLAYS_STORAGE_KEY: &str = "flyconomy.replays";
+
+/// Largest difference in net worth a verified replay may have, to allow for
+/// rounding.
+const NET_WORTH_TOLERANCE: f64 = 0.01;
+
+#[derive(Debug, Error)]
+pub enum HighScoreError {
+    #[error("Failed to access high scores: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to read high scores: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("Failed to access local storage: {0}")]
+    Storage(String),
+    #[error(transparent)]
+    Replay(#[from] ReplayError),
+    #[error(transparent)]
+    Level(#[from] LevelError),
+    #[error("Replay does not start with the configuration of the level")]
+    ConfigMismatch,
+    #[error("Replay does not start with the starting assets of the level")]
+    StartingAssetsMismatch,
+    #[error("Replay ends as won: {actual} instead of {expected}")]
+    WonMismatch { expected: bool, actual: bool },
+    #[error("Replay ends with a net worth of {actual:.2} instead of {expected:.2}")]
+    NetWorthMismatch { expected: f64, actual: f64 },
+    #[error("Replay transports {actual} passengers instead of {expected}")]
+    PassengersMismatch { expected: u32, actual: u32 },
+}
+
+/// Result of a finished run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScore {
+    pub alias: String,
+    pub level: String,
+    pub won: bool,
+    /// Simulated time of the run.
+    pub duration: Timestamp,
+    pub net_worth: f64,
+    pub passengers: u32,
+    /// Key the replay of the run is stored under, see
+    /// [`HighScores::save_replay`], to verify the score. Replays are stored
+    /// apart from the table to keep it small.
+    #[serde(default)]
+    pub replay_key: String,
+}
+
+impl HighScore {
+    pub fn new(
+        alias: String,
+        level: String,
+        won: bool,
+        simulation: &Simulation,
+        replay: &Replay,
+    ) -> Self {
+        let environment = &simulation.environment;
+        let mut hasher = DefaultHasher::new();
+        serde_yaml::to_string(replay)
+            .expect("Failed to serialize replay.")
+            .hash(&mut hasher);
+        Self {
+            alias,
+            level,
+            won,
+            duration: environment.timestamp,
+            net_worth: environment.net_worth(environment.timestamp),
+            passengers: environment
+                .metrics
+                .transported_passengers(environment.timestamp),
+            replay_key: format!("{:016x}", hasher.finish()),
+        }
+    }
+
+    /// Re-simulates the replay of the run for its duration and checks that it
+    /// starts like the level of the score, passes its checkpoints and results
+    /// in the recorded outcome, net worth and passengers.
+    pub fn verify(
+        &self,
+        replay: &Replay,
+        data_source: &WorldDataSource,
+        mut world_data_gateway: Box<dyn WorldDataGateway>,
+    ) -> Result<(), HighScoreError> {
+        let level_config = LevelConfig::load(&self.level, data_source)?;
+        if level_config.environment != replay.initial_config {
+            return Err(HighScoreError::ConfigMismatch);
+        }
+        // The starting assets take their interest scores from the world data.
+        world_data_gateway.set_interest_model(level_config.environment.interest);
+        let starting_assets: Vec<TimestampedCommand> = level_config
+            .starting_commands(world_data_gateway.as_ref())?
+            .into_iter()
+            .map(|command| TimestampedCommand::new(0, command))
+            .collect();
+        if serde_yaml::to_value(&starting_assets)? != serde_yaml::to_value(&replay.starting_assets)?
+        {
+            return Err(HighScoreError::StartingAssetsMismatch);
+        }
+
+        let mut simulation = replay.verify(world_data_gateway)?;
+        simulation.run_until(self.duration);
+
+        let environment: &Environment = &simulation.environment;
+        let won = level_config.evaluate(environment) == LevelOutcome::Won;
+        if won != self.won {
+            return Err(HighScoreError::WonMismatch {
+                expected: self.won,
+                actual: won,
+            });
+        }
+        let net_worth = environment.net_worth(environment.timestamp);
+        if (net_worth - self.net_worth).abs() > NET_WORTH_TOLERANCE {
+            return Err(HighScoreError::NetWorthMismatch {
+                expected: self.net_worth,
+                actual: net_worth,
+            });
+        }
+        let passengers = environment
+            .metrics
+            .transported_passengers(environment.timestamp);
+        if passengers != self.passengers {
+            return Err(HighScoreError::PassengersMismatch {
+                expected: self.passengers,
+                actual: passengers,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// The best runs by net worth, best first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    pub scores: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Adds a score and returns its rank, or `None` if it did not make it into
+    /// the table.
+    pub fn add(&mut self, score: HighScore) -> Option<usize> {
+        let rank = self
+            .scores
+            .partition_point(|other| other.net_worth >= score.net_worth);
+        if rank >= MAX_HIGH_SCORES {
+            return None;
+        }
+        self.scores.insert(rank, score);
+        self.scores.truncate(MAX_HIGH_SCORES);
+        Some(rank)
+    }
+
+    pub fn for_level<'a>(&'a self, level: &'a str) -> impl Iterator<Item = &'a HighScore> {
+        self.scores.iter().filter(move |score| score.level == level)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, HighScoreError> {
+        Ok(serde_yaml::from_str(yaml)?)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, HighScoreError> {
+        Ok(serde_yaml::to_string(self)?)
+    }
+
+    /// Loads the stored high scores, or an empty table if there are none.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load() -> Result<Self, HighScoreError> {
+        match std::fs::read_to_string(HIGH_SCORES_FILE) {
+            Ok(yaml) => Self::from_yaml(&yaml),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Stores the table and removes the replays of scores no longer in it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self) -> Result<(), HighScoreError> {
+        std::fs::write(HIGH_SCORES_FILE, self.to_yaml()?)?;
+        self.remove_unused_replays()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn replay_path(key: &str) -> std::path::PathBuf {
+        std::path::Path::new(HIGH_SCORE_REPLAYS_DIR).join(format!("{}.yaml", key))
+    }
+
+    /// Stores the replay of a score under its key.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_replay(score: &HighScore, replay: &Replay) -> Result<(), HighScoreError> {
+        std::fs::create_dir_all(HIGH_SCORE_REPLAYS_DIR)?;
+        std::fs::write(
+            Self::replay_path(&score.replay_key),
+            serde_yaml::to_string(replay)?,
+        )?;
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_replay(score: &HighScore) -> Result<Replay, HighScoreError> {
+        let yaml = std::fs::read_to_string(Self::replay_path(&score.replay_key))?;
+        Ok(serde_yaml::from_str(&yaml)?)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn remove_unused_replays(&self) -> Result<(), HighScoreError> {
+        let entries = match std::fs::read_dir(HIGH_SCORE_REPLAYS_DIR) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error.into()),
+        };
+        for entry in entries {
+            let path = entry?.path();
+            let is_used = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|key| self.is_used_replay(key));
+            if !is_used {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_used_replay(&self, key: &str) -> bool {
+        self.scores.iter().any(|score| score.replay_key == key)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn local_storage() -> Result<web_sys::Storage, HighScoreError> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| HighScoreError::Storage("not available".to_string()))
+    }
+
+    /// Loads the stored high scores, or an empty table if there are none.
+    #[cfg(target_arch = "wasm32")]
+    pub fn load() -> Result<Self, HighScoreError> {
+        let yaml = Self::local_storage()?
+            .get_item(HIGH_SCORES_STORAGE_KEY)
+            .map_err(|error| HighScoreError::Storage(format!("{:?}", error)))?;
+        match yaml {
+            Some(yaml) => Self::from_yaml(&yaml),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Stores the table and removes the replays of scores no longer in it.
+    #[cfg(target_arch = "wasm32")]
+    pub fn save(&self) -> Result<(), HighScoreError> {
+        Self::local_storage()?
+            .set_item(HIGH_SCORES_STORAGE_KEY, &self.to_yaml()?)
+            .map_err(|error| HighScoreError::Storage(format!("{:?}", error)))?;
+        self.remove_unused_replays()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn replay_storage_key(key: &str) -> String {
+        format!("{}.{}", HIGH_SCORE_REPLAYS_STORAGE_KEY, key)
+    }
+
+    /// Stores the replay of a score under its key.
+    #[cfg(target_arch = "wasm32")]
+    pub fn save_replay(score: &HighScore, replay: &Replay) -> Result<(), HighScoreError> {
+        Self::local_storage()?
+            .set_item(
+                &Self::replay_storage_key(&score.replay_key),
+                &serde_yaml::to_string(replay)?,
+            )
+            .map_err(|error| HighScoreError::Storage(format!("{:?}", error)))
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn load_replay(score: &HighScore) -> Result<Replay, HighScoreError> {
+        let yaml = Self::local_storage()?
+            .get_item(&Self::replay_storage_key(&score.replay_key))
+            .map_err(|error| HighScoreError::Storage(format!("{:?}", error)))?
+            .ok_or_else(|| {
+                HighScoreError::Storage(format!("replay {} not found", score.replay_key))
+            })?;
+        Ok(serde_yaml::from_str(&yaml)?)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn remove_unused_replays(&self) -> Result<(), HighScoreError> {
+        let storage = Self::local_storage()?;
+        let prefix = Self::replay_storage_key("");
+        let length = storage
+            .length()
+            .map_err(|error| HighScoreError::Storage(format!("{:?}", error)))?;
+        let unused: Vec<String> = (0..length)
+            .filter_map(|index| storage.key(index).ok().flatten())
+            .filter(|key| {
+                key.strip_prefix(&prefix)
+                    .is_some_and(|key| !self.is_used_replay(key))
+            })
+            .collect();
+        for key in unused {
+            storage
+                .remove_item(&key)
+                .map_err(|error| HighScoreError::Storage(format!("{:?}", error)))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        commands::BuyLandingRightsCommand, Aerodrome, EnvironmentConfig, StringBasedWorldData,
+    };
+
+    fn score(net_worth: f64) -> HighScore {
+        HighScore {
+            alias: "Pilot".to_string(),
+            level: "level1".to_string(),
+            won: false,
+            duration: 0,
+            net_worth,
+            passengers: 0,
+            replay_key: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_add_keeps_best_scores() {
+        let mut high_scores = HighScores::default();
+        for net_worth in 0..MAX_HIGH_SCORES {
+            assert!(high_scores.add(score(net_worth as f64)).is_some());
+        }
+
+        assert_eq!(high_scores.add(score(-1.0)), None);
+        assert_eq!(high_scores.add(score(4.5)), Some(5));
+        assert_eq!(high_scores.scores.len(), MAX_HIGH_SCORES);
+        assert_eq!(high_scores.scores[0].net_worth, 9.0);
+        assert_eq!(high_scores.scores[MAX_HIGH_SCORES - 1].net_worth, 1.0);
+        assert_eq!(high_scores.for_level("level1").count(), MAX_HIGH_SCORES);
+        assert_eq!(high_scores.for_level("level2").count(), 0);
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let mut high_scores = HighScores::default();
+        high_scores.add(score(1_000.0));
+
+        let loaded = HighScores::from_yaml(&high_scores.to_yaml().unwrap()).unwrap();
+
+        assert_eq!(loaded.scores.len(), 1);
+        assert_eq!(loaded.scores[0].alias, "Pilot");
+        assert_eq!(loaded.scores[0].net_worth, 1_000.0);
+    }
+
+    #[test]
+    fn test_yaml_keeps_replays_out_of_the_table() {
+        let (score, _) = level_score("level1");
+        let mut high_scores = HighScores::default();
+        high_scores.add(score.clone());
+
+        let yaml = high_scores.to_yaml().unwrap();
+
+        assert!(!score.replay_key.is_empty());
+        assert!(yaml.contains(&score.replay_key));
+        assert!(!yaml.contains("command_history"));
+        let (other_score, _) = level_score("level2");
+        assert_ne!(other_score.replay_key, score.replay_key);
+    }
+
+    fn level_score(level: &str) -> (HighScore, Replay) {
+        let mut simulation = LevelConfig::load(level, &WorldDataSource::Embedded)
+            .unwrap()
+            .create_simulation(Box::new(StringBasedWorldData::default()))
+            .unwrap();
+        simulation.add_command(Box::new(BuyLandingRightsCommand {
+            landing_rights_id: BuyLandingRightsCommand::generate_id(),
+            aerodrome: Aerodrome::paris(),
+        }));
+        simulation.run_until(60 * 60 * 1000);
+
+        let replay = Replay::from_simulation(&simulation);
+        let score = HighScore::new(
+            "Pilot".to_string(),
+            level.to_string(),
+            false,
+            &simulation,
+            &replay,
+        );
+        (score, replay)
+    }
+
+    fn verify(score: &HighScore, replay: &Replay) -> Result<(), HighScoreError> {
+        score.verify(
+            replay,
+            &WorldDataSource::Embedded,
+            Box::new(StringBasedWorldData::default()),
+        )
+    }
+
+    #[test]
+    fn test_verify() {
+        let (mut score, mut replay) = level_score("level1");
+        assert!(verify(&score, &replay).is_ok());
+
+        replay.checkpoints[0].fleet.push(1);
+        assert!(matches!(
+            verify(&score, &replay),
+            Err(HighScoreError::Replay(_))
+        ));
+
+        replay.checkpoints[0].fleet.clear();
+        score.net_worth += 1_000.0;
+        assert!(matches!(
+            verify(&score, &replay),
+            Err(HighScoreError::NetWorthMismatch { .. })
+        ));
+
+        score.net_worth -= 1_000.0;
+        score.won = true;
+        assert!(matches!(
+            verify(&score, &replay),
+            Err(HighScoreError::WonMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_inflated_start_capital() {
+        let mut simulation = Simulation::new(
+            EnvironmentConfig {
+                start_capital: 100_000_000.0,
+                ..LevelConfig::load("level1", &WorldDataSource::Embedded)
+                    .unwrap()
+                    .environment
+            },
+            Box::new(StringBasedWorldData::default()),
+        );
+        simulation.run_until(60 * 60 * 1000);
+        let replay = Replay::from_simulation(&simulation);
+        let score = HighScore::new(
+            "Pilot".to_string(),
+            "level1".to_string(),
+            false,
+            &simulation,
+            &replay,
+        );
+
+        assert!(matches!(
+            verify(&score, &replay),
+            Err(HighScoreError::ConfigMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_verify_checks_starting_assets() {
+        let (score, mut replay) = level_score("level2");
+        assert!(!replay.starting_assets.is_empty());
+        assert!(verify(&score, &replay).is_ok());
+
+        replay.starting_assets.pop();
+        assert!(matches!(
+            verify(&score, &replay),
+            Err(HighScoreError::StartingAssetsMismatch)
+        ));
+    }
+}
@@ -0,0 +1,400 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufWriter, Write},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::model::{
+    events::{SimulationEvent, TimestampedEvent},
+    Environment, Flight, Timestamp,
+};
+
+/// Number of entries kept for the UI.
+pub const RECENT_ENTRIES_CAPACITY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum JournalEntryKind {
+    AirplaneLanded,
+    AirplaneTakeoff,
+    BuyPlane,
+    SellPlane,
+    CreateBase,
+    BuyLandingRights,
+    SellLandingRights,
+    ScheduleFlight,
+    RemoveBase,
+    CancelFlight,
+    Error,
+}
+
+/// A structured record of something that happened in the simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: Timestamp,
+    pub kind: JournalEntryKind,
+    pub message: String,
+    pub aerodrome_ids: Vec<u64>,
+    pub base_id: Option<u64>,
+    pub plane_id: Option<u64>,
+    pub flight_id: Option<u64>,
+    /// Change of cash caused by the entry, negative for expenses.
+    pub amount: Option<f64>,
+}
+
+impl JournalEntry {
+    fn new(timestamp: Timestamp, kind: JournalEntryKind, message: String) -> Self {
+        Self {
+            timestamp,
+            kind,
+            message,
+            aerodrome_ids: vec![],
+            base_id: None,
+            plane_id: None,
+            flight_id: None,
+            amount: None,
+        }
+    }
+
+    pub fn error(timestamp: Timestamp, message: String) -> Self {
+        Self::new(timestamp, JournalEntryKind::Error, message)
+    }
+
+    /// Creates an entry for an event, looking up the involved entities and
+    /// amounts in the environment the event happened in.
+    pub fn from_event(timestamped_event: &TimestampedEvent, environment: &Environment) -> Self {
+        let event = &timestamped_event.event;
+        let message = event.message();
+        let timestamp = timestamped_event.timestamp;
+        let base_aerodrome_ids = |base_id: u64| -> Vec<u64> {
+            environment
+                .bases
+                .iter()
+                .filter(|base| base.id == base_id)
+                .map(|base| base.aerodrome.id)
+                .collect()
+        };
+
+        match event {
+            SimulationEvent::AirplaneLanded(event) => Self {
+                amount: Some(event.flight.calculate_profit()),
+                ..Self::for_flight(
+                    timestamp,
+                    JournalEntryKind::AirplaneLanded,
+                    message,
+                    &event.flight,
+                )
+            },
+            SimulationEvent::AirplaneTakeoff(event) => {
+                let fuel_cost =
+                    environment.config.fuel_cost_per_km * event.flight.calculate_total_distance();
+                Self {
+                    amount: Some(-(environment.config.takeoff_cost + fuel_cost)),
+                    ..Self::for_flight(
+                        timestamp,
+                        JournalEntryKind::AirplaneTakeoff,
+                        message,
+                        &event.flight,
+                    )
+                }
+            }
+            SimulationEvent::ScheduleFlight(event) => Self {
+                amount: Some(event.flight.calculate_profit()),
+                ..Self::for_flight(
+                    timestamp,
+                    JournalEntryKind::ScheduleFlight,
+                    message,
+                    &event.flight,
+                )
+            },
+            SimulationEvent::BuyPlane(event) => Self {
+                aerodrome_ids: base_aerodrome_ids(event.home_base_id),
+                base_id: Some(event.home_base_id),
+                plane_id: Some(event.plane_id),
+                amount: Some(-(event.plane_type.cost as f64)),
+                ..Self::new(timestamp, JournalEntryKind::BuyPlane, message)
+            },
+            SimulationEvent::SellPlane(event) => Self {
+                aerodrome_ids: base_aerodrome_ids(event.home_base_id),
+                base_id: Some(event.home_base_id),
+                plane_id: Some(event.plane_id),
+                amount: Some(event.refund),
+                ..Self::new(timestamp, JournalEntryKind::SellPlane, message)
+            },
+            SimulationEvent::CreateBase(event) => Self {
+                aerodrome_ids: vec![event.aerodrome.id],
+                base_id: Some(event.base_id),
+                amount: Some(-event.cost),
+                ..Self::new(timestamp, JournalEntryKind::CreateBase, message)
+            },
+            SimulationEvent::BuyLandingRights(event) => Self {
+                aerodrome_ids: vec![event.aerodrome.id],
+                amount: Some(-environment.config.landing_rights_cost),
+                ..Self::new(timestamp, JournalEntryKind::BuyLandingRights, message)
+            },
+            SimulationEvent::SellLandingRights(event) => Self {
+                aerodrome_ids: vec![event.aerodrome.id],
+                amount: Some(event.refund),
+                ..Self::new(timestamp, JournalEntryKind::SellLandingRights, message)
+            },
+            SimulationEvent::RemoveBase(event) => Self {
+                aerodrome_ids: vec![event.aerodrome.id],
+                base_id: Some(event.base_id),
+                amount: Some(event.refund),
+                ..Self::new(timestamp, JournalEntryKind::RemoveBase, message)
+            },
+            SimulationEvent::CancelFlight(event) => Self {
+                amount: Some(-event.flight.calculate_profit()),
+                ..Self::for_flight(
+                    timestamp,
+                    JournalEntryKind::CancelFlight,
+                    message,
+                    &event.flight,
+                )
+            },
+        }
+    }
+
+    fn for_flight(
+        timestamp: Timestamp,
+        kind: JournalEntryKind,
+        message: String,
+        flight: &Flight,
+    ) -> Self {
+        let mut aerodrome_ids = vec![flight.origin_aerodrome.id];
+        aerodrome_ids.extend(flight.stopovers.iter().map(|aerodrome| aerodrome.id));
+        Self {
+            aerodrome_ids,
+            base_id: Some(flight.airplane.base_id),
+            plane_id: Some(flight.airplane.id),
+            flight_id: Some(flight.flight_id),
+            ..Self::new(timestamp, kind, message)
+        }
+    }
+}
+
+/// Selects journal entries. Unset criteria match every entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JournalFilter {
+    pub kinds: Vec<JournalEntryKind>,
+    pub aerodrome_id: Option<u64>,
+    pub plane_id: Option<u64>,
+    pub from: Option<Timestamp>,
+    pub until: Option<Timestamp>,
+}
+
+impl JournalFilter {
+    pub fn with_kind(mut self, kind: JournalEntryKind) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+
+    pub fn with_aerodrome(mut self, aerodrome_id: u64) -> Self {
+        self.aerodrome_id = Some(aerodrome_id);
+        self
+    }
+
+    pub fn with_plane(mut self, plane_id: u64) -> Self {
+        self.plane_id = Some(plane_id);
+        self
+    }
+
+    /// Restricts the entries to the time range `from..=until`.
+    pub fn between(mut self, from: Timestamp, until: Timestamp) -> Self {
+        self.from = Some(from);
+        self.until = Some(until);
+        self
+    }
+
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        (self.kinds.is_empty() || self.kinds.contains(&entry.kind))
+            && self
+                .aerodrome_id
+                .map_or(true, |id| entry.aerodrome_ids.contains(&id))
+            && self.plane_id.map_or(true, |id| entry.plane_id == Some(id))
+            && self.from.map_or(true, |from| entry.timestamp >= from)
+            && self.until.map_or(true, |until| entry.timestamp <= until)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error("Failed to write journal: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to serialize journal entry: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Log of everything that happened in the simulation.
+///
+/// All entries are kept for querying and export, while the most recent ones
+/// are additionally held in a bounded ring buffer for display.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    entries: Vec<JournalEntry>,
+    recent: VecDeque<JournalEntry>,
+    recent_capacity: usize,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self::with_recent_capacity(RECENT_ENTRIES_CAPACITY)
+    }
+}
+
+impl Journal {
+    pub fn with_recent_capacity(recent_capacity: usize) -> Self {
+        Self {
+            entries: vec![],
+            recent: VecDeque::with_capacity(recent_capacity),
+            recent_capacity,
+        }
+    }
+
+    pub fn record(&mut self, entry: JournalEntry) {
+        if self.recent_capacity > 0 {
+            if self.recent.len() == self.recent_capacity {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(entry.clone());
+        }
+        self.entries.push(entry);
+    }
+
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// The most recent entries, oldest first.
+    pub fn recent(&self) -> impl DoubleEndedIterator<Item = &JournalEntry> {
+        self.recent.iter()
+    }
+
+    pub fn query<'a>(
+        &'a self,
+        filter: &'a JournalFilter,
+    ) -> impl Iterator<Item = &'a JournalEntry> + 'a {
+        self.entries.iter().filter(|entry| filter.matches(entry))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recent.clear();
+    }
+
+    /// Writes the entries matching `filter` as JSON Lines, one entry per line.
+    pub fn write_json_lines<W: Write>(
+        &self,
+        mut writer: W,
+        filter: &JournalFilter,
+    ) -> Result<(), JournalError> {
+        for entry in self.query(filter) {
+            serde_json::to_writer(&mut writer, entry)?;
+            writer.write_all(b"\n")?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn to_json_lines(&self, filter: &JournalFilter) -> Result<String, JournalError> {
+        let mut buffer = vec![];
+        self.write_json_lines(&mut buffer, filter)?;
+        Ok(String::from_utf8(buffer).expect("JSON is valid UTF-8"))
+    }
+
+    pub fn save_to_file(&self, filename: &str) -> Result<(), JournalError> {
+        let file = File::create(filename)?;
+        self.write_json_lines(BufWriter::new(file), &JournalFilter::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        events::{BuyLandingRightsEvent, CreateBaseEvent},
+        Aerodrome,
+    };
+
+    use super::*;
+
+    fn journal() -> Journal {
+        let environment = Environment::default();
+        let mut journal = Journal::with_recent_capacity(2);
+        journal.record(JournalEntry::from_event(
+            &TimestampedEvent::new(
+                1_000,
+                CreateBaseEvent {
+                    base_id: 1,
+                    aerodrome: Aerodrome::frankfurt(),
+                    cost: 400_000.0,
+                },
+            ),
+            &environment,
+        ));
+        journal.record(JournalEntry::from_event(
+            &TimestampedEvent::new(
+                2_000,
+                BuyLandingRightsEvent {
+                    landing_rights_id: 1,
+                    aerodrome: Aerodrome::paris(),
+                },
+            ),
+            &environment,
+        ));
+        journal.record(JournalEntry::error(3_000, "Insufficient funds".to_string()));
+        journal
+    }
+
+    #[test]
+    fn test_recent_entries_are_bounded() {
+        let journal = journal();
+
+        assert_eq!(journal.len(), 3);
+        let recent: Vec<_> = journal.recent().map(|entry| entry.timestamp).collect();
+        assert_eq!(recent, vec![2_000, 3_000]);
+    }
+
+    #[test]
+    fn test_query() {
+        let journal = journal();
+
+        let filter = JournalFilter::default().with_kind(JournalEntryKind::CreateBase);
+        let entries: Vec<_> = journal.query(&filter).collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].amount, Some(-400_000.0));
+        assert_eq!(entries[0].base_id, Some(1));
+
+        let filter = JournalFilter::default().with_aerodrome(Aerodrome::paris().id);
+        assert_eq!(journal.query(&filter).count(), 1);
+
+        let filter = JournalFilter::default().between(1_500, 3_000);
+        assert_eq!(journal.query(&filter).count(), 2);
+
+        let filter = JournalFilter::default().with_plane(1);
+        assert_eq!(journal.query(&filter).count(), 0);
+    }
+
+    #[test]
+    fn test_json_lines_export() {
+        let journal = journal();
+
+        let json_lines = journal.to_json_lines(&JournalFilter::default()).unwrap();
+        let entries: Vec<JournalEntry> = json_lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(entries, journal.entries());
+        assert_eq!(entries[2].kind, JournalEntryKind::Error);
+    }
+}
@@ -4,32 +4,71 @@ use std::{
 };
 
 use crate::model::{
-    commands::{Command, TimestampedCommand},
+    commands::{Command, CommandError, TimestampedCommand},
     events::{
-        AirplaneLandedEvent, AirplaneLandedEventHandler, AirplaneTakeoffEvent,
-        AirplaneTakeoffEventHandler, BuyLandingRightsEvent, BuyPlaneEvent, CreateBaseEvent,
-        EventManager,
+        handle_airplane_landed, handle_airplane_takeoff, AirplaneLandedEvent, AirplaneTakeoffEvent,
+        EventBus,
     },
     Environment, EnvironmentConfig, FlightState, Timestamp, WorldDataGateway,
 };
 
+use self::{
+    checkpoint::{Checkpoint, CHECKPOINT_INTERVAL},
+    future_events::{FutureEvent, FutureEventKind, FutureEvents},
+    journal::{Journal, JournalEntry},
+    undo::{HistoryOperation, UndoEntry, UndoError},
+};
+
+pub mod checkpoint;
+pub mod future_events;
+pub mod high_scores;
+pub mod journal;
 pub mod replay;
+pub mod replay_player;
+pub mod undo;
 
 #[cfg(test)]
 mod tests;
 
 pub const DEFAULT_TIME_MULTIPLIER: f64 = 1.0 * 5.0 * 60.0; // 1 second = 5 minutes
 
+/// Simulated time advanced by a single tick, independent of the frame rate.
+pub const TICK_DURATION: Duration = Duration::from_secs(60);
+
+/// A simulated day.
+pub const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Metrics older than this are merged into buckets of [`METRICS_BUCKET_WIDTH`]
+/// if [`EnvironmentConfig::compact_history`] is set. The net worth is recorded
+/// once per bucket either way.
+pub const METRICS_RETENTION: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub const METRICS_BUCKET_WIDTH: Duration = Duration::from_secs(60 * 60);
+
+/// Simulated time after its execution in which a command can be undone.
+pub const UNDO_GRACE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
 pub struct Simulation {
     pub environment: Environment,
     pub world_data_gateway: Box<dyn WorldDataGateway>,
     pub elapsed_time: Duration,
+    /// Simulated time that has been accumulated but not yet consumed by a tick.
+    pub accumulated_time: Duration,
     pub commands: Vec<TimestampedCommand>,
     pub time_multiplier: f64,
-    pub error_messages: Vec<(Timestamp, String)>,
-    pub event_messages: Vec<(Timestamp, String)>,
-    pub event_manager: EventManager,
+    pub journal: Journal,
+    pub event_bus: EventBus,
     pub command_history: Vec<TimestampedCommand>,
+    /// Commands the company got its assets by before the game started, see
+    /// [`Simulation::add_starting_assets`].
+    pub starting_assets: Vec<TimestampedCommand>,
+    /// Executed commands that can still be undone, most recent last.
+    pub undo_stack: Vec<UndoEntry>,
+    /// Undone commands that can be redone, most recently undone last.
+    pub redo_stack: Vec<UndoEntry>,
+    /// States recorded every [`CHECKPOINT_INTERVAL`], embedded into replays.
+    pub checkpoints: Vec<Checkpoint>,
+    history_operations: Vec<HistoryOperation>,
 }
 
 impl Default for Simulation {
@@ -54,77 +93,256 @@ impl std::fmt::Debug for Simulation {
 }
 
 impl Simulation {
-    pub fn new(config: EnvironmentConfig, world_data_gateway: Box<dyn WorldDataGateway>) -> Self {
+    pub fn new(
+        config: EnvironmentConfig,
+        mut world_data_gateway: Box<dyn WorldDataGateway>,
+    ) -> Self {
+        world_data_gateway.set_interest_model(config.interest);
         let mut simulation = Self {
             environment: Environment::new(config),
             world_data_gateway,
             elapsed_time: Duration::from_secs(0),
+            accumulated_time: Duration::from_secs(0),
             commands: vec![],
             time_multiplier: DEFAULT_TIME_MULTIPLIER, // 1 second = 5 minutes
-            error_messages: vec![],
-            event_messages: vec![],
-            event_manager: EventManager::default(),
+            journal: Journal::default(),
+            event_bus: EventBus::default(),
             command_history: vec![],
+            starting_assets: vec![],
+            undo_stack: vec![],
+            redo_stack: vec![],
+            checkpoints: vec![],
+            history_operations: vec![],
         };
         simulation.setup();
         simulation
     }
 
     pub fn setup(&mut self) {
-        let airplane_landed_event_handler = Box::new(AirplaneLandedEventHandler {});
-        self.event_manager
-            .add_event_handler(airplane_landed_event_handler);
-        let takeoff_event_handler = Box::new(AirplaneTakeoffEventHandler {});
-        self.event_manager.add_event_handler(takeoff_event_handler);
+        self.event_bus.subscribe(handle_airplane_landed);
+        self.event_bus.subscribe(handle_airplane_takeoff);
     }
 
+    /// Advances the simulation by a frame of `delta_time` real time.
+    ///
+    /// The scaled frame time is accumulated and consumed in fixed ticks of
+    /// [`TICK_DURATION`], so the results only depend on the simulated time and
+    /// not on how it was split into frames.
     pub fn update(&mut self, delta_time: Duration) {
-        let effective_delta_time =
+        self.accumulated_time +=
             Duration::from_secs_f64(delta_time.as_secs_f64() * self.time_multiplier);
-        self.elapsed_time += effective_delta_time;
-        self.environment.timestamp += effective_delta_time.as_millis();
 
+        while self.accumulated_time >= TICK_DURATION {
+            self.accumulated_time -= TICK_DURATION;
+            self.tick();
+        }
+    }
+
+    /// Ticks the simulation until its timestamp reaches `timestamp`.
+    ///
+    /// The timestamp only advances in steps of [`TICK_DURATION`], so it may end
+    /// up past `timestamp` by less than one tick.
+    pub fn run_until(&mut self, timestamp: Timestamp) {
+        while self.environment.timestamp < timestamp {
+            self.tick();
+        }
+    }
+
+    /// Advances the simulation by exactly one [`TICK_DURATION`].
+    ///
+    /// Within a tick, requested undos and redos are applied first, then due
+    /// commands are executed in the order of their timestamps (and insertion
+    /// order for equal timestamps), then flights are updated and finally the
+    /// emitted events are handled in emission order.
+    pub fn tick(&mut self) {
+        self.elapsed_time += TICK_DURATION;
+        self.environment.timestamp += TICK_DURATION.as_millis();
+
+        self.apply_history_operations();
+        self.execute_due_commands();
+        self.book_recurring_charges();
+        self.update_flights();
+        self.handle_events();
+        self.compact_metrics();
+        self.record_checkpoint();
+    }
+
+    /// Collects all events that are known to happen in the future.
+    pub fn future_events(&self) -> FutureEvents {
+        let mut future_events = FutureEvents::default();
+
+        for command in &self.commands {
+            future_events.push(command.timestamp, FutureEventKind::Command);
+        }
+        if !self.history_operations.is_empty() {
+            future_events.push(self.environment.timestamp + 1, FutureEventKind::Command);
+        }
+
+        for flight in &self.environment.flights {
+            let Some(timestamp) = flight.next_transition_time() else {
+                continue;
+            };
+            let flight_id = flight.flight_id;
+            let kind = match flight.state {
+                FlightState::EnRoute { .. } => FutureEventKind::Landing { flight_id },
+                _ => FutureEventKind::Takeoff { flight_id },
+            };
+            future_events.push(timestamp, kind);
+        }
+
+        future_events.push(
+            self.next_boundary(METRICS_BUCKET_WIDTH.as_millis()),
+            FutureEventKind::MetricsSnapshot,
+        );
+        future_events.push(
+            self.next_boundary(CHECKPOINT_INTERVAL),
+            FutureEventKind::Checkpoint,
+        );
+
+        future_events
+    }
+
+    /// First multiple of `interval` after the current timestamp.
+    fn next_boundary(&self, interval: Timestamp) -> Timestamp {
+        (self.environment.timestamp / interval + 1) * interval
+    }
+
+    /// Jumps directly to the tick in which the next future event happens and
+    /// executes that tick.
+    ///
+    /// The ticks at bookkeeping events on the way, like the hourly metrics
+    /// snapshots and the daily checkpoints, are executed as well and
+    /// everything else is skipped, so the result is the same as ticking
+    /// through them one by one. Returns the event that was reached, or `None`
+    /// without advancing if only bookkeeping is going to happen.
+    pub fn advance_to_next_event(&mut self) -> Option<FutureEvent> {
+        let event = self
+            .future_events()
+            .iter()
+            .filter(|event| !event.kind.is_bookkeeping())
+            .min()
+            .cloned()?;
+
+        loop {
+            let next = self
+                .future_events()
+                .peek()
+                .map_or(event.timestamp, |next| next.timestamp.min(event.timestamp));
+            self.skip_to(next);
+            if self.environment.timestamp >= event.timestamp {
+                break;
+            }
+        }
+
+        Some(event)
+    }
+
+    /// Skips the ticks before the one in which `timestamp` is reached and
+    /// executes that tick.
+    ///
+    /// Nothing but the recurring plane income happens in the skipped ticks, so
+    /// it is booked for each of them as if they were ticked.
+    fn skip_to(&mut self, timestamp: Timestamp) {
+        let tick = TICK_DURATION.as_millis();
+        let remaining = timestamp.saturating_sub(self.environment.timestamp);
+        let skipped_ticks = remaining.div_ceil(tick).saturating_sub(1);
+
+        for _ in 0..skipped_ticks {
+            self.elapsed_time += TICK_DURATION;
+            self.environment.timestamp += tick;
+            self.book_recurring_charges();
+        }
+        self.tick();
+    }
+
+    /// Books the plane income earned during the tick.
+    fn book_recurring_charges(&mut self) {
+        let profit = self.calculate_profit(TICK_DURATION);
+        if profit != 0.0 {
+            self.environment
+                .add_income(self.environment.timestamp, profit);
+        }
+    }
+
+    fn compact_metrics(&mut self) {
+        let width = METRICS_BUCKET_WIDTH.as_millis();
+        if self.environment.timestamp % width != 0 {
+            return;
+        }
+        let timestamp = self.environment.timestamp;
+        let net_worth = self.environment.net_worth(timestamp);
+        self.environment
+            .metrics
+            .record_net_worth(timestamp, net_worth);
+        if !self.environment.config.compact_history {
+            return;
+        }
+
+        let before = self
+            .environment
+            .timestamp
+            .saturating_sub(METRICS_RETENTION.as_millis());
+        self.environment.metrics.compact(before, width);
+        self.environment.company_finances.compact(before, width);
+    }
+
+    fn record_checkpoint(&mut self) {
+        if self.environment.timestamp % CHECKPOINT_INTERVAL != 0 {
+            return;
+        }
+        self.checkpoints
+            .push(Checkpoint::capture(&self.environment));
+    }
+
+    fn execute_due_commands(&mut self) {
+        let timestamp = self.environment.timestamp;
         let mut to_execute = vec![];
         self.commands.retain(|command| {
-            if self.environment.timestamp >= command.timestamp {
+            if timestamp >= command.timestamp {
                 to_execute.push(command.clone());
                 false
             } else {
                 true
             }
         });
+        to_execute.sort_by_key(|command| command.timestamp);
 
         for command in to_execute {
             self.execute_command(command);
         }
-
-        let profit = self.calculate_profit(effective_delta_time);
-        self.environment
-            .company_finances
-            .add_income(self.environment.timestamp, profit);
-
-        self.update_flights();
-        self.handle_events();
     }
 
     pub fn update_flights(&mut self) {
+        let timestamp = self.environment.timestamp;
         for flight in &mut self.environment.flights {
-            let previous_state = flight.state.clone();
-            flight.update_state(self.elapsed_time.as_millis());
-
-            match (previous_state, &flight.state) {
-                (FlightState::Scheduled, FlightState::EnRoute { .. }) => {
-                    self.event_manager.add_event(Box::new(AirplaneTakeoffEvent {
-                        flight: flight.clone(),
-                    }));
+            // A flight may pass through several states within one tick.
+            loop {
+                let previous_state = flight.state.clone();
+                flight.update_state(timestamp);
+                if flight.state == previous_state {
+                    break;
                 }
-                (FlightState::EnRoute { .. }, FlightState::Finished)
-                | (FlightState::Landed { .. }, FlightState::Finished) => {
-                    self.event_manager.add_event(Box::new(AirplaneLandedEvent {
-                        flight: flight.clone(),
-                    }));
+
+                match (previous_state, &flight.state) {
+                    (FlightState::Scheduled, FlightState::EnRoute { .. }) => {
+                        self.event_bus.publish(
+                            timestamp,
+                            AirplaneTakeoffEvent {
+                                flight: flight.clone(),
+                            },
+                        );
+                    }
+                    (FlightState::EnRoute { .. }, FlightState::Finished)
+                    | (FlightState::Landed { .. }, FlightState::Finished) => {
+                        self.event_bus.publish(
+                            timestamp,
+                            AirplaneLandedEvent {
+                                flight: flight.clone(),
+                            },
+                        );
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
     }
@@ -139,47 +357,172 @@ impl Simulation {
         self.commands.push(command);
     }
 
+    /// Gives the company the assets created by `commands` for free.
+    ///
+    /// The commands are executed right away without charging the company and
+    /// are kept apart from the command history, so the assets cannot be
+    /// undone and are worth nothing when sold.
+    pub fn add_starting_assets(
+        &mut self,
+        commands: Vec<Box<dyn Command>>,
+    ) -> Result<(), CommandError> {
+        let company_finances = self.environment.company_finances.clone();
+        self.environment
+            .company_finances
+            .add_income(self.environment.timestamp, f64::from(u32::MAX) * 1_000.0);
+        let (bases, landing_rights, planes) = (
+            self.environment.bases.len(),
+            self.environment.landing_rights.len(),
+            self.environment.planes.len(),
+        );
+        let result = commands.into_iter().try_for_each(|command| {
+            command.execute(&mut self.environment)?;
+            self.starting_assets
+                .push(TimestampedCommand::new(self.environment.timestamp, command));
+            Ok(())
+        });
+        self.environment.company_finances = company_finances;
+
+        for base in &mut self.environment.bases[bases..] {
+            base.cost = 0.0;
+        }
+        for landing_rights in &mut self.environment.landing_rights[landing_rights..] {
+            landing_rights.cost = 0.0;
+        }
+        for plane in &mut self.environment.planes[planes..] {
+            plane.cost = 0.0;
+        }
+        result
+    }
+
+    /// Continues a played replay as a normal game from the current timestamp.
+    ///
+    /// Pending commands of the replay are dropped and the history is cut at the
+    /// current timestamp, so only the player's commands follow.
+    pub fn take_control(&mut self) {
+        let timestamp = self.environment.timestamp;
+        self.commands.clear();
+        self.history_operations.clear();
+        self.command_history
+            .retain(|command| command.timestamp <= timestamp);
+        self.checkpoints
+            .retain(|checkpoint| checkpoint.timestamp <= timestamp);
+    }
+
+    /// Executes a command and makes it undoable if it has an inverse.
+    ///
+    /// The command is recorded in the history with the timestamp it was
+    /// executed at, so a replay executes it in the same tick.
     pub fn execute_command(&mut self, timestamped_command: TimestampedCommand) {
-        let command = &timestamped_command.command;
-        self.command_history.push(timestamped_command.clone());
-        match command.execute(&mut self.environment) {
-            Ok(_message) => {
-                if let Some(command) = command
-                    .as_any()
-                    .downcast_ref::<crate::model::commands::BuyPlaneCommand>()
-                {
-                    self.event_manager.add_event(Box::new(BuyPlaneEvent {
-                        plane_type: command.plane_type.clone(),
-                    }));
+        let command = timestamped_command.command;
+        let inverse = command.inverse(&self.environment);
+        if self.run_command(command.clone()).is_err() {
+            return;
+        }
+
+        let timestamp = self.environment.timestamp;
+        let grace_period = UNDO_GRACE_PERIOD.as_millis();
+        self.undo_stack
+            .retain(|entry| timestamp - entry.executed_at <= grace_period);
+        if let Some(inverse) = inverse {
+            self.undo_stack.push(UndoEntry {
+                executed_at: timestamp,
+                command,
+                inverse,
+            });
+        }
+        self.redo_stack.clear();
+    }
+
+    /// The command that [`Simulation::undo`] would revert.
+    pub fn next_undo(&self) -> Result<&UndoEntry, UndoError> {
+        let entry = self.undo_stack.last().ok_or(UndoError::NothingToUndo)?;
+        let elapsed = self.environment.timestamp.saturating_sub(entry.executed_at);
+        if elapsed > UNDO_GRACE_PERIOD.as_millis() {
+            return Err(UndoError::GracePeriodExpired);
+        }
+        entry.inverse.validate(&self.environment)?;
+        Ok(entry)
+    }
+
+    /// The command that [`Simulation::redo`] would execute again.
+    pub fn next_redo(&self) -> Result<&UndoEntry, UndoError> {
+        let entry = self.redo_stack.last().ok_or(UndoError::NothingToRedo)?;
+        entry.command.validate(&self.environment)?;
+        Ok(entry)
+    }
+
+    /// Reverts the most recent undoable command at the start of the next tick.
+    ///
+    /// The inverse command is recorded in the command history like any other
+    /// command, so replays reproduce the undo.
+    pub fn undo(&mut self) -> Result<(), UndoError> {
+        self.next_undo()?;
+        if let Some(entry) = self.undo_stack.pop() {
+            self.history_operations.push(HistoryOperation::Undo(entry));
+        }
+        Ok(())
+    }
+
+    /// Executes the most recently undone command again at the start of the
+    /// next tick.
+    pub fn redo(&mut self) -> Result<(), UndoError> {
+        self.next_redo()?;
+        if let Some(entry) = self.redo_stack.pop() {
+            self.history_operations.push(HistoryOperation::Redo(entry));
+        }
+        Ok(())
+    }
+
+    fn apply_history_operations(&mut self) {
+        let timestamp = self.environment.timestamp;
+        for operation in std::mem::take(&mut self.history_operations) {
+            match operation {
+                HistoryOperation::Undo(entry) => {
+                    if self.run_command(entry.inverse.clone()).is_ok() {
+                        self.redo_stack.push(entry);
+                    }
                 }
-                if let Some(command) = command
-                    .as_any()
-                    .downcast_ref::<crate::model::commands::CreateBaseCommand>()
-                {
-                    self.event_manager.add_event(Box::new(CreateBaseEvent {
-                        aerodrome: command.aerodrome.clone(),
-                    }));
+                HistoryOperation::Redo(entry) => {
+                    let inverse = entry.command.inverse(&self.environment);
+                    if self.run_command(entry.command.clone()).is_ok() {
+                        if let Some(inverse) = inverse {
+                            self.undo_stack.push(UndoEntry {
+                                executed_at: timestamp,
+                                command: entry.command,
+                                inverse,
+                            });
+                        }
+                    }
                 }
-                if let Some(command) = command
-                    .as_any()
-                    .downcast_ref::<crate::model::commands::BuyLandingRightsCommand>(
-                ) {
-                    self.event_manager
-                        .add_event(Box::new(BuyLandingRightsEvent {
-                            aerodrome: command.aerodrome.clone(),
-                        }));
+            }
+        }
+    }
+
+    fn run_command(&mut self, command: Box<dyn Command>) -> Result<(), CommandError> {
+        let timestamp = self.environment.timestamp;
+        self.command_history
+            .push(TimestampedCommand::new(timestamp, command.clone()));
+        match command.execute(&mut self.environment) {
+            Ok(events) => {
+                for event in events {
+                    self.event_bus.publish(timestamp, event);
                 }
+                Ok(())
             }
             Err(error) => {
                 log::error!("Error executing command: {}", error);
-                self.error_messages
-                    .push((self.elapsed_time.as_millis(), error.to_string()));
+                self.journal
+                    .record(JournalEntry::error(timestamp, error.to_string()));
 
-                self.environment.last_errors = self.error_messages.clone();
+                self.environment
+                    .last_errors
+                    .push((timestamp, error.to_string()));
                 // If more than 10 error messages in environment, remove the oldest one
                 if self.environment.last_errors.len() > 10 {
                     self.environment.last_errors.remove(0);
                 }
+                Err(error)
             }
         }
     }
@@ -197,11 +540,9 @@ impl Simulation {
     }
 
     pub fn handle_events(&mut self) {
-        for event in self.event_manager.handle_events(&mut self.environment) {
-            self.event_messages.push((
-                self.elapsed_time.as_millis(),
-                format!("{}", event.message()),
-            ));
+        for event in self.event_bus.handle_events(&mut self.environment) {
+            self.journal
+                .record(JournalEntry::from_event(&event, &self.environment));
         }
     }
 }
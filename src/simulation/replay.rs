@@ -5,19 +5,47 @@ use serde_yaml::Value;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::path::Path;
+use thiserror::Error;
 
 use crate::model::commands::{
-    BuyLandingRightsCommand, BuyPlaneCommand, Command, CreateBaseCommand, ScheduleFlightCommand,
-    SellLandingRightsCommand, SellPlaneCommand, TimestampedCommand,
+    BuyLandingRightsCommand, BuyPlaneCommand, CancelFlightCommand, Command, CommandError,
+    CreateBaseCommand, RemoveBaseCommand, ScheduleFlightCommand, SellLandingRightsCommand,
+    SellPlaneCommand, TimestampedCommand,
 };
-use crate::model::EnvironmentConfig;
+use crate::model::{EnvironmentConfig, WorldDataGateway};
 
 use crate::model::Timestamp;
 
-#[derive(Clone)]
+use super::checkpoint::{Checkpoint, Divergence};
+use super::Simulation;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Replay diverges: {0}")]
+    Divergence(Divergence),
+    #[error("Invalid starting asset: {0}")]
+    StartingAsset(#[from] CommandError),
+}
+
+/// Replay a game was branched from by taking control while watching it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayBranch {
+    /// Timestamp at which control was taken.
+    pub timestamp: Timestamp,
+    pub parent: Box<Replay>,
+}
+
+#[derive(Clone, Debug)]
 pub struct Replay {
     pub initial_config: EnvironmentConfig,
+    /// Commands the company got its starting assets by, see
+    /// [`Simulation::add_starting_assets`].
+    pub starting_assets: Vec<TimestampedCommand>,
     pub command_history: Vec<TimestampedCommand>,
+    /// States the recorded game passed through, in order of their timestamps.
+    pub checkpoints: Vec<Checkpoint>,
+    /// Replay this game was branched from, if any.
+    pub branch: Option<ReplayBranch>,
 }
 
 impl Serialize for Replay {
@@ -25,9 +53,19 @@ impl Serialize for Replay {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("Replay", 2)?;
+        let mut s = serializer.serialize_struct("Replay", 5)?;
         s.serialize_field("initial_config", &self.initial_config)?;
+        if self.starting_assets.is_empty() {
+            s.skip_field("starting_assets")?;
+        } else {
+            s.serialize_field("starting_assets", &self.starting_assets)?;
+        }
         s.serialize_field("command_history", &self.command_history)?;
+        s.serialize_field("checkpoints", &self.checkpoints)?;
+        match &self.branch {
+            Some(branch) => s.serialize_field("branch", branch)?,
+            None => s.skip_field("branch")?,
+        }
         s.end()
     }
 }
@@ -40,17 +78,29 @@ impl<'de> Deserialize<'de> for Replay {
         #[derive(Deserialize)]
         struct InnerReplay {
             initial_config: EnvironmentConfig,
+            #[serde(default)]
+            starting_assets: Vec<TimestampedCommand>,
             command_history: Vec<TimestampedCommand>,
+            #[serde(default)]
+            checkpoints: Vec<Checkpoint>,
+            #[serde(default)]
+            branch: Option<ReplayBranch>,
         }
 
         let InnerReplay {
             initial_config,
+            starting_assets,
             command_history,
+            checkpoints,
+            branch,
         } = InnerReplay::deserialize(deserializer)?;
 
         Ok(Replay {
             initial_config,
+            starting_assets,
             command_history,
+            checkpoints,
+            branch,
         })
     }
 }
@@ -62,8 +112,67 @@ impl Replay {
     ) -> Self {
         Self {
             initial_config,
+            starting_assets: vec![],
             command_history,
+            checkpoints: vec![],
+            branch: None,
+        }
+    }
+
+    /// Records the commands of the simulation together with its checkpoints
+    /// and a final checkpoint of the current state.
+    pub fn from_simulation(simulation: &Simulation) -> Self {
+        let mut checkpoints = simulation.checkpoints.clone();
+        let last = Checkpoint::capture(&simulation.environment);
+        if checkpoints.last().map(|checkpoint| checkpoint.timestamp) != Some(last.timestamp) {
+            checkpoints.push(last);
+        }
+        Self {
+            initial_config: simulation.environment.config.clone(),
+            starting_assets: simulation.starting_assets.clone(),
+            command_history: simulation.command_history.clone(),
+            checkpoints,
+            branch: None,
+        }
+    }
+
+    /// Creates a simulation in the initial state of the recorded game, with
+    /// its starting assets but none of its commands scheduled.
+    pub fn create_simulation(
+        &self,
+        world_data_gateway: Box<dyn WorldDataGateway>,
+    ) -> Result<Simulation, ReplayError> {
+        let mut simulation = Simulation::new(self.initial_config.clone(), world_data_gateway);
+        simulation.add_starting_assets(
+            self.starting_assets
+                .iter()
+                .map(|command| command.command.clone_box())
+                .collect(),
+        )?;
+        Ok(simulation)
+    }
+
+    /// Re-runs the replay headlessly up to its last checkpoint and compares
+    /// the state at every checkpoint, returning the first divergence.
+    ///
+    /// On success the re-simulated game is returned, stopped at the last
+    /// checkpoint.
+    pub fn verify(
+        &self,
+        world_data_gateway: Box<dyn WorldDataGateway>,
+    ) -> Result<Simulation, ReplayError> {
+        let mut simulation = self.create_simulation(world_data_gateway)?;
+        for command in &self.command_history {
+            simulation.add_command_timed(command.clone());
         }
+        for checkpoint in &self.checkpoints {
+            simulation.run_until(checkpoint.timestamp);
+            let actual = Checkpoint::capture(&simulation.environment);
+            if let Some(divergence) = checkpoint.diverges_from(&actual) {
+                return Err(ReplayError::Divergence(divergence));
+            }
+        }
+        Ok(simulation)
     }
 
     #[cfg(not(target_arch = "wasm32"))]
@@ -148,6 +257,20 @@ impl Serialize for TimestampedCommand {
                 arguments: command.clone(),
             };
             command_wrapper.serialize(serializer)
+        } else if let Some(command) = self.command.as_any().downcast_ref::<RemoveBaseCommand>() {
+            let command_wrapper = CommandWrapper {
+                timestamp: self.timestamp,
+                command: "RemoveBaseCommand".to_string(),
+                arguments: command.clone(),
+            };
+            command_wrapper.serialize(serializer)
+        } else if let Some(command) = self.command.as_any().downcast_ref::<CancelFlightCommand>() {
+            let command_wrapper = CommandWrapper {
+                timestamp: self.timestamp,
+                command: "CancelFlightCommand".to_string(),
+                arguments: command.clone(),
+            };
+            command_wrapper.serialize(serializer)
         } else {
             panic!("Unknown command type.");
         }
@@ -186,7 +309,7 @@ impl<'de> Deserialize<'de> for TimestampedCommand {
                     serde_yaml::from_value(arguments).map_err(de::Error::custom)?;
                 Box::new(command)
             }
-            "SellPlane" => {
+            "SellPlaneCommand" | "SellPlane" => {
                 let command: SellPlaneCommand =
                     serde_yaml::from_value(arguments).map_err(de::Error::custom)?;
                 Box::new(command)
@@ -196,6 +319,16 @@ impl<'de> Deserialize<'de> for TimestampedCommand {
                     serde_yaml::from_value(arguments).map_err(de::Error::custom)?;
                 Box::new(command)
             }
+            "RemoveBaseCommand" => {
+                let command: RemoveBaseCommand =
+                    serde_yaml::from_value(arguments).map_err(de::Error::custom)?;
+                Box::new(command)
+            }
+            "CancelFlightCommand" => {
+                let command: CancelFlightCommand =
+                    serde_yaml::from_value(arguments).map_err(de::Error::custom)?;
+                Box::new(command)
+            }
             _ => return Err(de::Error::custom("Unknown command")),
         };
 
@@ -0,0 +1,307 @@
+use std::time::Duration;
+
+use crate::model::{commands::TimestampedCommand, Environment, Timestamp};
+
+use super::{
+    checkpoint::Checkpoint, journal::Journal, replay::Replay, undo::UndoEntry, Simulation,
+    TICK_DURATION,
+};
+
+/// Simulated time between two snapshots taken at the start of a replay.
+pub const SNAPSHOT_INTERVAL: Timestamp = 24 * 60 * 60 * 1000;
+
+/// Number of snapshots kept at most. When there are more, every other one
+/// is dropped and the interval between them doubles.
+pub const MAX_SNAPSHOTS: usize = 32;
+
+/// Copy of the state of a simulation between two ticks.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    environment: Environment,
+    elapsed_time: Duration,
+    commands: Vec<TimestampedCommand>,
+    command_history: Vec<TimestampedCommand>,
+    journal: Journal,
+    undo_stack: Vec<UndoEntry>,
+    redo_stack: Vec<UndoEntry>,
+    checkpoints: Vec<Checkpoint>,
+}
+
+impl Snapshot {
+    fn take(simulation: &Simulation) -> Self {
+        Self {
+            environment: simulation.environment.clone(),
+            elapsed_time: simulation.elapsed_time,
+            commands: simulation.commands.clone(),
+            command_history: simulation.command_history.clone(),
+            journal: simulation.journal.clone(),
+            undo_stack: simulation.undo_stack.clone(),
+            redo_stack: simulation.redo_stack.clone(),
+            checkpoints: simulation.checkpoints.clone(),
+        }
+    }
+
+    fn restore(&self, simulation: &mut Simulation) {
+        simulation.environment = self.environment.clone();
+        simulation.elapsed_time = self.elapsed_time;
+        simulation.accumulated_time = Duration::ZERO;
+        simulation.commands = self.commands.clone();
+        simulation.command_history = self.command_history.clone();
+        simulation.journal = self.journal.clone();
+        simulation.undo_stack = self.undo_stack.clone();
+        simulation.redo_stack = self.redo_stack.clone();
+        simulation.checkpoints = self.checkpoints.clone();
+        simulation.history_operations.clear();
+        simulation.event_bus.clear_events();
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.environment.timestamp
+    }
+}
+
+/// Plays a replay on a simulation and lets the viewer seek to any point in
+/// time and step through its commands.
+///
+/// Seeking forward re-simulates the ticks in between. Snapshots of the
+/// simulation are kept every [`SNAPSHOT_INTERVAL`] of played time, spaced
+/// further apart as the replay gets longer so there are never more than
+/// [`MAX_SNAPSHOTS`]. Seeking backward restores the latest snapshot before
+/// the target and only re-simulates from there.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    commands: Vec<TimestampedCommand>,
+    end: Timestamp,
+    /// Snapshots sorted by timestamp, starting with the initial state.
+    snapshots: Vec<Snapshot>,
+    snapshot_interval: Timestamp,
+}
+
+impl ReplayPlayer {
+    /// Schedules the commands of `replay` on the fresh `simulation`.
+    pub fn new(replay: &Replay, simulation: &mut Simulation) -> Self {
+        for command in &replay.command_history {
+            simulation.add_command_timed(command.clone());
+        }
+        let last_command = replay
+            .command_history
+            .last()
+            .map(|command| command.timestamp);
+        let last_checkpoint = replay
+            .checkpoints
+            .last()
+            .map(|checkpoint| checkpoint.timestamp);
+        Self {
+            commands: replay.command_history.clone(),
+            end: last_command.max(last_checkpoint).unwrap_or_default(),
+            snapshots: vec![Snapshot::take(simulation)],
+            snapshot_interval: SNAPSHOT_INTERVAL,
+        }
+    }
+
+    pub fn commands(&self) -> &[TimestampedCommand] {
+        &self.commands
+    }
+
+    /// Timestamp of the last checkpoint or command of the replay.
+    pub fn end(&self) -> Timestamp {
+        self.end
+    }
+
+    /// Index of the most recently executed command of the replay.
+    pub fn current_command(&self, simulation: &Simulation) -> Option<usize> {
+        let timestamp = simulation.environment.timestamp;
+        self.commands
+            .partition_point(|command| command.timestamp <= timestamp)
+            .checked_sub(1)
+    }
+
+    /// Plays the replay for a frame of `delta_time` real time at the speed of
+    /// the simulation.
+    pub fn update(&mut self, simulation: &mut Simulation, delta_time: Duration) {
+        simulation.update(delta_time);
+        self.record_snapshot(simulation);
+    }
+
+    /// Brings the simulation to the first tick at or after `timestamp`.
+    pub fn seek(&mut self, simulation: &mut Simulation, timestamp: Timestamp) {
+        let index = self
+            .snapshots
+            .partition_point(|snapshot| snapshot.timestamp() <= timestamp)
+            .saturating_sub(1);
+        let snapshot = &self.snapshots[index];
+        let current = simulation.environment.timestamp;
+        if timestamp < current || snapshot.timestamp() > current {
+            snapshot.restore(simulation);
+        }
+        while simulation.environment.timestamp < timestamp {
+            simulation.tick();
+            self.record_snapshot(simulation);
+        }
+    }
+
+    /// Seeks to the tick in which the next command is executed and returns
+    /// its index. Commands of the same tick are executed together.
+    pub fn step(&mut self, simulation: &mut Simulation) -> Option<usize> {
+        let next = self
+            .current_command(simulation)
+            .map_or(0, |index| index + 1);
+        let timestamp = self.commands.get(next)?.timestamp;
+        self.seek(simulation, timestamp);
+        self.current_command(simulation)
+    }
+
+    /// Seeks to the tick before the most recently executed command and
+    /// returns the index of the command executed before that.
+    pub fn step_back(&mut self, simulation: &mut Simulation) -> Option<usize> {
+        let current = self.current_command(simulation)?;
+        let timestamp = self.commands[current].timestamp;
+        self.seek(
+            simulation,
+            timestamp.saturating_sub(TICK_DURATION.as_millis()),
+        );
+        self.current_command(simulation)
+    }
+
+    fn record_snapshot(&mut self, simulation: &Simulation) {
+        let last = self.snapshots.last().map_or(0, Snapshot::timestamp);
+        if simulation.environment.timestamp < last + self.snapshot_interval {
+            return;
+        }
+        self.snapshots.push(Snapshot::take(simulation));
+        if self.snapshots.len() > MAX_SNAPSHOTS {
+            let mut index = 0;
+            self.snapshots.retain(|_| {
+                index += 1;
+                index % 2 == 1
+            });
+            self.snapshot_interval *= 2;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{
+        commands::{BuyLandingRightsCommand, Command, CreateBaseCommand},
+        Aerodrome, StringBasedWorldData,
+    };
+
+    const DAY: Timestamp = SNAPSHOT_INTERVAL;
+
+    fn replay() -> Replay {
+        let command =
+            |timestamp, command: Box<dyn Command>| TimestampedCommand::new(timestamp, command);
+        Replay::new(
+            Default::default(),
+            vec![
+                command(
+                    60_000,
+                    Box::new(CreateBaseCommand {
+                        base_id: 1,
+                        aerodrome: Aerodrome::frankfurt(),
+                    }),
+                ),
+                command(
+                    2 * DAY,
+                    Box::new(BuyLandingRightsCommand {
+                        landing_rights_id: 2,
+                        aerodrome: Aerodrome::paris(),
+                    }),
+                ),
+                command(
+                    3 * DAY,
+                    Box::new(BuyLandingRightsCommand {
+                        landing_rights_id: 3,
+                        aerodrome: Aerodrome::paris(),
+                    }),
+                ),
+            ],
+        )
+    }
+
+    fn simulation() -> Simulation {
+        Simulation::new(
+            Default::default(),
+            Box::new(StringBasedWorldData::default()),
+        )
+    }
+
+    #[test]
+    fn test_seek() {
+        let replay = replay();
+        let mut simulation = simulation();
+        let mut player = ReplayPlayer::new(&replay, &mut simulation);
+        assert_eq!(player.end(), 3 * DAY);
+        assert_eq!(player.current_command(&simulation), None);
+
+        player.seek(&mut simulation, 2 * DAY + 1);
+        assert_eq!(simulation.environment.timestamp, 2 * DAY + 60_000);
+        assert_eq!(simulation.environment.landing_rights.len(), 1);
+        assert_eq!(player.current_command(&simulation), Some(1));
+        let cash = simulation
+            .environment
+            .company_finances
+            .cash(simulation.environment.timestamp);
+
+        player.seek(&mut simulation, DAY);
+        assert_eq!(simulation.environment.timestamp, DAY);
+        assert!(simulation.environment.landing_rights.is_empty());
+        assert_eq!(simulation.command_history.len(), 1);
+        assert_eq!(player.current_command(&simulation), Some(0));
+
+        player.seek(&mut simulation, 2 * DAY + 1);
+        assert_eq!(
+            simulation
+                .environment
+                .company_finances
+                .cash(simulation.environment.timestamp),
+            cash
+        );
+    }
+
+    #[test]
+    fn test_step() {
+        let replay = replay();
+        let mut simulation = simulation();
+        let mut player = ReplayPlayer::new(&replay, &mut simulation);
+
+        assert_eq!(player.step(&mut simulation), Some(0));
+        assert_eq!(player.step(&mut simulation), Some(1));
+        assert_eq!(simulation.environment.timestamp, 2 * DAY);
+        assert_eq!(player.step(&mut simulation), Some(2));
+        assert_eq!(player.step(&mut simulation), None);
+        assert_eq!(simulation.environment.landing_rights.len(), 2);
+
+        assert_eq!(player.step_back(&mut simulation), Some(1));
+        assert_eq!(simulation.environment.timestamp, 3 * DAY - 60_000);
+        assert_eq!(simulation.environment.landing_rights.len(), 1);
+        assert_eq!(player.step_back(&mut simulation), Some(0));
+        assert_eq!(player.step_back(&mut simulation), None);
+        assert_eq!(simulation.environment.timestamp, 0);
+        assert!(simulation.environment.bases.is_empty());
+    }
+
+    #[test]
+    fn test_snapshots_are_thinned_out() {
+        let replay = replay();
+        let mut simulation = simulation();
+        let mut player = ReplayPlayer::new(&replay, &mut simulation);
+
+        let end = MAX_SNAPSHOTS as Timestamp * DAY;
+        player.seek(&mut simulation, end);
+        assert!(player.snapshots.len() <= MAX_SNAPSHOTS);
+        assert_eq!(player.snapshot_interval, 2 * SNAPSHOT_INTERVAL);
+        assert!(player
+            .snapshots
+            .windows(2)
+            .all(|pair| pair[1].timestamp() - pair[0].timestamp() >= SNAPSHOT_INTERVAL));
+        let cash = simulation.environment.company_finances.cash(end);
+
+        player.seek(&mut simulation, DAY);
+        assert_eq!(simulation.environment.landing_rights.len(), 0);
+        player.seek(&mut simulation, end);
+        assert_eq!(simulation.environment.company_finances.cash(end), cash);
+    }
+}
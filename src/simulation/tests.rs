@@ -3,9 +3,14 @@ use crate::{
     model::{
         commands::{
             BuyLandingRightsCommand, BuyPlaneCommand, CreateBaseCommand, ScheduleFlightCommand,
+            TimestampedCommand,
         },
         Aerodrome, StringBasedWorldData,
     },
+    simulation::{
+        replay::{Replay, ReplayBranch, ReplayError},
+        undo::UndoError,
+    },
 };
 
 use super::*;
@@ -83,3 +88,425 @@ fn test_simulation() {
     assert_eq!(simulation.environment.planes.len(), 1);
     assert_eq!(simulation.environment.flights.len(), 1);
 }
+
+fn scheduled_simulation() -> Simulation {
+    let mut simulation = Simulation::new(
+        Default::default(),
+        Box::new(StringBasedWorldData::default()),
+    );
+
+    let frankfurt_aerodrome = Aerodrome::frankfurt();
+    let paris_aerodrome = Aerodrome::paris();
+
+    let planes_config: PlanesConfig =
+        serde_yaml::from_str(include_str!("../../assets/planes.yaml")).unwrap();
+
+    let mut plane_type = planes_config.planes[0].clone();
+    plane_type.monthly_income = 30_000.0;
+
+    let base_id = CreateBaseCommand::generate_id();
+    let plane_id = BuyPlaneCommand::generate_id();
+
+    simulation.add_command_timed(TimestampedCommand::new(
+        1_000,
+        Box::new(CreateBaseCommand {
+            base_id,
+            aerodrome: frankfurt_aerodrome.clone(),
+        }),
+    ));
+    simulation.add_command_timed(TimestampedCommand::new(
+        1_000,
+        Box::new(BuyLandingRightsCommand {
+            landing_rights_id: BuyLandingRightsCommand::generate_id(),
+            aerodrome: paris_aerodrome.clone(),
+        }),
+    ));
+    simulation.add_command_timed(TimestampedCommand::new(
+        90_000,
+        Box::new(BuyPlaneCommand {
+            plane_id,
+            plane_type: plane_type.clone(),
+            home_base_id: base_id,
+        }),
+    ));
+    simulation.add_command_timed(TimestampedCommand::new(
+        200_000,
+        Box::new(ScheduleFlightCommand {
+            flight_id: ScheduleFlightCommand::generate_id(),
+            airplane: crate::model::AirPlane {
+                id: plane_id,
+                base_id,
+                cost: plane_type.cost as f64,
+                plane_type,
+                purchased_at: 0,
+            },
+            origin_aerodrome: frankfurt_aerodrome,
+            stopovers: vec![paris_aerodrome],
+            departure_time: 300_000,
+        }),
+    ));
+    simulation
+}
+
+#[test]
+fn test_update_is_independent_of_frame_rate() {
+    let mut slow_frames = scheduled_simulation();
+    let mut fast_frames = scheduled_simulation();
+
+    let end_of_simulation: Timestamp = 6 * 60 * 60 * 1000;
+    while slow_frames.environment.timestamp < end_of_simulation {
+        slow_frames.update(Duration::from_millis(1_000));
+    }
+    while fast_frames.environment.timestamp < slow_frames.environment.timestamp {
+        fast_frames.update(Duration::from_millis(16));
+    }
+
+    assert_eq!(
+        slow_frames.environment.timestamp,
+        fast_frames.environment.timestamp
+    );
+    assert_eq!(slow_frames.command_history.len(), 4);
+    assert_eq!(fast_frames.command_history.len(), 4);
+    assert_eq!(
+        slow_frames.environment.flights[0].state,
+        FlightState::Finished
+    );
+    assert_eq!(
+        slow_frames.environment.flights[0].arrival_time,
+        fast_frames.environment.flights[0].arrival_time
+    );
+    let timestamp = slow_frames.environment.timestamp;
+    assert_eq!(
+        slow_frames.environment.company_finances.cash(timestamp),
+        fast_frames.environment.company_finances.cash(timestamp)
+    );
+}
+
+#[test]
+fn test_run_until() {
+    let mut simulation = scheduled_simulation();
+
+    simulation.run_until(90_000);
+
+    assert_eq!(simulation.environment.timestamp, 120_000);
+    assert_eq!(simulation.environment.bases.len(), 1);
+    assert_eq!(simulation.environment.landing_rights.len(), 1);
+    assert_eq!(simulation.environment.planes.len(), 1);
+    assert_eq!(simulation.commands.len(), 1);
+
+    simulation.run_until(400_000);
+
+    assert_eq!(simulation.environment.timestamp, 420_000);
+    assert!(simulation.commands.is_empty());
+    assert!(matches!(
+        simulation.environment.flights[0].state,
+        FlightState::EnRoute { .. }
+    ));
+}
+
+#[test]
+fn test_advance_to_next_event() {
+    let mut simulation = scheduled_simulation();
+
+    let event = simulation.advance_to_next_event().unwrap();
+    assert_eq!(event.timestamp, 1_000);
+    assert_eq!(event.kind, future_events::FutureEventKind::Command);
+    assert_eq!(simulation.environment.timestamp, 60_000);
+    assert_eq!(simulation.environment.bases.len(), 1);
+
+    let event = simulation.advance_to_next_event().unwrap();
+    assert_eq!(event.timestamp, 90_000);
+    assert_eq!(simulation.environment.timestamp, 120_000);
+    assert_eq!(simulation.environment.planes.len(), 1);
+
+    let event = simulation.advance_to_next_event().unwrap();
+    assert_eq!(event.timestamp, 200_000);
+    let event = simulation.advance_to_next_event().unwrap();
+    assert!(matches!(
+        event.kind,
+        future_events::FutureEventKind::Takeoff { .. }
+    ));
+    assert_eq!(simulation.environment.timestamp, 300_000);
+}
+
+#[test]
+fn test_advance_to_next_event_matches_ticking() {
+    let mut skipped = scheduled_simulation();
+    let mut ticked = scheduled_simulation();
+
+    let end_of_simulation: Timestamp = 3 * 24 * 60 * 60 * 1000;
+    let mut advances = 0;
+    while skipped.environment.timestamp < end_of_simulation {
+        skipped.advance_to_next_event().unwrap();
+        advances += 1;
+    }
+    ticked.run_until(skipped.environment.timestamp);
+
+    assert!(advances < 20);
+    assert_eq!(skipped.environment.timestamp, ticked.environment.timestamp);
+    assert_eq!(skipped.elapsed_time, ticked.elapsed_time);
+    assert_eq!(skipped.command_history.len(), ticked.command_history.len());
+    assert_eq!(skipped.environment.flights[0].state, FlightState::Finished);
+    assert_eq!(
+        skipped.environment.flights[0].arrival_time,
+        ticked.environment.flights[0].arrival_time
+    );
+    let message_timestamps = |simulation: &Simulation| -> Vec<Timestamp> {
+        simulation
+            .journal
+            .entries()
+            .iter()
+            .map(|entry| entry.timestamp)
+            .collect()
+    };
+    assert_eq!(message_timestamps(&skipped), message_timestamps(&ticked));
+    assert_eq!(
+        skipped.environment.company_finances.income,
+        ticked.environment.company_finances.income
+    );
+    assert_eq!(
+        skipped.environment.company_finances.expenses,
+        ticked.environment.company_finances.expenses
+    );
+    assert_eq!(skipped.environment.metrics, ticked.environment.metrics);
+    assert_eq!(skipped.checkpoints.len(), 3);
+    assert_eq!(skipped.checkpoints, ticked.checkpoints);
+}
+
+#[test]
+fn test_metrics_follow_landings() {
+    let mut simulation = scheduled_simulation();
+    simulation.run_until(3 * 24 * 60 * 60 * 1000);
+
+    let environment = &simulation.environment;
+    let finished = environment
+        .flights
+        .iter()
+        .filter(|flight| flight.state == FlightState::Finished)
+        .count();
+    assert!(finished > 0);
+    assert_eq!(
+        environment.metrics.landed_flights(environment.timestamp),
+        finished as u32
+    );
+}
+
+#[test]
+fn test_advance_to_next_event_without_events() {
+    let mut simulation = Simulation::new(
+        Default::default(),
+        Box::new(StringBasedWorldData::default()),
+    );
+
+    assert!(simulation
+        .future_events()
+        .iter()
+        .all(|event| event.kind.is_bookkeeping()));
+    assert!(simulation.advance_to_next_event().is_none());
+    assert_eq!(simulation.environment.timestamp, 0);
+}
+
+fn landing_rights_simulation() -> Simulation {
+    let mut simulation = Simulation::new(
+        Default::default(),
+        Box::new(StringBasedWorldData::default()),
+    );
+    simulation.add_command(Box::new(BuyLandingRightsCommand {
+        landing_rights_id: BuyLandingRightsCommand::generate_id(),
+        aerodrome: Aerodrome::paris(),
+    }));
+    simulation.tick();
+    simulation
+}
+
+#[test]
+fn test_undo_redo() {
+    let mut simulation = landing_rights_simulation();
+    let cash = |simulation: &Simulation| {
+        simulation
+            .environment
+            .company_finances
+            .cash(simulation.environment.timestamp)
+    };
+    let cash_before = cash(&simulation) + simulation.environment.config.landing_rights_cost;
+    assert_eq!(simulation.environment.landing_rights.len(), 1);
+
+    simulation.undo().unwrap();
+    simulation.tick();
+    assert!(simulation.environment.landing_rights.is_empty());
+    assert_eq!(cash(&simulation), cash_before);
+    assert!(matches!(simulation.undo(), Err(UndoError::NothingToUndo)));
+
+    simulation.redo().unwrap();
+    simulation.tick();
+    assert_eq!(simulation.environment.landing_rights.len(), 1);
+    assert!(matches!(simulation.redo(), Err(UndoError::NothingToRedo)));
+    assert_eq!(simulation.command_history.len(), 3);
+}
+
+#[test]
+fn test_undo_grace_period_expired() {
+    let mut simulation = landing_rights_simulation();
+
+    simulation.run_until(simulation.environment.timestamp + UNDO_GRACE_PERIOD.as_millis() + 1);
+
+    assert!(matches!(
+        simulation.undo(),
+        Err(UndoError::GracePeriodExpired)
+    ));
+}
+
+#[test]
+fn test_undo_is_replayed() {
+    let mut simulation = landing_rights_simulation();
+    simulation.undo().unwrap();
+    simulation.tick();
+
+    let replay = Replay::new(
+        simulation.environment.config.clone(),
+        simulation.command_history.clone(),
+    );
+    let replay: Replay = serde_yaml::from_str(&serde_yaml::to_string(&replay).unwrap()).unwrap();
+
+    let mut replayed = Simulation::new(
+        replay.initial_config.clone(),
+        Box::new(StringBasedWorldData::default()),
+    );
+    for command in replay.command_history {
+        replayed.add_command_timed(command);
+    }
+    replayed.run_until(simulation.environment.timestamp);
+
+    assert!(replayed.environment.landing_rights.is_empty());
+    assert_eq!(
+        replayed.environment.company_finances.income,
+        simulation.environment.company_finances.income
+    );
+    assert_eq!(
+        replayed.environment.company_finances.expenses,
+        simulation.environment.company_finances.expenses
+    );
+}
+
+#[test]
+fn test_replay_verify_is_deterministic() {
+    let mut simulation = scheduled_simulation();
+    simulation.run_until(3 * DAY.as_millis() + 60_000);
+
+    let replay = Replay::from_simulation(&simulation);
+    let replay: Replay = serde_yaml::from_str(&serde_yaml::to_string(&replay).unwrap()).unwrap();
+    assert_eq!(replay.checkpoints.len(), 4);
+
+    let verified = replay
+        .verify(Box::new(StringBasedWorldData::default()))
+        .unwrap();
+    assert_eq!(
+        verified.environment.timestamp,
+        simulation.environment.timestamp
+    );
+
+    let mut tampered = replay.clone();
+    tampered.checkpoints[1].cash += 1_000.0;
+    let Err(ReplayError::Divergence(divergence)) =
+        tampered.verify(Box::new(StringBasedWorldData::default()))
+    else {
+        panic!("Expected a divergence");
+    };
+    assert_eq!(divergence.timestamp, replay.checkpoints[1].timestamp);
+    assert_eq!(divergence.field, "cash");
+}
+
+#[test]
+fn test_take_control_of_replay() {
+    let mut simulation = scheduled_simulation();
+    simulation.run_until(60_000);
+    assert!(!simulation.commands.is_empty());
+
+    simulation.take_control();
+    simulation.run_until(DAY.as_millis());
+
+    assert!(simulation.commands.is_empty());
+    assert!(simulation.environment.planes.is_empty());
+    assert_eq!(simulation.command_history.len(), 2);
+
+    let parent = Replay::new(Default::default(), vec![]);
+    let replay = Replay {
+        branch: Some(ReplayBranch {
+            timestamp: 60_000,
+            parent: Box::new(parent),
+        }),
+        ..Replay::from_simulation(&simulation)
+    };
+    let replay: Replay = serde_yaml::from_str(&serde_yaml::to_string(&replay).unwrap()).unwrap();
+    let branch = replay.branch.unwrap();
+    assert_eq!(branch.timestamp, 60_000);
+    assert!(branch.parent.branch.is_none());
+    assert_eq!(replay.command_history.len(), 2);
+}
+
+#[test]
+fn test_replay_keeps_starting_assets() {
+    let planes_config: PlanesConfig =
+        serde_yaml::from_str(include_str!("../../assets/planes.yaml")).unwrap();
+    let mut simulation = Simulation::new(
+        EnvironmentConfig::default(),
+        Box::new(StringBasedWorldData::default()),
+    );
+    let base_id = CreateBaseCommand::generate_id();
+    simulation
+        .add_starting_assets(vec![
+            Box::new(CreateBaseCommand {
+                base_id,
+                aerodrome: Aerodrome::frankfurt(),
+            }),
+            Box::new(BuyPlaneCommand {
+                plane_id: BuyPlaneCommand::generate_id(),
+                plane_type: planes_config.planes[0].clone(),
+                home_base_id: base_id,
+            }),
+        ])
+        .unwrap();
+    assert!(simulation.command_history.is_empty());
+    assert_eq!(
+        simulation.environment.company_finances.cash(0),
+        EnvironmentConfig::default().start_capital
+    );
+    simulation.run_until(DAY.as_millis() + 60_000);
+
+    let replay = Replay::from_simulation(&simulation);
+    let replay: Replay = serde_yaml::from_str(&serde_yaml::to_string(&replay).unwrap()).unwrap();
+    assert_eq!(replay.starting_assets.len(), 2);
+
+    let verified = replay
+        .verify(Box::new(StringBasedWorldData::default()))
+        .unwrap();
+    assert_eq!(verified.environment.bases.len(), 1);
+    assert_eq!(verified.environment.planes.len(), 1);
+}
+
+#[test]
+fn test_compact_history_is_opt_in() {
+    let mut simulation = scheduled_simulation();
+    let mut compacted = scheduled_simulation();
+    compacted.environment.config.compact_history = true;
+
+    let end = 3 * DAY.as_millis();
+    simulation.run_until(end);
+    compacted.run_until(end);
+
+    let width = METRICS_BUCKET_WIDTH.as_millis();
+    let expenses = simulation.environment.company_finances.expenses.entries();
+    assert!(expenses.iter().any(|entry| entry.0 % width != 0));
+    let compacted_expenses = compacted.environment.company_finances.expenses.entries();
+    assert!(compacted_expenses
+        .iter()
+        .filter(|entry| entry.0 <= end - METRICS_RETENTION.as_millis())
+        .all(|entry| entry.0 % width == 0));
+    assert!(compacted_expenses.len() < expenses.len());
+
+    for timestamp in (0..=end).step_by(width as usize) {
+        let cash = simulation.environment.company_finances.cash(timestamp);
+        let compacted_cash = compacted.environment.company_finances.cash(timestamp);
+        assert!((cash - compacted_cash).abs() < 1e-6);
+    }
+}
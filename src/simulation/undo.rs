@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use crate::model::{
+    commands::{Command, CommandError},
+    Timestamp,
+};
+
+/// An executed command together with the command reverting it.
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub executed_at: Timestamp,
+    pub command: Box<dyn Command>,
+    pub inverse: Box<dyn Command>,
+}
+
+#[derive(Debug, Error)]
+pub enum UndoError {
+    #[error("Nothing to undo")]
+    NothingToUndo,
+    #[error("Nothing to redo")]
+    NothingToRedo,
+    #[error("The last command can no longer be undone")]
+    GracePeriodExpired,
+    #[error(transparent)]
+    Command(#[from] CommandError),
+}
+
+/// Undo or redo requested by the player, applied at the start of the next tick.
+#[derive(Debug, Clone)]
+pub(crate) enum HistoryOperation {
+    Undo(UndoEntry),
+    Redo(UndoEntry),
+}
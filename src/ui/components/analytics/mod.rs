@@ -1,15 +1,46 @@
 use bevy::prelude::Res;
 use bevy_egui::egui;
-use egui_plot::{Line, Plot};
+use egui_plot::{Bar, BarChart, Line, Plot};
 
 use crate::{
     game::GameResource,
     model::analytics::{
-        calculate_average_profit_per_flight, calculate_cash_history,
-        calculate_total_flight_distance, calculate_transported_passengers,
+        calculate_average_profit_per_flight, calculate_cash_history, calculate_kpi_history,
+        calculate_net_worth_history, calculate_route_demand, calculate_total_flight_distance,
+        calculate_transported_passengers, Kpis, ProfitabilityReport,
     },
 };
 
+const KPI_WINDOW: u128 = 7 * 24 * 60 * 60 * 1000;
+
+/// KPIs shown in the dashboard with their name and value format.
+const KPIS: [(&str, fn(&Kpis) -> f64, fn(f64) -> String); 8] = [
+    ("ASK", |kpis| kpis.ask, |value| format!("{:.0}", value)),
+    ("RPK", |kpis| kpis.rpk, |value| format!("{:.0}", value)),
+    (
+        "Load Factor",
+        |kpis| kpis.load_factor,
+        |value| format!("{:.0}%", value * 100.0),
+    ),
+    ("RASK", |kpis| kpis.rask, |value| format!("$ {:.3}", value)),
+    ("CASK", |kpis| kpis.cask, |value| format!("$ {:.3}", value)),
+    (
+        "Yield",
+        |kpis| kpis.yield_per_rpk,
+        |value| format!("$ {:.3}", value),
+    ),
+    (
+        "Block Hours per Aircraft Day",
+        |kpis| kpis.block_hours_per_aircraft_day,
+        |value| format!("{:.1} h", value),
+    ),
+    (
+        "On-Time Performance",
+        |kpis| kpis.on_time_performance,
+        |value| format!("{:.0}%", value * 100.0),
+    ),
+];
+
 pub fn company_finances(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
     let environment = &game_resource.simulation.environment;
     ui.vertical(|ui| {
@@ -17,6 +48,10 @@ pub fn company_finances(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
             "Cash: ${:.2}",
             environment.company_finances.cash(environment.timestamp)
         ));
+        ui.label(format!(
+            "Net Worth: ${:.2}",
+            environment.metrics.net_worth(environment.timestamp)
+        ));
         ui.label(format!("Planes: {}", environment.planes.len()));
         ui.label(format!(
             "Total Income: ${:.2}",
@@ -61,6 +96,30 @@ pub fn cash_history(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
     });
 }
 
+pub fn net_worth_history(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
+    let net_worth_history = calculate_net_worth_history(&game_resource.simulation.environment);
+
+    let net_worth_history_for_plot: Vec<[f64; 2]> = net_worth_history
+        .into_iter()
+        .map(|(timestamp, net_worth)| [timestamp as f64, net_worth])
+        .collect();
+
+    let net_worth_history_line = Line::new(net_worth_history_for_plot);
+
+    let net_worth_history_plot = Plot::new("Net Worth History")
+        .view_aspect(2.0)
+        .label_formatter(|name, value| {
+            if !name.is_empty() {
+                format!("{}: $ {:.*}", name, 2, value.y)
+            } else {
+                "".to_owned()
+            }
+        });
+    net_worth_history_plot.show(ui, |ui| {
+        ui.line(net_worth_history_line);
+    });
+}
+
 pub fn total_flight_distance(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
     let flight_distance_history =
         calculate_total_flight_distance(&game_resource.simulation.environment);
@@ -144,3 +203,153 @@ pub fn average_profit_per_flight(ui: &mut egui::Ui, game_resource: &Res<GameReso
         ui.line(average_profit_history_line);
     });
 }
+
+pub fn route_demand(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
+    let route_demand = calculate_route_demand(&game_resource.simulation.environment);
+    if route_demand.is_empty() {
+        ui.label("No flights yet.");
+        return;
+    }
+
+    egui::Grid::new("Route Demand")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("Route");
+            ui.label("Flights");
+            ui.label("Load Factor");
+            ui.label("Season");
+            ui.label("Weekday");
+            ui.label("Time of Day");
+            ui.end_row();
+
+            for route in route_demand {
+                ui.label(route.route);
+                ui.label(route.flights.to_string());
+                ui.label(format!("{:.0}%", route.load_factor * 100.0));
+                ui.label(format!("{:.2}", route.demand.season));
+                ui.label(format!("{:.2}", route.demand.weekday));
+                ui.label(format!("{:.2}", route.demand.time_of_day));
+                ui.end_row();
+            }
+        });
+}
+
+pub fn profitability_table(ui: &mut egui::Ui, id: &str, reports: &[ProfitabilityReport]) {
+    if reports.is_empty() {
+        ui.label("No flights yet.");
+        return;
+    }
+
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        ui.label("Name");
+        ui.label("Flights");
+        ui.label("Revenue");
+        ui.label("Costs");
+        ui.label("Margin");
+        ui.label("Load Factor");
+        ui.label("Hours");
+        ui.end_row();
+
+        for report in reports {
+            ui.label(&report.name);
+            ui.label(report.flights.to_string());
+            ui.label(format!("${:.2}", report.revenue));
+            ui.label(format!("${:.2}", report.costs));
+            ui.label(format!("{:.0}%", report.margin() * 100.0));
+            ui.label(format!("{:.0}%", report.load_factor() * 100.0));
+            ui.label(format!("{:.1}", report.utilisation_hours));
+            ui.end_row();
+        }
+    });
+}
+
+pub fn profit_chart(ui: &mut egui::Ui, id: &str, reports: &[ProfitabilityReport]) {
+    let bars: Vec<Bar> = reports
+        .iter()
+        .enumerate()
+        .map(|(index, report)| Bar::new(index as f64, report.profit()).name(&report.name))
+        .collect();
+
+    let profit_plot = Plot::new(id)
+        .view_aspect(2.0)
+        .label_formatter(|name, value| {
+            if !name.is_empty() {
+                format!("{}: $ {:.*}", name, 2, value.y)
+            } else {
+                "".to_owned()
+            }
+        });
+    profit_plot.show(ui, |ui| {
+        ui.bar_chart(BarChart::new(bars));
+    });
+}
+
+/// Current KPIs of the last seven days with their history.
+pub fn kpi_dashboard(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
+    let history = calculate_kpi_history(&game_resource.simulation.environment, KPI_WINDOW, 100);
+    let Some((_, current)) = history.last() else {
+        ui.label("No flights yet.");
+        return;
+    };
+
+    for (name, value, format) in KPIS {
+        egui::CollapsingHeader::new(format!("{}: {}", name, format(value(current))))
+            .id_source(name)
+            .show(ui, |ui| {
+                let line = Line::new(
+                    history
+                        .iter()
+                        .map(|(timestamp, kpis)| [*timestamp as f64, value(kpis)])
+                        .collect::<Vec<[f64; 2]>>(),
+                );
+                Plot::new(name)
+                    .view_aspect(2.0)
+                    .label_formatter(move |name, point| {
+                        if !name.is_empty() {
+                            format!("{}: {}", name, format(point.y))
+                        } else {
+                            "".to_owned()
+                        }
+                    })
+                    .show(ui, |ui| {
+                        ui.line(line);
+                    });
+            });
+    }
+}
+
+/// Small trend lines of the KPIs without axes.
+pub fn kpi_sparklines(ui: &mut egui::Ui, game_resource: &Res<GameResource>) {
+    let history = calculate_kpi_history(&game_resource.simulation.environment, KPI_WINDOW, 20);
+    let Some((_, current)) = history.last() else {
+        return;
+    };
+
+    egui::Grid::new("KPI Sparklines").show(ui, |ui| {
+        for (name, value, format) in KPIS {
+            ui.label(name);
+            ui.label(format(value(current)));
+            let line = Line::new(
+                history
+                    .iter()
+                    .map(|(timestamp, kpis)| [*timestamp as f64, value(kpis)])
+                    .collect::<Vec<[f64; 2]>>(),
+            );
+            Plot::new(format!("{} Sparkline", name))
+                .width(80.0)
+                .height(16.0)
+                .show_axes(false)
+                .show_grid(false)
+                .show_x(false)
+                .show_y(false)
+                .allow_drag(false)
+                .allow_zoom(false)
+                .allow_scroll(false)
+                .allow_boxed_zoom(false)
+                .show(ui, |ui| {
+                    ui.line(line);
+                });
+            ui.end_row();
+        }
+    });
+}
@@ -0,0 +1,42 @@
+use bevy_egui::egui::{Button, Response, Ui, Widget};
+
+use crate::model::{commands::Command, Environment};
+
+/// Button for a command, disabled with the violated rules as tooltip while the
+/// command would fail.
+pub struct CommandButton<'a> {
+    text: &'a str,
+    command: &'a dyn Command,
+    environment: &'a Environment,
+}
+
+impl<'a> CommandButton<'a> {
+    pub fn new(text: &'a str, command: &'a dyn Command, environment: &'a Environment) -> Self {
+        Self {
+            text,
+            command,
+            environment,
+        }
+    }
+}
+
+impl<'a> Widget for CommandButton<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        match self.command.validate(self.environment) {
+            Ok(preview) => ui.add(Button::new(self.text)).on_hover_text(format!(
+                "Cost: ${:.2}\nCash after: ${:.2}",
+                preview.cost, preview.cash_after
+            )),
+            Err(error) => {
+                let reasons = error
+                    .violations()
+                    .iter()
+                    .map(|violation| violation.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                ui.add_enabled(false, Button::new(self.text))
+                    .on_disabled_hover_text(reasons)
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use crate::game::GameResource;
+use crate::model::analytics::{ExportData, ExportFormat};
+use bevy::prelude::{ResMut, Resource};
+use bevy_egui::egui;
+use strum::IntoEnumIterator;
+
+#[derive(Resource, Default)]
+pub struct UiInputExport {
+    pub format: ExportFormat,
+}
+
+pub fn export_analytics(
+    ui: &mut egui::Ui,
+    game_resource: &GameResource,
+    mut export_input: ResMut<UiInputExport>,
+) {
+    ui.horizontal(|ui| {
+        ui.label("Format:");
+        for format in ExportFormat::iter() {
+            ui.selectable_value(&mut export_input.format, format, format.to_string());
+        }
+    });
+
+    let format = export_input.format;
+    ui.horizontal_wrapped(|ui| {
+        for data in ExportData::iter() {
+            if ui.button(data.to_string()).clicked() {
+                export(game_resource, data, format);
+            }
+        }
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export(game_resource: &GameResource, data: ExportData, format: ExportFormat) {
+    use crate::model::analytics::export_to_file;
+
+    if let Err(e) = export_to_file(
+        &data.file_name(format),
+        &game_resource.simulation.environment,
+        data,
+        format,
+    ) {
+        println!("Failed to export {}: {:?}", data, e);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn export(game_resource: &GameResource, data: ExportData, format: ExportFormat) {
+    use crate::{model::analytics::export_to_string, ui::components::save_replay::download};
+
+    match export_to_string(&game_resource.simulation.environment, data, format) {
+        Ok(content) => download(&data.file_name(format), &content),
+        Err(e) => println!("Failed to export {}: {:?}", data, e),
+    }
+}
@@ -0,0 +1,62 @@
+use bevy_egui::egui;
+
+use crate::{game::HighScoresResource, model::world_data::WorldDataSource};
+
+const HOUR: u128 = 60 * 60 * 1000;
+
+pub fn high_scores_table(
+    ui: &mut egui::Ui,
+    high_scores: &mut HighScoresResource,
+    data_source: &WorldDataSource,
+) {
+    if high_scores.high_scores.scores.is_empty() {
+        ui.label("No high scores yet.");
+        return;
+    }
+
+    let mut verify = None;
+    egui::Grid::new("High Scores").striped(true).show(ui, |ui| {
+        ui.label("#");
+        ui.label("Pilot");
+        ui.label("Level");
+        ui.label("Result");
+        ui.label("Days");
+        ui.label("Net Worth");
+        ui.label("Passengers");
+        ui.label("Replay");
+        ui.end_row();
+
+        for (rank, score) in high_scores.high_scores.scores.iter().enumerate() {
+            let place = format!("{}", rank + 1);
+            if high_scores.last_rank == Some(rank) {
+                ui.strong(place);
+            } else {
+                ui.label(place);
+            }
+            ui.label(&score.alias);
+            ui.label(&score.level);
+            ui.label(if score.won { "Won" } else { "Lost" });
+            ui.label(format!("{:.1}", score.duration as f64 / (24 * HOUR) as f64));
+            ui.label(format!("${:.2}", score.net_worth));
+            ui.label(score.passengers.to_string());
+            match high_scores.verified.get(&rank) {
+                Some(Ok(())) => {
+                    ui.label("Verified");
+                }
+                Some(Err(error)) => {
+                    ui.label("Invalid").on_hover_text(error);
+                }
+                None => {
+                    if ui.button("Verify").clicked() {
+                        verify = Some(rank);
+                    }
+                }
+            }
+            ui.end_row();
+        }
+    });
+
+    if let Some(rank) = verify {
+        high_scores.verify(rank, data_source);
+    }
+}
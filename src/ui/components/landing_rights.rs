@@ -5,6 +5,7 @@ use bevy_panorbit_camera::PanOrbitCamera;
 use crate::{
     game::{aerodrome::SelectedAerodromeChangeEvent, GameResource},
     model::{commands::SellLandingRightsCommand, LandingRights},
+    ui::components::CommandButton,
 };
 
 #[derive(Default, Resource)]
@@ -67,10 +68,17 @@ pub fn landing_rights_list(
                 pan_orbit.force_update = true;
             }
         }
-        if ui.button("Sell Landing Rights").clicked() {
-            let cmd = SellLandingRightsCommand {
-                landing_rights_id: landing_rights.id.into(),
-            };
+        let cmd = SellLandingRightsCommand {
+            landing_rights_id: landing_rights.id,
+        };
+        if ui
+            .add(CommandButton::new(
+                "Sell Landing Rights",
+                &cmd,
+                &game_resource.simulation.environment,
+            ))
+            .clicked()
+        {
             game_resource.simulation.add_command(Box::new(cmd));
             landing_rights_input.selected_landing_rights = None;
         }
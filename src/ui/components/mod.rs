@@ -1,10 +1,15 @@
 pub mod analytics;
 pub mod bases;
+pub mod command_button;
 pub mod config;
+pub mod export_analytics;
 pub mod flight;
+pub mod high_scores;
 pub mod landing_rights;
+pub mod objectives;
 pub mod planes;
 pub mod save_replay;
 
+pub use command_button::CommandButton;
 pub use flight::Flight;
 pub use planes::{Plane, PlaneType};
@@ -0,0 +1,30 @@
+use bevy_egui::egui::{self, ProgressBar};
+
+use crate::{config::LevelConfig, model::Environment, simulation::DAY};
+
+/// Objectives of the level with the progress made in `environment`.
+pub fn objectives(ui: &mut egui::Ui, level_config: &LevelConfig, environment: &Environment) {
+    for objective in &level_config.objectives {
+        let progress = objective.progress(environment) / objective.target();
+        ui.label(objective.description());
+        ui.add(ProgressBar::new(progress.clamp(0.0, 1.0) as f32).show_percentage());
+    }
+    if let Some(time_limit) = level_config.time_limit() {
+        let day = DAY.as_millis();
+        let days_left = time_limit
+            .saturating_sub(environment.timestamp)
+            .div_ceil(day);
+        ui.label(format!("Days left: {}", days_left));
+    }
+}
+
+/// Objectives and time limit of a level before it is started.
+pub fn level_summary(ui: &mut egui::Ui, level_config: &LevelConfig) {
+    ui.label(&level_config.description);
+    for objective in &level_config.objectives {
+        ui.label(format!("- {}", objective.description()));
+    }
+    if let Some(days) = level_config.time_limit_days {
+        ui.label(format!("Time limit: {} days", days));
+    }
+}
@@ -50,20 +50,28 @@ pub fn buy_plane(
 
                 ui.add(components::PlaneType::new(selected_plane));
 
-                if ui.button("Buy").clicked() {
-                    let home_base_id = game_resource
-                        .simulation
-                        .environment
-                        .bases
-                        .iter()
-                        .find(|base| base.aerodrome.id == selected_aerodrome.id)
-                        .map(|base| base.id);
+                let home_base_id = game_resource
+                    .simulation
+                    .environment
+                    .bases
+                    .iter()
+                    .find(|base| base.aerodrome.id == selected_aerodrome.id)
+                    .map(|base| base.id);
 
-                    let buy_plane = BuyPlaneCommand {
-                        plane_id: BuyPlaneCommand::generate_id(),
-                        plane_type: selected_plane.clone(),
-                        home_base_id: home_base_id.unwrap_or_default(),
-                    };
+                let mut buy_plane = BuyPlaneCommand {
+                    plane_id: 0,
+                    plane_type: selected_plane.clone(),
+                    home_base_id: home_base_id.unwrap_or_default(),
+                };
+                if ui
+                    .add(components::CommandButton::new(
+                        "Buy",
+                        &buy_plane,
+                        &game_resource.simulation.environment,
+                    ))
+                    .clicked()
+                {
+                    buy_plane.plane_id = BuyPlaneCommand::generate_id();
                     game_resource.simulation.add_command(Box::new(buy_plane));
                 }
             }
@@ -16,6 +16,12 @@ impl<'a> Widget for PlaneType<'a> {
     fn ui(self, ui: &mut Ui) -> Response {
         ui.vertical_centered(|ui| {
             ui.heading(&self.plane_type.name);
+            if !self.plane_type.manufacturer.is_empty() {
+                ui.label(format!(
+                    "{} {}",
+                    self.plane_type.manufacturer, self.plane_type.family
+                ));
+            }
             ui.end_row();
 
             ui.separator();
@@ -26,11 +32,23 @@ impl<'a> Widget for PlaneType<'a> {
             ));
             ui.label(format!("Range: {} km", self.plane_type.range));
             ui.label(format!("Speed: {} km/h", self.plane_type.speed));
-            ui.label(format!("Capacity: {} passengers", self.plane_type.seats));
+            let cabin = self.plane_type.cabin();
+            ui.label(format!(
+                "Capacity: {} passengers ({} economy, {} business, {} first)",
+                self.plane_type.seats, cabin.economy, cabin.business, cabin.first
+            ));
             ui.label(format!(
                 "Fuel Consumption: {} L/km",
                 self.plane_type.fuel_consumption_per_km
             ));
+            ui.label(format!(
+                "Minimum Runway: {} m",
+                self.plane_type.min_runway_length
+            ));
+            ui.label(format!(
+                "Turnaround: {} min",
+                self.plane_type.turnaround_time
+            ));
         })
         .response
     }
@@ -54,10 +54,17 @@ pub fn planes_list(
             &game_resource.simulation.environment.bases,
         ));
 
-        if ui.button("Sell Airplane").clicked() {
-            let cmd = SellPlaneCommand {
-                plane_id: airplane.id,
-            };
+        let cmd = SellPlaneCommand {
+            plane_id: airplane.id,
+        };
+        if ui
+            .add(components::CommandButton::new(
+                "Sell Airplane",
+                &cmd,
+                &game_resource.simulation.environment,
+            ))
+            .clicked()
+        {
             game_resource.simulation.add_command(Box::new(cmd));
 
             selected_airplane.airplane = None;
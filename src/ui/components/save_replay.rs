@@ -1,5 +1,4 @@
 use crate::game::GameResource;
-use crate::Replay;
 use bevy::prelude::{Res, ResMut, Resource};
 use bevy_egui::egui;
 #[cfg(target_arch = "wasm32")]
@@ -24,10 +23,7 @@ pub fn save_replay(
     if ui.button("Save").clicked() {
         if !replay_filename.replay_filename.is_empty() {
             // Create Replay struct and save to file.
-            let replay = Replay::new(
-                game_resource.simulation.environment.config.clone(),
-                game_resource.simulation.command_history.clone(),
-            );
+            let replay = game_resource.create_replay();
 
             if let Err(e) = replay.save_to_file(&replay_filename.replay_filename) {
                 println!("Failed to save replay: {:?}", e);
@@ -48,8 +44,6 @@ pub fn save_replay(
     game_resource: Res<GameResource>,
     mut replay_filename: ResMut<UiInputReplayFilename>,
 ) {
-    use web_sys::HtmlAnchorElement;
-
     ui.horizontal(|ui| {
         ui.label("Filename:");
         ui.text_edit_singleline(&mut replay_filename.replay_filename);
@@ -57,30 +51,34 @@ pub fn save_replay(
 
     if ui.button("Save").clicked() {
         if !replay_filename.replay_filename.is_empty() {
-            let replay = Replay::new(
-                game_resource.simulation.environment.config.clone(),
-                game_resource.simulation.command_history.clone(),
-            );
+            let replay = game_resource.create_replay();
 
             let serialized_replay =
                 serde_yaml::to_string(&replay).expect("Failed to serialize replay.");
 
-            let blob = web_sys::Blob::new_with_str_sequence(&js_sys::Array::of1(
-                &wasm_bindgen::JsValue::from_str(&serialized_replay),
-            ))
-            .expect("Failed to create blob.");
-
-            let url = create_object_url(&blob).as_string().unwrap();
-
-            let document = web_sys::window().unwrap().document().unwrap();
-            let link: HtmlAnchorElement = document.create_element("a").unwrap().dyn_into().unwrap();
-            link.set_attribute("href", &url).unwrap();
-            link.set_attribute("download", &replay_filename.replay_filename)
-                .unwrap();
-            link.style().set_property("display", "none").unwrap();
-            document.body().unwrap().append_child(&link).unwrap();
-            link.click();
-            document.body().unwrap().remove_child(&link).unwrap();
+            download(&replay_filename.replay_filename, &serialized_replay);
         }
     }
 }
+
+/// Lets the browser download `content` as a file named `filename`.
+#[cfg(target_arch = "wasm32")]
+pub fn download(filename: &str, content: &str) {
+    use web_sys::HtmlAnchorElement;
+
+    let blob = web_sys::Blob::new_with_str_sequence(&js_sys::Array::of1(
+        &wasm_bindgen::JsValue::from_str(content),
+    ))
+    .expect("Failed to create blob.");
+
+    let url = create_object_url(&blob).as_string().unwrap();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let link: HtmlAnchorElement = document.create_element("a").unwrap().dyn_into().unwrap();
+    link.set_attribute("href", &url).unwrap();
+    link.set_attribute("download", filename).unwrap();
+    link.style().set_property("display", "none").unwrap();
+    document.body().unwrap().append_child(&link).unwrap();
+    link.click();
+    document.body().unwrap().remove_child(&link).unwrap();
+}
@@ -1,5 +1,8 @@
-use crate::game::{GameResource, GameState};
-use crate::simulation::Simulation;
+use crate::config::{LevelOutcome, LossReason};
+use crate::game::{GameResource, GameState, HighScoresResource};
+use crate::model::analytics::calculate_valuation;
+use crate::ui::components::high_scores::high_scores_table;
+use crate::ui::components::objectives::objectives;
 use bevy::prelude::{in_state, App, IntoSystemConfigs, NextState, Plugin, ResMut, Update};
 use bevy_egui::{egui, EguiContexts};
 
@@ -17,37 +20,73 @@ impl Plugin for GameOverScreenPlugin {
 pub fn game_over_screen_system(
     mut contexts: EguiContexts,
     mut game_resources: ResMut<GameResource>,
+    mut high_scores: ResMut<HighScoresResource>,
     mut game_state_next_state: ResMut<NextState<GameState>>,
 ) {
     egui::CentralPanel::default().show(contexts.ctx_mut(), |ui| {
         ui.vertical_centered(|ui| {
             ui.label("");
-            ui.heading("Game Over!");
+            ui.heading(match game_resources.outcome {
+                LevelOutcome::Won => "Level Complete!",
+                _ => "Game Over!",
+            });
+            match game_resources.outcome {
+                LevelOutcome::Lost(LossReason::Bankrupt) => {
+                    ui.label("Your airline ran out of money.");
+                }
+                LevelOutcome::Lost(LossReason::TimeLimitExceeded) => {
+                    ui.label("The time limit of the level is over.");
+                }
+                _ => {}
+            }
             ui.label("");
 
+            let environment = &game_resources.simulation.environment;
+            let valuation = calculate_valuation(environment, environment.timestamp);
             ui.label(format!(
                 "Final Airline Value: ${:.2}",
-                game_resources
-                    .simulation
-                    .environment
-                    .company_finances
-                    .cash(game_resources.simulation.environment.timestamp)
+                valuation.net_worth()
             ));
+            egui::Grid::new("Valuation").show(ui, |ui| {
+                for (name, value) in [
+                    ("Cash", valuation.cash),
+                    ("Aircraft", valuation.aircraft),
+                    ("Bases", valuation.bases),
+                    ("Landing Rights", valuation.landing_rights),
+                    ("Route Goodwill", valuation.goodwill),
+                    ("Debt", -valuation.debt),
+                ] {
+                    ui.label(name);
+                    ui.label(format!("${:.2}", value));
+                    ui.end_row();
+                }
+            });
             ui.label(format!(
                 "Total Planes: {}",
                 game_resources.simulation.environment.planes.len()
             ));
 
+            objectives(
+                ui,
+                &game_resources.level_config,
+                &game_resources.simulation.environment,
+            );
+
+            ui.label("");
+            match high_scores.last_rank {
+                Some(rank) => ui.heading(format!("New High Score! Rank {}", rank + 1)),
+                None => ui.heading("High Scores"),
+            };
+            high_scores_table(ui, &mut high_scores, &game_resources.data_source);
+            ui.label("");
+
             ui.label("Thank you for playing Flyconomy!");
 
             if ui.button("Restart Game").clicked() {
-                game_resources.simulation = Simulation::new(
-                    Default::default(),
-                    #[cfg(not(feature = "web3"))]
-                    Box::new(crate::model::world_data::StringBasedWorldData::default()),
-                    #[cfg(feature = "web3")]
-                    Box::new(crate::model::world_data::web3_world_data::Web3WorldData::default()),
-                );
+                let level = game_resources.level.clone();
+                let level_config = game_resources.level_config.clone();
+                let data_source = game_resources.data_source.clone();
+                *game_resources = GameResource::from_level(level, level_config, data_source);
                 game_state_next_state.set(GameState::Welcome);
             }
             #[cfg(not(target_arch = "wasm32"))]
@@ -4,9 +4,13 @@ use crate::game::GameState;
 use bevy::prelude::*;
 
 mod layout;
+mod replay_controls;
+mod sparklines;
 mod styles;
 mod updates;
 use layout::{despawn_hud, spawn_hud};
+use replay_controls::replay_controls_system;
+use sparklines::kpi_sparklines_system;
 
 use bevy::prelude::Component;
 
@@ -46,6 +50,8 @@ impl Plugin for HudPlugin {
                     update_planes_system,
                     update_income_system,
                     update_expenses_system,
+                    kpi_sparklines_system,
+                    replay_controls_system,
                 )
                     .run_if(in_state(GameState::Playing)),
             )
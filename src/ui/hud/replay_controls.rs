@@ -0,0 +1,106 @@
+use bevy::prelude::ResMut;
+use bevy_egui::{
+    egui::{self, vec2, Align2},
+    EguiContexts,
+};
+
+use crate::{
+    game::GameResource,
+    model::{commands::TypeName, Timestamp},
+    simulation::{Simulation, DEFAULT_TIME_MULTIPLIER},
+};
+
+const DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
+
+/// Timeline, stepping, speed and command list of a played replay.
+pub fn replay_controls_system(mut contexts: EguiContexts, mut game_resource: ResMut<GameResource>) {
+    let game_resource = &mut *game_resource;
+    let Some(replay_player) = &mut game_resource.replay_player else {
+        return;
+    };
+    let simulation: &mut Simulation = &mut game_resource.simulation;
+    let mut take_control = false;
+
+    egui::Window::new("Replay")
+        .anchor(Align2::LEFT_BOTTOM, vec2(10.0, -100.0))
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            let timestamp = simulation.environment.timestamp;
+            let calendar = simulation.environment.config.calendar;
+            ui.label(format!(
+                "{} / {}",
+                calendar.format(timestamp),
+                calendar.format(replay_player.end())
+            ));
+
+            let mut day = timestamp as f64 / DAY;
+            let end = (replay_player.end() as f64 / DAY).max(day);
+            if ui
+                .add(egui::Slider::new(&mut day, 0.0..=end).text("Day"))
+                .changed()
+            {
+                replay_player.seek(simulation, (day * DAY) as Timestamp);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("⏮ Step Back").clicked() {
+                    replay_player.step_back(simulation);
+                }
+                let paused = simulation.time_multiplier == 0.0;
+                if ui
+                    .button(if paused { "▶ Play" } else { "⏸ Pause" })
+                    .clicked()
+                {
+                    simulation.time_multiplier = if paused { DEFAULT_TIME_MULTIPLIER } else { 0.0 };
+                }
+                if ui.button("Step ⏭").clicked() {
+                    replay_player.step(simulation);
+                }
+            });
+
+            let mut speed = simulation.time_multiplier / DEFAULT_TIME_MULTIPLIER;
+            if ui
+                .add(
+                    egui::Slider::new(&mut speed, 0.0..=64.0)
+                        .logarithmic(true)
+                        .text("Speed"),
+                )
+                .changed()
+            {
+                simulation.time_multiplier = speed * DEFAULT_TIME_MULTIPLIER;
+            }
+
+            if ui
+                .button("Take Control")
+                .on_hover_text("Continue playing from here")
+                .clicked()
+            {
+                take_control = true;
+            }
+
+            ui.separator();
+            let current = replay_player.current_command(simulation);
+            let mut selected = None;
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for (index, command) in replay_player.commands().iter().enumerate() {
+                        let label = format!(
+                            "{} {}",
+                            calendar.format(command.timestamp),
+                            command.command.type_name()
+                        );
+                        if ui.selectable_label(current == Some(index), label).clicked() {
+                            selected = Some(command.timestamp);
+                        }
+                    }
+                });
+            if let Some(timestamp) = selected {
+                replay_player.seek(simulation, timestamp);
+            }
+        });
+
+    if take_control {
+        game_resource.take_control();
+    }
+}
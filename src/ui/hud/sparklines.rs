@@ -0,0 +1,17 @@
+use bevy::prelude::Res;
+use bevy_egui::{
+    egui::{self, vec2, Align2},
+    EguiContexts,
+};
+
+use crate::{game::GameResource, ui::components::analytics::kpi_sparklines};
+
+pub fn kpi_sparklines_system(mut contexts: EguiContexts, game_resource: Res<GameResource>) {
+    egui::Window::new("KPIs")
+        .anchor(Align2::CENTER_TOP, vec2(0.0, 100.0))
+        .default_open(false)
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            kpi_sparklines(ui, &game_resource);
+        });
+}
@@ -1,5 +1,5 @@
 use super::{CalendarText, CashText, ExpensesText, IncomeText, PlanesText};
-use crate::{game::GameResource, utils::timestamp_to_calendar_string};
+use crate::game::GameResource;
 use bevy::prelude::*;
 
 pub fn update_calendar_system(
@@ -8,7 +8,7 @@ pub fn update_calendar_system(
 ) {
     for mut text in query.iter_mut() {
         let environment = &game_resource.simulation.environment;
-        text.sections[0].value = timestamp_to_calendar_string(environment.timestamp);
+        text.sections[0].value = environment.config.calendar.format(environment.timestamp);
     }
 }
 
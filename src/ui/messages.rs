@@ -1,5 +1,5 @@
-use crate::{game::GameResource, utils::timestamp_to_calendar_string};
-use bevy::prelude::{App, Plugin, Res, Update};
+use crate::{game::GameResource, simulation::journal::JournalEntryKind};
+use bevy::prelude::{App, Plugin, Res, ResMut, Update};
 use bevy_egui::{egui, EguiContexts};
 
 use super::layouts::{left_bottom_layout, right_bottom_layout};
@@ -15,19 +15,27 @@ impl Plugin for MessagesPlugin {
 pub fn show_error_messages(mut contexts: EguiContexts, game_resource: Res<GameResource>) {
     let elapsed_time = game_resource.simulation.elapsed_time.as_millis();
     let time_multiplier = game_resource.simulation.time_multiplier;
+    let calendar = game_resource.simulation.environment.config.calendar;
 
-    if !game_resource.simulation.error_messages.is_empty() {
+    let mut error_messages = game_resource
+        .simulation
+        .journal
+        .recent()
+        .filter(|entry| entry.kind == JournalEntryKind::Error)
+        .peekable();
+
+    if error_messages.peek().is_some() {
         right_bottom_layout("Error Book").show(contexts.ctx_mut(), |ui| {
             egui::ScrollArea::vertical()
                 .id_source("errors_list")
                 .max_height(300.0)
                 .show(ui, |ui| {
-                    for message in &game_resource.simulation.error_messages {
-                        if elapsed_time - message.0 < (8_000.0 * time_multiplier) as u128 {
+                    for message in error_messages {
+                        if elapsed_time - message.timestamp < (8_000.0 * time_multiplier) as u128 {
                             let text = format!(
                                 "{}, {}",
-                                timestamp_to_calendar_string(message.0),
-                                message.1
+                                calendar.format(message.timestamp),
+                                message.message
                             );
                             ui.label(text);
                         }
@@ -37,27 +45,61 @@ pub fn show_error_messages(mut contexts: EguiContexts, game_resource: Res<GameRe
     }
 }
 
-pub fn show_event_messages(mut contexts: EguiContexts, game_resource: Res<GameResource>) {
-    let elapsed_time = game_resource.simulation.elapsed_time.as_millis();
-    let time_multiplier = game_resource.simulation.time_multiplier;
+pub fn show_event_messages(mut contexts: EguiContexts, mut game_resource: ResMut<GameResource>) {
+    let simulation = &mut game_resource.simulation;
+    let elapsed_time = simulation.elapsed_time.as_millis();
+    let time_multiplier = simulation.time_multiplier;
+    let calendar = simulation.environment.config.calendar;
 
-    if !game_resource.simulation.event_messages.is_empty() {
-        left_bottom_layout("Log Book").show(contexts.ctx_mut(), |ui| {
-            egui::ScrollArea::vertical()
-                .id_source("messages_list")
-                .max_height(300.0)
-                .show(ui, |ui| {
-                    for message in &game_resource.simulation.event_messages {
-                        if elapsed_time - message.0 < (10_000.0 * time_multiplier) as u128 {
-                            let text = format!(
-                                "{}, {}",
-                                timestamp_to_calendar_string(message.0),
-                                message.1
-                            );
-                            ui.label(text);
-                        }
-                    }
-                });
+    let event_messages: Vec<String> = simulation
+        .journal
+        .recent()
+        .filter(|entry| entry.kind != JournalEntryKind::Error)
+        .filter(|entry| elapsed_time - entry.timestamp < (10_000.0 * time_multiplier) as u128)
+        .map(|entry| format!("{}, {}", calendar.format(entry.timestamp), entry.message))
+        .collect();
+    let next_undo = simulation
+        .next_undo()
+        .map(|_| ())
+        .map_err(|e| e.to_string());
+    let next_redo = simulation
+        .next_redo()
+        .map(|_| ())
+        .map_err(|e| e.to_string());
+
+    if event_messages.is_empty() && next_undo.is_err() && next_redo.is_err() {
+        return;
+    }
+
+    left_bottom_layout("Log Book").show(contexts.ctx_mut(), |ui| {
+        ui.horizontal(|ui| {
+            if history_button(ui, "Undo", &next_undo).clicked() {
+                if let Err(error) = simulation.undo() {
+                    log::error!("Undo failed: {}", error);
+                }
+            }
+            if history_button(ui, "Redo", &next_redo).clicked() {
+                if let Err(error) = simulation.redo() {
+                    log::error!("Redo failed: {}", error);
+                }
+            }
         });
+        egui::ScrollArea::vertical()
+            .id_source("messages_list")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for message in event_messages {
+                    ui.label(message);
+                }
+            });
+    });
+}
+
+fn history_button(ui: &mut egui::Ui, text: &str, state: &Result<(), String>) -> egui::Response {
+    match state {
+        Ok(()) => ui.button(text),
+        Err(reason) => ui
+            .add_enabled(false, egui::Button::new(text))
+            .on_disabled_hover_text(reason),
     }
 }
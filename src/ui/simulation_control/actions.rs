@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use bevy::{
     prelude::{NextState, Query, ResMut},
     ui::Interaction,
@@ -54,11 +52,9 @@ pub fn skip_button_system(
             Interaction::Pressed => {
                 game_resource.simulation.time_multiplier = DEFAULT_TIME_MULTIPLIER;
 
-                while game_resource.simulation.commands.len() > 0 {
-                    game_resource.simulation.update(Duration::from_millis(20));
-                }
+                game_resource.simulation.advance_to_next_event();
 
-                simulation_control.action = SimulationControlAction::SkipToEnd;
+                simulation_control.action = SimulationControlAction::SkipToNextEvent;
             }
             _ => {}
         }
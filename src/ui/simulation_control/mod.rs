@@ -23,7 +23,7 @@ pub enum SimulationControlAction {
     #[default]
     Play,
     SpeedUp,
-    SkipToEnd,
+    SkipToNextEvent,
     Pause,
 }
 
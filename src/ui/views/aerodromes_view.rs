@@ -1,10 +1,11 @@
 use crate::game::aerodrome::{SelectedAerodrome, SelectedAerodromeChangeEvent};
 use crate::game::{GameResource, GameState};
 use crate::model::commands::{BuyLandingRightsCommand, CreateBaseCommand};
-use crate::model::Base;
+use crate::model::{Base, Continent, Flight, PoiCategory};
 use crate::ui::components::bases::bases_list;
 use crate::ui::components::landing_rights::{landing_rights_list, LandingRightsInput};
 use crate::ui::components::planes::{buy_plane, planes_list, SelectedPlane};
+use crate::ui::components::CommandButton;
 use crate::ui::layouts::{left_center_layout, left_layout, right_layout};
 use crate::utils::filter_and_prioritize_aerodromes;
 use bevy::prelude::{
@@ -13,6 +14,7 @@ use bevy::prelude::{
 };
 use bevy_egui::{egui, EguiContexts};
 use bevy_panorbit_camera::PanOrbitCamera;
+use strum::IntoEnumIterator;
 
 use super::UiView;
 
@@ -20,21 +22,19 @@ pub struct AerodromesUiPlugin;
 
 impl Plugin for AerodromesUiPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(UiInput {
-            search_string: String::new(),
-        })
-        .insert_resource(LandingRightsInput::default())
-        .insert_resource(SelectedPlane::default())
-        .add_systems(
-            Update,
-            (selected_aerodrome_info_ui_system,).run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            (aerodromes_ui_system, player_ownership_info_ui)
-                .run_if(in_state(GameState::Playing))
-                .run_if(in_state(UiView::Aerodromes)),
-        );
+        app.insert_resource(UiInput::default())
+            .insert_resource(LandingRightsInput::default())
+            .insert_resource(SelectedPlane::default())
+            .add_systems(
+                Update,
+                (selected_aerodrome_info_ui_system,).run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (aerodromes_ui_system, player_ownership_info_ui)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(in_state(UiView::Aerodromes)),
+            );
     }
 }
 
@@ -51,11 +51,31 @@ pub fn aerodromes_ui_system(
         .show(contexts.ctx_mut(), |ui| {
             ui.label("Available Aerodromes:");
             ui.text_edit_singleline(&mut search_input.search_string);
+            egui::ComboBox::from_id_source("aerodromes_region")
+                .selected_text(
+                    search_input
+                        .continent
+                        .map_or("All Regions".to_string(), |continent| continent.to_string()),
+                )
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut search_input.continent, None, "All Regions");
+                    for continent in Continent::iter() {
+                        ui.selectable_value(
+                            &mut search_input.continent,
+                            Some(continent),
+                            continent.to_string(),
+                        );
+                    }
+                });
 
+            let continent = search_input.continent;
             for aerodrome in
                 filter_and_prioritize_aerodromes(&aerodromes, &search_input.search_string)
+                    .into_iter()
+                    .filter(|aerodrome| continent.map_or(true, |c| aerodrome.continent() == c))
             {
-                if ui.selectable_label(false, &aerodrome.name).clicked() {
+                let label = format!("{} ({})", aerodrome.name, aerodrome.country);
+                if ui.selectable_label(false, label).clicked() {
                     ev_selected_aerodrome_change
                         .send(SelectedAerodromeChangeEvent(aerodrome.clone()));
                     let alpha = (90.0 + aerodrome.lon).to_radians();
@@ -74,6 +94,7 @@ pub fn aerodromes_ui_system(
 #[derive(Resource, Default)]
 pub struct UiInput {
     pub search_string: String,
+    pub continent: Option<Continent>,
 }
 fn selected_aerodrome_info_ui_system(
     mut contexts: EguiContexts,
@@ -114,11 +135,27 @@ fn selected_aerodrome_info_ui_system(
                     pan_orbit.force_update = true;
                 }
             }
+            ui.label(format!(
+                "{}, {}",
+                selected_aerodrome.city, selected_aerodrome.country
+            ));
+            ui.label(format!(
+                "IATA: {}, ICAO: {}",
+                selected_aerodrome.iata.as_deref().unwrap_or("-"),
+                selected_aerodrome.icao.as_deref().unwrap_or("-")
+            ));
             ui.label(format!("Latitude: {:.4}", selected_aerodrome.lat));
             ui.label(format!("Longitude: {:.4}", selected_aerodrome.lon));
             if let Some(passengers) = selected_aerodrome.passengers {
                 ui.label(format!("Passengers: {}", passengers));
             }
+            ui.label(format!("Elevation: {:.0} m", selected_aerodrome.altitude));
+            if let Some(runway_length) = selected_aerodrome.runway_length {
+                ui.label(format!("Runway: {} m", runway_length));
+            }
+            if let Some(utc_offset) = selected_aerodrome.utc_offset {
+                ui.label(format!("Time Zone: UTC{:+}", utc_offset));
+            }
 
             let interest_score_5 = 1.0 + selected_aerodrome.interest_score as f32 * 4.0;
 
@@ -129,12 +166,48 @@ fn selected_aerodrome_info_ui_system(
 
             ui.add(progress_bar);
 
+            ui.collapsing("Interest by Category", |ui| {
+                for category in PoiCategory::iter() {
+                    let score = selected_aerodrome.category_scores.get(category);
+                    ui.add(
+                        egui::ProgressBar::new(score).text(format!("{} {:.2}", category, score)),
+                    );
+                }
+
+                let demand = game_resource
+                    .simulation
+                    .environment
+                    .config
+                    .interest
+                    .travel_demand(std::slice::from_ref(selected_aerodrome));
+                ui.label(format!(
+                    "Travellers: mostly {} ({:.0}% business)",
+                    demand.purpose(),
+                    demand.business_share() * 100.0
+                ));
+            });
+
+            ui.collapsing("Nearest Aerodromes", |ui| {
+                let nearest = game_resource
+                    .simulation
+                    .world_data_gateway
+                    .nearest_aerodromes(selected_aerodrome.lat, selected_aerodrome.lon, 6);
+                for aerodrome in nearest.iter().filter(|a| a.id != selected_aerodrome.id) {
+                    ui.label(format!(
+                        "{} ({:.0} km)",
+                        aerodrome.name,
+                        Flight::calculate_distance_between(selected_aerodrome, aerodrome)
+                    ));
+                }
+            });
+
             ui.separator();
 
             if is_base {
                 ui.label("This is one of your bases.");
                 if let Some(base) = base {
                     ui.label("Airplanes at this base:");
+                    let mut max_range: f32 = 0.0;
                     for airplane_id in &base.airplane_ids {
                         let airplane = game_resource
                             .simulation
@@ -147,8 +220,24 @@ fn selected_aerodrome_info_ui_system(
                                 "Airplane ID: {}, Type: {}",
                                 airplane.id, airplane.plane_type.name
                             ));
+                            max_range = max_range.max(airplane.plane_type.range);
                         }
                     }
+                    if max_range > 0.0 {
+                        let in_range = game_resource
+                            .simulation
+                            .world_data_gateway
+                            .aerodromes_within(
+                                selected_aerodrome.lat,
+                                selected_aerodrome.lon,
+                                max_range as f64,
+                            )
+                            .len();
+                        ui.label(format!(
+                            "Aerodromes within range of your planes: {}",
+                            in_range.saturating_sub(1)
+                        ));
+                    }
 
                     ui.separator();
 
@@ -166,19 +255,32 @@ fn selected_aerodrome_info_ui_system(
                 ));
                 ui.label("You do not have a base at this aerodrome.");
 
-                if ui.button("Create Base").clicked() {
-                    let buy_plane = CreateBaseCommand {
+                if ui
+                    .add(CommandButton::new("Create Base", &buy_command, environment))
+                    .clicked()
+                {
+                    let create_base = CreateBaseCommand {
                         base_id: CreateBaseCommand::generate_id(),
                         aerodrome: selected_aerodrome.clone(),
                     };
-                    game_resource.simulation.add_command(Box::new(buy_plane));
+                    game_resource.simulation.add_command(Box::new(create_base));
                 }
-                if ui.button("Buy Landing Rights").clicked() {
-                    let buy_plane = BuyLandingRightsCommand {
-                        landing_rights_id: BuyLandingRightsCommand::generate_id(),
-                        aerodrome: selected_aerodrome.clone(),
-                    };
-                    game_resource.simulation.add_command(Box::new(buy_plane));
+                let mut buy_landing_rights = BuyLandingRightsCommand {
+                    landing_rights_id: 0,
+                    aerodrome: selected_aerodrome.clone(),
+                };
+                if ui
+                    .add(CommandButton::new(
+                        "Buy Landing Rights",
+                        &buy_landing_rights,
+                        &game_resource.simulation.environment,
+                    ))
+                    .clicked()
+                {
+                    buy_landing_rights.landing_rights_id = BuyLandingRightsCommand::generate_id();
+                    game_resource
+                        .simulation
+                        .add_command(Box::new(buy_landing_rights));
                 }
             }
         });
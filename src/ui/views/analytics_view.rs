@@ -1,15 +1,18 @@
 use crate::{
     game::{GameResource, GameState},
+    model::analytics::{aircraft_reports, base_reports, route_reports, ReportPeriod},
     ui::{
         components::analytics::{
-            average_profit_per_flight, cash_history, company_finances, total_flight_distance,
-            transported_passengers,
+            average_profit_per_flight, cash_history, company_finances, kpi_dashboard,
+            net_worth_history, profit_chart, profitability_table, route_demand,
+            total_flight_distance, transported_passengers,
         },
-        layouts::{left_layout, right_layout},
+        layouts::{left_bottom_layout, left_layout, right_bottom_layout, right_layout},
     },
 };
-use bevy::prelude::{in_state, App, IntoSystemConfigs, Plugin, Res, Update};
+use bevy::prelude::{in_state, App, IntoSystemConfigs, Plugin, Res, ResMut, Resource, Update};
 use bevy_egui::{egui, EguiContexts};
+use strum::IntoEnumIterator;
 
 use super::UiView;
 
@@ -17,9 +20,14 @@ pub struct AnalyticsViewPlugin;
 
 impl Plugin for AnalyticsViewPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
+        app.insert_resource(ReportsInput::default()).add_systems(
             Update,
-            (company_info_system, flight_analytics_system)
+            (
+                company_info_system,
+                flight_analytics_system,
+                profitability_reports_system,
+                kpi_dashboard_system,
+            )
                 .run_if(in_state(GameState::Playing))
                 .run_if(in_state(UiView::Analytics)),
         );
@@ -35,6 +43,11 @@ pub fn company_info_system(mut contexts: EguiContexts, game_resource: Res<GameRe
             .show(ui, |ui| {
                 cash_history(ui, &game_resource);
             });
+        egui::CollapsingHeader::new("Net Worth History")
+            .default_open(false)
+            .show(ui, |ui| {
+                net_worth_history(ui, &game_resource);
+            });
         egui::CollapsingHeader::new("Average Profit Per Flight")
             .default_open(true)
             .show(ui, |ui| {
@@ -55,5 +68,56 @@ pub fn flight_analytics_system(mut contexts: EguiContexts, game_resource: Res<Ga
             .show(ui, |ui| {
                 transported_passengers(ui, &game_resource);
             });
+        egui::CollapsingHeader::new("Route Demand")
+            .default_open(true)
+            .show(ui, |ui| {
+                route_demand(ui, &game_resource);
+            });
     });
 }
+
+#[derive(Resource, Default)]
+pub struct ReportsInput {
+    pub period: ReportPeriod,
+}
+
+pub fn profitability_reports_system(
+    mut contexts: EguiContexts,
+    mut reports_input: ResMut<ReportsInput>,
+    game_resource: Res<GameResource>,
+) {
+    let environment = &game_resource.simulation.environment;
+    right_bottom_layout("Profitability")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::ComboBox::from_id_source("reports_period")
+                .selected_text(reports_input.period.to_string())
+                .show_ui(ui, |ui| {
+                    for period in ReportPeriod::iter() {
+                        ui.selectable_value(&mut reports_input.period, period, period.to_string());
+                    }
+                });
+
+            let period = reports_input.period;
+            for (title, reports) in [
+                ("Routes", route_reports(environment, period)),
+                ("Aircraft", aircraft_reports(environment, period)),
+                ("Bases", base_reports(environment, period)),
+            ] {
+                egui::CollapsingHeader::new(title)
+                    .default_open(title == "Routes")
+                    .show(ui, |ui| {
+                        profit_chart(ui, &format!("{} Profit", title), &reports);
+                        profitability_table(ui, &format!("{} Profitability", title), &reports);
+                    });
+            }
+        });
+}
+
+pub fn kpi_dashboard_system(mut contexts: EguiContexts, game_resource: Res<GameResource>) {
+    left_bottom_layout("KPI Dashboard")
+        .default_open(false)
+        .show(contexts.ctx_mut(), |ui| {
+            kpi_dashboard(ui, &game_resource);
+        });
+}
@@ -4,6 +4,7 @@ use crate::game::manager::GameManagers;
 use crate::game::GameResource;
 use crate::game::GameState;
 use crate::ui::components::analytics::cash_history;
+use crate::ui::components::objectives::objectives;
 use crate::ui::layouts::left_layout;
 use crate::ui::layouts::right_layout;
 use bevy::prelude::in_state;
@@ -35,6 +36,15 @@ impl Plugin for OfficeViewPlugin {
 pub fn show_cash_history(mut contexts: EguiContexts, game_resource: Res<GameResource>) {
     left_layout("Cash History").show(contexts.ctx_mut(), |ui| {
         cash_history(ui, &game_resource);
+        if !game_resource.level_config.objectives.is_empty() {
+            ui.separator();
+            ui.heading("Objectives");
+            objectives(
+                ui,
+                &game_resource.level_config,
+                &game_resource.simulation.environment,
+            );
+        }
     });
 }
 
@@ -4,7 +4,6 @@ use bevy::prelude::{
 };
 use bevy_egui::{egui, EguiContexts};
 use bevy_panorbit_camera::PanOrbitCamera;
-use chrono::{TimeZone, Utc};
 
 use crate::{
     game::{
@@ -198,18 +197,27 @@ pub fn flight_planning_ui(
                             segment_departure_time: game_resource.simulation.environment.timestamp,
                             arrival_time: None,
                             state: Default::default(),
+                            demand: Default::default(),
                         };
 
                         components::Flight::new(&flight);
 
-                        if ui.button("Plan Flight").clicked() {
-                            let schedule_flight = ScheduleFlightCommand {
-                                flight_id: ScheduleFlightCommand::generate_id(),
-                                airplane: airplane.clone(),
-                                origin_aerodrome,
-                                stopovers,
-                                departure_time: game_resource.simulation.environment.timestamp,
-                            };
+                        let mut schedule_flight = ScheduleFlightCommand {
+                            flight_id: 0,
+                            airplane: airplane.clone(),
+                            origin_aerodrome,
+                            stopovers,
+                            departure_time: game_resource.simulation.environment.timestamp,
+                        };
+                        if ui
+                            .add(components::CommandButton::new(
+                                "Plan Flight",
+                                &schedule_flight,
+                                &game_resource.simulation.environment,
+                            ))
+                            .clicked()
+                        {
+                            schedule_flight.flight_id = ScheduleFlightCommand::generate_id();
                             game_resource
                                 .simulation
                                 .add_command(Box::new(schedule_flight));
@@ -248,11 +256,10 @@ pub fn flight_list_ui(
                         format!("{}, {}", acc, stopover.name)
                     });
 
-                    let date_time = flight.departure_time;
-
-                    let start_of_2000: i64 = 946684800000;
-                    let timestamp: i64 = start_of_2000 + date_time as i64;
-                    let datetime = Utc.timestamp_millis_opt(timestamp).unwrap();
+                    let departure = environment
+                        .config
+                        .calendar
+                        .format_local(flight.departure_time, &flight.origin_aerodrome);
 
                     let is_selected = flight_planning_input
                         .selected_flight
@@ -263,10 +270,7 @@ pub fn flight_list_ui(
                             is_selected,
                             format!(
                                 "Flight ID: {}, From: {}, To: {}, Departure: {}",
-                                flight_id,
-                                from,
-                                to,
-                                datetime.format("%Y-%m-%d %H:%M").to_string()
+                                flight_id, from, to, departure
                             ),
                         )
                         .clicked()
@@ -278,14 +282,22 @@ pub fn flight_list_ui(
 
         if let Some(flight) = &flight_planning_input.selected_flight {
             components::Flight::new(flight);
-            if ui.button("Replicate Flight").clicked() {
-                let new_flight = ScheduleFlightCommand {
-                    flight_id: ScheduleFlightCommand::generate_id(),
-                    airplane: flight.airplane.clone(),
-                    origin_aerodrome: flight.origin_aerodrome.clone(),
-                    stopovers: flight.stopovers.clone(),
-                    departure_time: game_resource.simulation.environment.timestamp,
-                };
+            let mut new_flight = ScheduleFlightCommand {
+                flight_id: 0,
+                airplane: flight.airplane.clone(),
+                origin_aerodrome: flight.origin_aerodrome.clone(),
+                stopovers: flight.stopovers.clone(),
+                departure_time: environment.timestamp,
+            };
+            if ui
+                .add(components::CommandButton::new(
+                    "Replicate Flight",
+                    &new_flight,
+                    environment,
+                ))
+                .clicked()
+            {
+                new_flight.flight_id = ScheduleFlightCommand::generate_id();
                 new_flights.push(Box::new(new_flight));
             }
         }
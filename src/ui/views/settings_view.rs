@@ -3,12 +3,13 @@ use crate::{
     ui::{
         components::{
             config::{identity_alias, style_switch::StyleSwitch},
+            export_analytics::{export_analytics, UiInputExport},
             save_replay::{save_replay, UiInputReplayFilename},
         },
         layouts::left_layout,
     },
 };
-use bevy::prelude::{in_state, App, IntoSystemConfigs, Plugin, ResMut, Resource, Update};
+use bevy::prelude::{in_state, App, IntoSystemConfigs, Plugin, Res, ResMut, Resource, Update};
 use bevy_egui::EguiContexts;
 
 use super::UiView;
@@ -18,6 +19,7 @@ pub struct SettingsViewPlugin;
 impl Plugin for SettingsViewPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(StyleState { is_dark: true });
+        app.insert_resource(UiInputExport::default());
         app.insert_resource(UiInputReplayFilename {
             replay_filename: "last.replay.yaml".to_string(),
         });
@@ -39,6 +41,7 @@ pub fn settings_view_system(
     mut contexts: EguiContexts,
     mut game_resource: ResMut<GameResource>,
     replay_filename: ResMut<UiInputReplayFilename>,
+    export_input: ResMut<UiInputExport>,
     mut style_state: ResMut<StyleState>,
 ) {
     left_layout("Settings").show(contexts.ctx_mut(), |ui| {
@@ -52,6 +55,11 @@ pub fn settings_view_system(
         ));
 
         ui.separator();
-        save_replay(ui, game_resource.into(), replay_filename)
+        let game_resource: Res<GameResource> = game_resource.into();
+        save_replay(ui, Res::clone(&game_resource), replay_filename);
+
+        ui.separator();
+        ui.label("Export:");
+        export_analytics(ui, &game_resource, export_input);
     });
 }
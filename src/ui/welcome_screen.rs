@@ -1,5 +1,8 @@
-use crate::game::GameState;
-use bevy::prelude::{in_state, App, IntoSystemConfigs, NextState, Plugin, ResMut, Update};
+use crate::config::{available_levels, LevelConfig};
+use crate::game::{GameResource, GameState, HighScoresResource};
+use crate::ui::components::high_scores::high_scores_table;
+use crate::ui::components::objectives::level_summary;
+use bevy::prelude::{in_state, App, IntoSystemConfigs, Local, NextState, Plugin, ResMut, Update};
 use bevy_egui::{egui, EguiContexts};
 
 pub struct WelcomeScreenPlugin;
@@ -15,8 +18,12 @@ impl Plugin for WelcomeScreenPlugin {
 
 pub fn welcome_screen_system(
     mut contexts: EguiContexts,
+    mut game_resource: ResMut<GameResource>,
+    mut high_scores: ResMut<HighScoresResource>,
+    mut levels: Local<Option<Vec<(String, LevelConfig)>>>,
     mut game_state_next_state: ResMut<NextState<GameState>>,
 ) {
+    let levels = levels.get_or_insert_with(|| available_levels(&game_resource.data_source));
     egui::CentralPanel::default().show(contexts.ctx_mut(), |ui| {
         ui.vertical_centered(|ui| {
             ui.label("");
@@ -42,9 +49,36 @@ pub fn welcome_screen_system(
             ui.label("- Be aware of your assets. Know the planes you have, the landing rights you possess, and the bases you've established.");
             ui.label("- Every decision has a consequence. Think before you act, strategize, and lead your airline to success!");
 
+            if game_resource.replay.is_none() {
+                ui.label("");
+                ui.heading("Choose a Level");
+                ui.horizontal_wrapped(|ui| {
+                    for (name, level_config) in levels.iter() {
+                        let selected = game_resource.level == *name;
+                        if ui
+                            .selectable_label(selected, &level_config.name)
+                            .clicked()
+                            && !selected
+                        {
+                            *game_resource = GameResource::from_level(
+                                name.clone(),
+                                level_config.clone(),
+                                game_resource.data_source.clone(),
+                            );
+                        }
+                    }
+                });
+                level_summary(ui, &game_resource.level_config);
+                ui.label("");
+            }
+
             if ui.button("Dive In & Start Building Your Airline Empire!").clicked() {
                 game_state_next_state.set(GameState::Playing);
             }
+
+            ui.label("");
+            ui.heading("High Scores");
+            high_scores_table(ui, &mut high_scores, &game_resource.data_source);
         });
     });
 }
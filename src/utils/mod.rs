@@ -1,14 +1,14 @@
-use chrono::{TimeZone, Utc};
 use strsim::normalized_levenshtein;
 
-use crate::model::{Aerodrome, Timestamp};
-
-const START_OF_2000: i64 = 946684800000;
+use crate::model::{Aerodrome, GameCalendar, Timestamp};
 
+/// Formats the month, day and time of the default calendar, prefer
+/// [`GameCalendar::format`] with the calendar of the level.
 pub fn timestamp_to_calendar_string(timestamp: Timestamp) -> String {
-    let timestamp: i64 = START_OF_2000 + timestamp as i64;
-    let datetime = Utc.timestamp_millis_opt(timestamp).unwrap();
-    datetime.format("%m-%d %H:%M").to_string()
+    GameCalendar::default()
+        .date_time(timestamp)
+        .format("%m-%d %H:%M")
+        .to_string()
 }
 
 pub fn find_aerodrome_by_code(aerodromes: &[Aerodrome], code: &str) -> Option<Aerodrome> {
@@ -45,10 +45,7 @@ pub fn filter_and_prioritize_aerodromes(aerodromes: &[Aerodrome], input: &str) -
 
     let filtered_aerodromes: Vec<Aerodrome> = aerodromes
         .iter()
-        .filter(|a| {
-            a.name.to_lowercase().contains(&input.to_lowercase())
-                || a.code.to_lowercase().contains(&input.to_lowercase())
-        })
+        .filter(|a| a.matches_search(input))
         .cloned()
         .collect();
 
@@ -1,9 +1,10 @@
 use cucumber::World;
-use flyconomy::simulation::Simulation;
+use flyconomy::{
+    model::{commands::CommandError, events::SimulationEvent},
+    simulation::Simulation,
+};
 pub mod steps;
 use cucumber::WriterExt;
-use std::boxed::Box;
-use std::error::Error;
 use std::result::Result;
 
 #[derive(Debug, World)]
@@ -13,7 +14,7 @@ pub struct BddWorld {
     starting_landing_rights_count: usize,
     starting_plane_count: usize,
     starting_cash: f64,
-    last_result: Result<Option<String>, Box<dyn Error>>,
+    last_result: Result<Vec<SimulationEvent>, CommandError>,
     last_plane_type: String,
     last_base_id: u64,
     landing_rights_id: u64,
@@ -28,7 +29,7 @@ impl Default for BddWorld {
             starting_landing_rights_count: 0,
             starting_plane_count: 0,
             starting_cash: 0.0,
-            last_result: Ok(None),
+            last_result: Ok(vec![]),
             last_plane_type: String::new(),
             last_base_id: 0,
             landing_rights_id: 0,
@@ -1,9 +1,9 @@
 @simulation
 Feature: Checking simulation properties
 
-  Scenario: Running the simulation until timestamp 1000
-    Given the simulation is at timestamp 1000
-    Then the simulation timestamp should be less than 1001
+  Scenario: Running the simulation until timestamp 60000
+    Given the simulation is at timestamp 60000
+    Then the simulation timestamp should be less than 60001
 
   Scenario: Checking cash amount
     Given the simulation is running
@@ -2,6 +2,7 @@ use crate::BddWorld;
 use approx::assert_relative_eq;
 use cucumber::{given, then, when};
 use flyconomy::model::commands::Command;
+use flyconomy::model::commands::CommandError;
 use flyconomy::model::commands::CreateBaseCommand;
 use flyconomy::model::commands::CreateBaseError;
 use flyconomy::model::Aerodrome;
@@ -94,12 +95,14 @@ async fn i_try_to_create_another_base_at(w: &mut BddWorld, aerodrome_name: Strin
 async fn i_should_get_a_base_already_exists_error(w: &mut BddWorld) {
     match &w.last_result {
         Err(err) => {
-            if let Some(specific_err) = err.downcast_ref::<CreateBaseError>() {
-                if !matches!(*specific_err, CreateBaseError::BaseAlreadyExists { .. }) {
-                    panic!("Expected a BaseAlreadyExists error but got a different error");
-                }
-            } else {
-                panic!("Expected a BaseAlreadyExists error but got a different error type");
+            let is_base_already_exists = err.violations().iter().any(|violation| {
+                matches!(
+                    violation,
+                    CommandError::CreateBase(CreateBaseError::BaseAlreadyExists { .. })
+                )
+            });
+            if !is_base_already_exists {
+                panic!("Expected a BaseAlreadyExists error but got {:?}", err);
             }
         }
         _ => panic!("Expected an error but got a successful result"),
@@ -1,21 +1,20 @@
 use crate::BddWorld;
 use cucumber::then;
-use flyconomy::model::commands::{SellLandingRightsError, SellPlaneError};
+use flyconomy::model::commands::{CommandError, SellLandingRightsError, SellPlaneError};
 
 #[then("I should get a NotExist error")]
 async fn i_should_get_a_not_exist_error(w: &mut BddWorld) {
     match &w.last_result {
         Err(err) => {
-            if let Some(specific_err) = err.downcast_ref::<SellPlaneError>() {
-                if !matches!(*specific_err, SellPlaneError::NotExist) {
-                    panic!("Expected a NotExist error but got a different error");
-                }
-            } else if let Some(specific_err) = err.downcast_ref::<SellLandingRightsError>() {
-                if !matches!(*specific_err, SellLandingRightsError::NotExist) {
-                    panic!("Expected a NotExist error, but got a different type");
-                }
-            } else {
-                panic!("Expected a NotExist error but got a different error type");
+            let is_not_exist = err.violations().iter().any(|violation| {
+                matches!(
+                    violation,
+                    CommandError::SellPlane(SellPlaneError::NotExist)
+                        | CommandError::SellLandingRights(SellLandingRightsError::NotExist)
+                )
+            });
+            if !is_not_exist {
+                panic!("Expected a NotExist error but got {:?}", err);
             }
         }
         _ => panic!("Expected an error but got a successful result"),
@@ -1,7 +1,7 @@
 use crate::BddWorld;
 use cucumber::{given, then, when};
 use flyconomy::model::commands::{
-    BuyPlaneCommand, Command, ScheduleFlightCommand, ScheduleFlightError,
+    BuyPlaneCommand, Command, CommandError, ScheduleFlightCommand, ScheduleFlightError,
 };
 use flyconomy::model::AirPlane;
 use flyconomy::model::PlaneType;
@@ -95,18 +95,22 @@ async fn i_should_get_error_message_if_the_flight_wasnt_scheduled(
     error_message: String,
 ) {
     if let Err(err) = &w.last_result {
-        if let Some(specific_err) = err.downcast_ref::<ScheduleFlightError>() {
-            println!("specific_err: {:?}", specific_err);
-            let actual_message = match *specific_err {
-                ScheduleFlightError::AirplaneInUse => "Airplane is already in use",
-                ScheduleFlightError::DistanceBeyondRange => {
+        let actual_messages: Vec<&str> = err
+            .violations()
+            .into_iter()
+            .map(|violation| match violation {
+                CommandError::ScheduleFlight(ScheduleFlightError::AirplaneInUse) => {
+                    "Airplane is already in use"
+                }
+                CommandError::ScheduleFlight(ScheduleFlightError::DistanceBeyondRange) => {
                     "Distance is beyond the airplane's range"
                 }
-                _ => panic!("Unexpected error when scheduling the flight"),
-            };
-            assert_eq!(actual_message, error_message);
-        } else {
-            panic!("Expected a ScheduleFlightError but got a different error type");
-        }
+                _ => panic!(
+                    "Unexpected error when scheduling the flight: {:?}",
+                    violation
+                ),
+            })
+            .collect();
+        assert!(actual_messages.contains(&error_message.as_str()));
     }
 }
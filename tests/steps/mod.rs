@@ -1,6 +1,6 @@
 use crate::BddWorld;
 use cucumber::{given, then};
-use flyconomy::model::Timestamp;
+use flyconomy::model::{analytics::MetricsStore, Timestamp};
 use std::time::Duration;
 
 pub mod base_management;
@@ -16,11 +16,7 @@ async fn the_simulation_is_at_timestamp(w: &mut BddWorld, timestamp: Timestamp)
         "The simulation timestamp is already over {}",
         timestamp
     );
-    w.simulation.update(Duration::from_millis(
-        (timestamp - w.simulation.environment.timestamp)
-            .try_into()
-            .unwrap(),
-    ));
+    w.simulation.run_until(timestamp);
 }
 
 #[then(regex = r"^the simulation timestamp should be less than (\d+)$")]
@@ -98,6 +94,7 @@ async fn i_have_a_starting_cash_of(w: &mut BddWorld, cash: f64) {
         .environment
         .company_finances
         .add_income(0, cash);
+    w.simulation.environment.metrics = MetricsStore::new(cash);
 }
 
 #[then("I should get an InsufficientFunds error")]